@@ -16,13 +16,16 @@ use ark_std::str::FromStr;
 use ark_std::test_rng;
 
 use groth_sahai::{
+    col_vec_to_vec,
     prover::{
-        batch_commit_G1, batch_commit_G2, batch_commit_scalar_to_B1, batch_commit_scalar_to_B2,
-        CProof, Commit1, Commit2, Provable,
+        batch_commit_G1, batch_commit_G1_fast, batch_commit_G2, batch_commit_scalar_to_B1,
+        batch_commit_scalar_to_B2, prove_ppe, prove_quad_cached, prove_system, CProof, Commit1,
+        Commit2, LinearMapCache, Provable, SystemWitness, SCALAR_COMMIT_BATCH_THRESHOLD,
     },
-    statement::PPE,
-    verifier::Verifiable,
-    AbstractCrs, Com1, Mat, Matrix, B1, CRS,
+    statement::{EquationSystem, QuadEqu, PPE},
+    vec_to_col_vec,
+    verifier::{batch_verify_ppe, verify_ppe_bool, verify_system, ProofWithStatement, Verifiable},
+    AbstractCrs, CRSPrecomp, Com1, Com2, ComT, Mat, Matrix, B1, B2, BT, CRS,
 };
 
 type G1Projective = <F as Pairing>::G1;
@@ -321,6 +324,48 @@ fn bench_large_batch_commit_G1(c: &mut Criterion) {
     });
 }
 
+// Takes several minutes, so it only runs if `GS_BENCH_HUGE` is set
+fn bench_huge_batch_commit_G1(c: &mut Criterion) {
+    if std::env::var_os("GS_BENCH_HUGE").is_none() {
+        return;
+    }
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let crs = CRS::<F>::generate_crs(&mut rng);
+
+    let m = 10_000;
+    let mut xvars: Vec<G1Affine> = Vec::with_capacity(m);
+    for _ in 0..m {
+        xvars.push(crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine());
+    }
+
+    // Run with `--features parallel` to compare against the concurrent path
+    c.bench_function(&format!("commit {} G1", m), |bench| {
+        bench.iter(|| {
+            let _ = batch_commit_G1(&xvars, &crs, &mut rng);
+        });
+    });
+}
+
+fn bench_large_batch_commit_G1_fast(c: &mut Criterion) {
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let crs = CRS::<F>::generate_crs(&mut rng);
+
+    let m = 334;
+    let mut xvars: Vec<G1Affine> = Vec::with_capacity(m);
+    for _ in 0..m {
+        xvars.push(crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine());
+    }
+    let precomp = CRSPrecomp::<F>::new(&crs, m);
+
+    c.bench_function(&format!("commit {} G1 (precomputed CRS)", m), |bench| {
+        bench.iter(|| {
+            let _ = batch_commit_G1_fast(&xvars, &precomp, &mut rng);
+        });
+    });
+}
+
 fn bench_small_batch_commit_G2(c: &mut Criterion) {
     std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
     let mut rng = test_rng();
@@ -385,6 +430,58 @@ fn bench_large_batch_commit_scalar_to_B1(c: &mut Criterion) {
     });
 }
 
+// Baseline for `batch_commit_scalar_to_B1`, computing i_1'(x) + r u_1 entry-wise without
+// multi-scalar multiplication
+fn bench_large_batch_commit_scalar_to_B1_naive(c: &mut Criterion) {
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let crs = CRS::<F>::generate_crs(&mut rng);
+
+    let m = 334;
+    let mut scalar_xvars: Vec<Fr> = Vec::with_capacity(m);
+    for _ in 0..m {
+        scalar_xvars.push(Fr::rand(&mut rng));
+    }
+
+    c.bench_function(&format!("commit {} scalar to B1 (naive)", m), |bench| {
+        bench.iter(|| {
+            let _: Vec<Com1<F>> = Com1::<F>::batch_scalar_linear_map(&scalar_xvars, &crs)
+                .into_iter()
+                .map(|slin| slin + crs.u[0].scalar_mul(&Fr::rand(&mut rng)))
+                .collect();
+        });
+    });
+}
+
+// Around SCALAR_COMMIT_BATCH_THRESHOLD, where committing switches from a 2-term MSM per
+// commitment to fixed-base tables; compare the time per scalar on each curve to pick the threshold
+fn bench_threshold_batch_commit_scalar_to_B1_on<E: Pairing>(c: &mut Criterion, curve: &str) {
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let crs = CRS::<E>::generate_crs(&mut rng);
+
+    for m in [
+        SCALAR_COMMIT_BATCH_THRESHOLD / 2,
+        SCALAR_COMMIT_BATCH_THRESHOLD - 1,
+        SCALAR_COMMIT_BATCH_THRESHOLD,
+        2 * SCALAR_COMMIT_BATCH_THRESHOLD,
+    ] {
+        let scalar_xvars: Vec<E::ScalarField> =
+            (0..m).map(|_| E::ScalarField::rand(&mut rng)).collect();
+
+        c.bench_function(&format!("commit {} scalar to B1 ({})", m, curve), |bench| {
+            bench.iter(|| {
+                let _ = batch_commit_scalar_to_B1(&scalar_xvars, &crs, &mut rng);
+            });
+        });
+    }
+}
+
+fn bench_threshold_batch_commit_scalar_to_B1(c: &mut Criterion) {
+    bench_threshold_batch_commit_scalar_to_B1_on::<F>(c, "BLS12-381");
+    bench_threshold_batch_commit_scalar_to_B1_on::<ark_bn254::Bn254>(c, "BN254");
+}
+
 fn bench_small_batch_commit_scalar_to_B2(c: &mut Criterion) {
     std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
     let mut rng = test_rng();
@@ -497,6 +594,57 @@ fn bench_large_PPE_proof(c: &mut Criterion) {
     );
 }
 
+// Repeatedly proves a quadratic equation with many constants, with and without caching their
+// images under the CRS-dependent linear maps
+fn bench_quad_proof_cached(c: &mut Criterion) {
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let crs = CRS::<F>::generate_crs(&mut rng);
+
+    let (m, n) = (64, 64);
+    let scalar_xvars: Vec<Fr> = (0..m).map(|_| Fr::rand(&mut rng)).collect();
+    let scalar_yvars: Vec<Fr> = (0..n).map(|_| Fr::rand(&mut rng)).collect();
+    let xcoms: Commit1<F> = batch_commit_scalar_to_B1(&scalar_xvars, &crs, &mut rng);
+    let ycoms: Commit2<F> = batch_commit_scalar_to_B2(&scalar_yvars, &crs, &mut rng);
+    let equ = QuadEqu::<F> {
+        a_consts: (0..n).map(|_| Fr::rand(&mut rng)).collect(),
+        b_consts: (0..m).map(|_| Fr::rand(&mut rng)).collect(),
+        gamma: vec![vec![Fr::zero(); n]; m],
+        // NOTE: dummy variable for this bench
+        target: Fr::rand(&mut rng),
+    };
+    let cache = LinearMapCache::for_quad(&equ, &crs);
+
+    c.bench_function(
+        &format!("prove quadratic equation with {} x vars, {} y vars", m, n),
+        |bench| {
+            bench.iter(|| {
+                let _ = equ.prove(&scalar_xvars, &scalar_yvars, &xcoms, &ycoms, &crs, &mut rng);
+            });
+        },
+    );
+    c.bench_function(
+        &format!(
+            "prove quadratic equation with {} x vars, {} y vars (cached linear maps)",
+            m, n
+        ),
+        |bench| {
+            bench.iter(|| {
+                let _ = prove_quad_cached(
+                    &equ,
+                    &cache,
+                    &scalar_xvars,
+                    &scalar_yvars,
+                    &xcoms,
+                    &ycoms,
+                    &crs,
+                    &mut rng,
+                );
+            });
+        },
+    );
+}
+
 fn bench_small_PPE_verify(c: &mut Criterion) {
     std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
     let mut rng = test_rng();
@@ -528,6 +676,140 @@ fn bench_small_PPE_verify(c: &mut Criterion) {
     });
 }
 
+// Verifies the same equation as `bench_small_PPE_verify` with a full pairing per term, as a
+// baseline for the shared Miller loop in `verify_ppe`.
+fn bench_small_PPE_verify_naive(c: &mut Criterion) {
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let crs = CRS::<F>::generate_crs(&mut rng);
+
+    let xvars: Vec<G1Affine> = vec![
+        crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(),
+    ];
+    let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+
+    let equ: PPE<F> = PPE::<F> {
+        a_consts: vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()],
+        b_consts: vec![
+            crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
+            crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ],
+        gamma: vec![vec![Fr::one()], vec![Fr::zero()]],
+        // NOTE: dummy variable for this bench
+        target: GT::rand(&mut rng),
+    };
+
+    let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+    let (xcoms, ycoms, pf) = (&proof.xcoms.coms, &proof.ycoms.coms, &proof.equ_proofs[0]);
+
+    c.bench_function(
+        "verify PPE equation with 2 G1 vars, 1 G2 var (naive pairings)",
+        |bench| {
+            bench.iter(|| {
+                let lin_a_com_y =
+                    ComT::<F>::pairing_sum(&Com1::<F>::batch_linear_map(&equ.a_consts), ycoms);
+                let com_x_lin_b =
+                    ComT::<F>::pairing_sum(xcoms, &Com2::<F>::batch_linear_map(&equ.b_consts));
+                let stmt_com_y = vec_to_col_vec(ycoms).left_mul(&equ.gamma, false);
+                let com_x_stmt_com_y = ComT::<F>::pairing_sum(xcoms, &col_vec_to_vec(&stmt_com_y));
+                let lhs = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
+                let rhs = ComT::<F>::linear_map_PPE(&equ.target)
+                    + ComT::<F>::pairing_sum(&crs.u, &pf.pi)
+                    + ComT::<F>::pairing_sum(&pf.theta, &crs.v);
+                let _ = lhs == rhs;
+            });
+        },
+    );
+}
+
+fn bench_system_verify(c: &mut Criterion) {
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let crs = CRS::<F>::generate_crs(&mut rng);
+
+    // 50 pairing-product equations e(X_i, Y_i) = t_i over shared commitments
+    let n = 50;
+    let witness = SystemWitness::<F> {
+        xvars: (0..n)
+            .map(|_| crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine())
+            .collect(),
+        yvars: (0..n)
+            .map(|_| crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine())
+            .collect(),
+        scalar_xvars: vec![],
+        scalar_yvars: vec![],
+    };
+    let mut system = EquationSystem::<F>::new();
+    for i in 0..n {
+        let mut gamma = vec![vec![Fr::zero(); n]; n];
+        gamma[i][i] = Fr::one();
+        system.push(PPE::<F> {
+            a_consts: vec![G1Affine::zero(); n],
+            b_consts: vec![G2Affine::zero(); n],
+            gamma,
+            target: F::pairing(witness.xvars[i], witness.yvars[i]),
+        });
+    }
+    let proof = prove_system(&system, &witness, &crs, &mut rng);
+
+    c.bench_function(&format!("verify {} equation system", n), |bench| {
+        bench.iter(|| verify_system(&system, &proof, &crs));
+    });
+    #[cfg(feature = "parallel")]
+    c.bench_function(
+        &format!("verify {} equation system in parallel", n),
+        |bench| {
+            bench.iter(|| groth_sahai::verifier::verify_system_parallel(&system, &proof, &crs));
+        },
+    );
+}
+
+fn bench_batch_PPE_verify(c: &mut Criterion) {
+    std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+    let mut rng = test_rng();
+    let crs = CRS::<F>::generate_crs(&mut rng);
+
+    let n = 100;
+    let mut proofs: Vec<ProofWithStatement<F>> = Vec::with_capacity(n);
+    for _ in 0..n {
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()],
+            b_consts: vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()],
+            gamma: vec![vec![Fr::one()]],
+            // NOTE: dummy variable for this bench
+            target: GT::rand(&mut rng),
+        };
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        proofs.push(ProofWithStatement {
+            equ,
+            xcoms: xcoms.coms,
+            ycoms: ycoms.coms,
+            proof,
+        });
+    }
+
+    c.bench_function(
+        &format!("verify {} PPE equations sequentially", n),
+        |bench| {
+            bench.iter(|| {
+                let _ = proofs
+                    .iter()
+                    .all(|p| verify_ppe_bool(&p.equ, &p.xcoms, &p.ycoms, &p.proof, &crs));
+            });
+        },
+    );
+    c.bench_function(&format!("batch verify {} PPE equations", n), |bench| {
+        bench.iter(|| {
+            let _ = batch_verify_ppe(&proofs, &crs, &mut rng);
+        });
+    });
+}
+
 fn bench_large_PPE_verify(c: &mut Criterion) {
     std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
     let mut rng = test_rng();
@@ -620,6 +902,7 @@ criterion_group! {
         bench_small_batch_commit_G1,
         bench_small_batch_commit_G2,
         bench_small_batch_commit_scalar_to_B1,
+        bench_threshold_batch_commit_scalar_to_B1,
         bench_small_batch_commit_scalar_to_B2,
 }
 
@@ -628,11 +911,20 @@ criterion_group! {
     config = Criterion::default().sample_size(10).measurement_time(Duration::new(20, 0));
     targets =
         bench_large_batch_commit_G1,
+        bench_large_batch_commit_G1_fast,
         bench_large_batch_commit_G2,
         bench_large_batch_commit_scalar_to_B1,
+        bench_large_batch_commit_scalar_to_B1_naive,
         bench_large_batch_commit_scalar_to_B2
 }
 
+criterion_group! {
+    name = huge_commit;
+    config = Criterion::default().sample_size(10).measurement_time(Duration::new(60, 0));
+    targets =
+        bench_huge_batch_commit_G1
+}
+
 criterion_group! {
     name = small_prove;
     config = Criterion::default().sample_size(200);
@@ -643,7 +935,8 @@ criterion_group! {
     name = large_prove;
     config = Criterion::default().sample_size(20).measurement_time(Duration::new(30, 0));
     targets =
-        bench_large_PPE_proof
+        bench_large_PPE_proof,
+        bench_quad_proof_cached
 }
 
 criterion_group! {
@@ -651,6 +944,14 @@ criterion_group! {
     config = Criterion::default().sample_size(200).measurement_time(Duration::new(20, 0));
     targets =
         bench_small_PPE_verify,
+        bench_small_PPE_verify_naive,
+}
+criterion_group! {
+    name = batch_ver;
+    config = Criterion::default().sample_size(10).measurement_time(Duration::new(30, 0));
+    targets =
+        bench_batch_PPE_verify,
+        bench_system_verify
 }
 criterion_group! {
     name = large_ver;
@@ -666,8 +967,10 @@ criterion_main!(
     //    G1_arith
     small_commit,
     large_commit,
+    huge_commit,
     small_prove,
     large_prove,
     small_ver,
+    batch_ver,
     //    large_ver
 );
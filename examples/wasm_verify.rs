@@ -0,0 +1,51 @@
+//! Constructs a CRS, commits to a witness, proves a pairing-product equation and verifies it,
+//! using only functionality that compiles to `wasm32-unknown-unknown`:
+//!
+//! ```bash
+//! rustup target add wasm32-unknown-unknown
+//! cargo build --example wasm_verify --target wasm32-unknown-unknown --no-default-features
+//! ```
+//!
+//! The crate never draws entropy itself; every randomized function takes an `Rng`. In a browser,
+//! seed a `ChaCha20Rng` from `getrandom` (with its `js` feature, which uses
+//! `crypto.getRandomValues`) and pass it in. To call this from JavaScript, add `wasm-bindgen`
+//! to your own crate and annotate `verify_in_browser` with `#[wasm_bindgen]`.
+
+use ark_bls12_381::{Bls12_381 as F, Fr};
+use ark_ec::{pairing::Pairing, CurveGroup};
+use ark_std::UniformRand;
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+use groth_sahai::prover::{batch_commit_G1, batch_commit_G2, prove_ppe};
+use groth_sahai::statement::PPE;
+use groth_sahai::verifier::verify_ppe;
+use groth_sahai::CRS;
+
+/// Proves and verifies `e(X_1, b_1) * e(a_1, Y_1) = t` for a random witness, using `seed`
+/// (e.g. 32 bytes from `getrandom`) as the only source of randomness.
+pub fn verify_in_browser(seed: [u8; 32]) -> bool {
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    let crs = CRS::<F>::generate_hiding(&mut rng);
+
+    let xvars = vec![(crs.g1_gen * Fr::rand(&mut rng)).into_affine()];
+    let yvars = vec![(crs.g2_gen * Fr::rand(&mut rng)).into_affine()];
+    let a_consts = vec![(crs.g1_gen * Fr::rand(&mut rng)).into_affine()];
+    let b_consts = vec![(crs.g2_gen * Fr::rand(&mut rng)).into_affine()];
+    let target = F::pairing(xvars[0], b_consts[0]) + F::pairing(a_consts[0], yvars[0]);
+    let equ = PPE::<F> {
+        a_consts,
+        b_consts,
+        gamma: vec![vec![Fr::from(0u64)]],
+        target,
+    };
+
+    let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+    let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+    let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+    verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs).is_ok()
+}
+
+fn main() {
+    // Only for running the example natively; use real entropy in production
+    assert!(verify_in_browser([7u8; 32]));
+}
@@ -0,0 +1,442 @@
+//! Contains the aggregation of proofs for many instances of the same pairing-product equation.
+//!
+//! Given proofs `(π_i, θ_i)` for `N` equations that share `A`, `B` and `Γ` and only differ in
+//! their targets `t_i`, the aggregator hashes the [`CRS`], the shared constants, every target and
+//! every commitment with a pluggable [`Digest`] to derive challenges `ρ_i`, and publishes the
+//! commitments together with the single proof `π = Σ ρ_i π_i`, `θ = Σ ρ_i θ_i`. No witness is
+//! needed to aggregate. Since the verification equation is linear in the proof, the aggregate
+//! satisfies the random linear combination of the instances' verification equations:
+//!
+//! `ι_1(A)·(Σ ρ_i D_i) + Σ ρ_i C_i·(ι_2(B) + Γ D_i) = ι_T(Σ ρ_i t_i) + u·π + θ·v`,
+//!
+//! which costs `|A| + N |B| + 4` pairing terms instead of `N (|A| + |B| + 4)` for verifying the
+//! proofs separately.
+//!
+//! The challenges are fixed by the commitments and the targets before the combination is formed,
+//! so (modelling the digest as a random oracle) an aggregate containing an unsatisfied instance
+//! is rejected except with negligible probability. As a consequence, a failing aggregate does not
+//! tell which instance is unsatisfied.
+
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_ec::CurveGroup;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{vec::Vec, Zero};
+use digest::Digest;
+
+use crate::data_structures::{Com1, Com2, ComT, B1, B2, BT};
+use crate::fiat_shamir::absorb;
+use crate::generator::CRS;
+use crate::statement::{EquType, PPE};
+use crate::verifier::{
+    check_pairing_product, check_points_valid, stmt_com_y, ProofWithStatement, VerificationError,
+};
+
+// Domain separator for the transcript of an aggregate proof
+const DOMAIN: &[u8] = b"groth-sahai/aggregate/ppe/v1";
+
+/// A single proof for many instances of the same pairing-product equation, as produced by
+/// [`aggregate_ppe`].
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AggregateProof<E: Pairing> {
+    /// The `X` commitments of every instance, in order.
+    pub xcoms: Vec<Vec<Com1<E>>>,
+    /// The `Y` commitments of every instance, in order.
+    pub ycoms: Vec<Vec<Com2<E>>>,
+    pub pi: Vec<Com2<E>>,
+    pub theta: Vec<Com1<E>>,
+}
+
+// Checks that two equations only differ in their targets.
+fn same_structure<E: Pairing>(lhs: &PPE<E>, rhs: &PPE<E>) -> bool {
+    lhs.a_consts == rhs.a_consts && lhs.b_consts == rhs.b_consts && lhs.gamma == rhs.gamma
+}
+
+// Checks that the commitments of one instance match the shape of `equ`.
+fn check_instance_dims<E: Pairing>(equ: &PPE<E>, xcoms: &[Com1<E>], ycoms: &[Com2<E>]) -> bool {
+    xcoms.len() == equ.b_consts.len()
+        && xcoms.len() == equ.gamma.len()
+        && ycoms.len() == equ.a_consts.len()
+        && equ.gamma.iter().all(|row| row.len() == ycoms.len())
+}
+
+// Derives one challenge per instance from the full transcript of an aggregate proof.
+fn challenges<'a, D, E>(
+    crs: &CRS<E>,
+    equ: &PPE<E>,
+    instances: impl ExactSizeIterator<Item = (&'a PairingOutput<E>, &'a [Com1<E>], &'a [Com2<E>])>,
+) -> Vec<E::ScalarField>
+where
+    D: Digest,
+    E: Pairing,
+{
+    let mut hasher = D::new();
+    hasher.update(DOMAIN);
+    absorb(&mut hasher, crs);
+    absorb(&mut hasher, &equ.a_consts);
+    absorb(&mut hasher, &equ.b_consts);
+    absorb(&mut hasher, &equ.gamma);
+    let count = instances.len() as u64;
+    hasher.update(count.to_le_bytes());
+    for (target, xcoms, ycoms) in instances {
+        absorb(&mut hasher, target);
+        absorb(&mut hasher, &xcoms.to_vec());
+        absorb(&mut hasher, &ycoms.to_vec());
+    }
+    let seed = hasher.finalize();
+
+    (0..count)
+        .map(|i| {
+            let mut hasher = D::new();
+            hasher.update(&seed);
+            hasher.update(i.to_le_bytes());
+            E::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+        })
+        .collect()
+}
+
+/// Aggregates proofs for instances of the same pairing-product equation, hashing the transcript
+/// with `D`.
+///
+/// Returns `None` if `instances` is empty, if the equations differ in anything but their targets,
+/// or if any instance's commitments or proof do not match the shape of the equation. The
+/// individual proofs are not verified; an aggregate containing an invalid proof simply fails
+/// [`verify_aggregate`].
+pub fn aggregate_ppe<D, E>(
+    instances: &[ProofWithStatement<E>],
+    crs: &CRS<E>,
+) -> Option<AggregateProof<E>>
+where
+    D: Digest,
+    E: Pairing,
+{
+    let equ = &instances.first()?.equ;
+    let well_formed = instances.iter().all(|inst| {
+        same_structure(equ, &inst.equ)
+            && check_instance_dims(equ, &inst.xcoms, &inst.ycoms)
+            && inst.proof.equ_type == EquType::PairingProduct
+            && inst.proof.pi.len() == 2
+            && inst.proof.theta.len() == 2
+    });
+    if !well_formed {
+        return None;
+    }
+
+    let rho = challenges::<D, E>(
+        crs,
+        equ,
+        instances
+            .iter()
+            .map(|inst| (&inst.equ.target, &inst.xcoms[..], &inst.ycoms[..])),
+    );
+    // One challenge per instance, so the linear combinations are always defined
+    let pi = (0..2)
+        .map(|j| {
+            let pis: Vec<Com2<E>> = instances.iter().map(|inst| inst.proof.pi[j]).collect();
+            Com2::<E>::linear_combination(&pis, &rho).unwrap()
+        })
+        .collect();
+    let theta = (0..2)
+        .map(|j| {
+            let thetas: Vec<Com1<E>> = instances.iter().map(|inst| inst.proof.theta[j]).collect();
+            Com1::<E>::linear_combination(&thetas, &rho).unwrap()
+        })
+        .collect();
+
+    Some(AggregateProof {
+        xcoms: instances.iter().map(|inst| inst.xcoms.clone()).collect(),
+        ycoms: instances.iter().map(|inst| inst.ycoms.clone()).collect(),
+        pi,
+        theta,
+    })
+}
+
+/// Verifies an aggregate proof `agg` for the pairing-product equations `equs`, produced by
+/// [`aggregate_ppe`] with the same digest `D`, where `equs[i]` is the equation of the `i`-th
+/// aggregated instance.
+///
+/// All equations must share `A`, `B` and `Γ`. Since a failing aggregate cannot be attributed to
+/// a single instance, a mismatch is always reported at `equation_index` 0.
+pub fn verify_aggregate<D, E>(
+    equs: &[PPE<E>],
+    agg: &AggregateProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError>
+where
+    D: Digest,
+    E: Pairing,
+{
+    let equ = equs.first().ok_or(VerificationError::DimensionMismatch)?;
+    if equs.len() != agg.xcoms.len()
+        || equs.len() != agg.ycoms.len()
+        || agg.pi.len() != 2
+        || agg.theta.len() != 2
+        || !equs.iter().all(|other| same_structure(equ, other))
+        || !agg
+            .xcoms
+            .iter()
+            .zip(agg.ycoms.iter())
+            .all(|(xcoms, ycoms)| check_instance_dims(equ, xcoms, ycoms))
+    {
+        return Err(VerificationError::DimensionMismatch);
+    }
+    check_points_valid(
+        agg.xcoms.iter().flatten().chain(agg.theta.iter()),
+        agg.ycoms.iter().flatten().chain(agg.pi.iter()),
+    )?;
+
+    let rho = challenges::<D, E>(
+        crs,
+        equ,
+        equs.iter()
+            .zip(agg.xcoms.iter().zip(agg.ycoms.iter()))
+            .map(|(equ, (xcoms, ycoms))| (&equ.target, &xcoms[..], &ycoms[..])),
+    );
+
+    // i_1(A) * (sum_i rho_i D_i)
+    let mut g1_terms: Vec<Com1<E>> = Com1::<E>::batch_linear_map(&equ.a_consts);
+    let mut g2_terms: Vec<Com2<E>> = vec![Com2::<E>::zero(); equ.a_consts.len()];
+    // sum_i (rho_i C_i) * (i_2(B) + Gamma D_i), with rho_i C_i normalized in one batch
+    let lin_b = Com2::<E>::batch_linear_map(&equ.b_consts);
+    let mut scaled_x: Vec<E::G1> = Vec::new();
+    let mut target = PairingOutput::<E>::zero();
+    for (((equ, xcoms), ycoms), rho) in equs.iter().zip(&agg.xcoms).zip(&agg.ycoms).zip(&rho) {
+        for (acc, d) in g2_terms.iter_mut().zip(ycoms.iter()) {
+            *acc += d.scalar_mul(rho);
+        }
+        let gamma_d = stmt_com_y(&equ.gamma, ycoms);
+        for ((c, b), gd) in xcoms.iter().zip(lin_b.iter()).zip(gamma_d) {
+            scaled_x.push(c.0 * *rho);
+            scaled_x.push(c.1 * *rho);
+            g2_terms.push(*b + gd);
+        }
+        target += equ.target * *rho;
+    }
+    g1_terms.extend(
+        E::G1::normalize_batch(&scaled_x)
+            .chunks(2)
+            .map(|x| Com1::<E>(x[0], x[1])),
+    );
+    // - (u * pi + theta * v)
+    g1_terms.extend(crs.u.iter().map(|u| -*u));
+    g2_terms.extend(agg.pi.iter().copied());
+    g1_terms.extend(agg.theta.iter().map(|theta| -*theta));
+    g2_terms.extend(crs.v.iter().copied());
+
+    if check_pairing_product(&g1_terms, &g2_terms, &ComT::<E>::linear_map_PPE(&target)) {
+        Ok(())
+    } else {
+        Err(VerificationError::PairingMismatch { equation_index: 0 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::CurveGroup;
+    use ark_ff::UniformRand;
+    use ark_std::ops::Mul;
+    use ark_std::rand::Rng;
+    use ark_std::test_rng;
+
+    use super::*;
+    use crate::generator::AbstractCrs;
+    use crate::prover::{batch_commit_G1, batch_commit_G2, prove_ppe};
+    use crate::verifier::verify_ppe;
+    use sha2::Sha256;
+
+    type G1Affine = <F as Pairing>::G1Affine;
+    type G2Affine = <F as Pairing>::G2Affine;
+    type Fr = <F as Pairing>::ScalarField;
+
+    // Proves `count` instances of e(X_1, b_1) * e(X_2, b_2) * e(a_1, Y_1) * e(X_2, Y_1)^g = t_i
+    // for fresh variables, where instance `i` is unsatisfied if `unsatisfied == Some(i)`
+    fn instances(
+        crs: &CRS<F>,
+        count: usize,
+        unsatisfied: Option<usize>,
+        rng: &mut impl Rng,
+    ) -> Vec<ProofWithStatement<F>> {
+        let a_consts = vec![crs.g1_gen.mul(Fr::rand(rng)).into_affine()];
+        let b_consts = vec![
+            crs.g2_gen.mul(Fr::rand(rng)).into_affine(),
+            crs.g2_gen.mul(Fr::rand(rng)).into_affine(),
+        ];
+        let gamma = vec![vec![Fr::zero()], vec![Fr::rand(rng)]];
+
+        (0..count)
+            .map(|i| {
+                let xvars: Vec<G1Affine> = vec![
+                    crs.g1_gen.mul(Fr::rand(rng)).into_affine(),
+                    crs.g1_gen.mul(Fr::rand(rng)).into_affine(),
+                ];
+                let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(rng)).into_affine()];
+                let mut target = F::pairing(xvars[0], b_consts[0])
+                    + F::pairing(xvars[1], b_consts[1])
+                    + F::pairing(a_consts[0], yvars[0])
+                    + F::pairing(xvars[1], yvars[0].mul(gamma[1][0]).into_affine());
+                if unsatisfied == Some(i) {
+                    target += crs.gt_gen;
+                }
+                let equ = PPE::<F> {
+                    a_consts: a_consts.clone(),
+                    b_consts: b_consts.clone(),
+                    gamma: gamma.clone(),
+                    target,
+                };
+                let xcoms = batch_commit_G1(&xvars, crs, rng);
+                let ycoms = batch_commit_G2(&yvars, crs, rng);
+                let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, crs, rng);
+                ProofWithStatement {
+                    equ,
+                    xcoms: xcoms.coms,
+                    ycoms: ycoms.coms,
+                    proof,
+                }
+            })
+            .collect()
+    }
+
+    fn equations(instances: &[ProofWithStatement<F>]) -> Vec<PPE<F>> {
+        instances.iter().map(|inst| inst.equ.clone()).collect()
+    }
+
+    #[test]
+    fn test_aggregate_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let insts = instances(&crs, 8, None, &mut rng);
+        let equs = equations(&insts);
+
+        let agg = aggregate_ppe::<Sha256, F>(&insts, &crs).unwrap();
+        assert_eq!(verify_aggregate::<Sha256, F>(&equs, &agg, &crs), Ok(()));
+
+        // Survives serialization
+        let mut bytes = Vec::new();
+        agg.serialize_compressed(&mut bytes).unwrap();
+        let agg_de = AggregateProof::<F>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(verify_aggregate::<Sha256, F>(&equs, &agg_de, &crs), Ok(()));
+
+        // Binds the targets and the order of the instances
+        let mut other_equs = equs.clone();
+        other_equs[5].target += crs.gt_gen;
+        assert!(verify_aggregate::<Sha256, F>(&other_equs, &agg, &crs).is_err());
+        let mut swapped = equs.clone();
+        swapped.swap(0, 1);
+        assert!(verify_aggregate::<Sha256, F>(&swapped, &agg, &crs).is_err());
+        assert_eq!(
+            verify_aggregate::<Sha256, F>(&equs[1..], &agg, &crs),
+            Err(VerificationError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn test_aggregate_rejects_unsatisfied_instance() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let insts = instances(&crs, 8, Some(3), &mut rng);
+        let equs = equations(&insts);
+        assert!(verify_ppe(
+            &insts[3].equ,
+            &insts[3].xcoms,
+            &insts[3].ycoms,
+            &insts[3].proof,
+            &crs
+        )
+        .is_err());
+
+        let agg = aggregate_ppe::<Sha256, F>(&insts, &crs).unwrap();
+        assert_eq!(
+            verify_aggregate::<Sha256, F>(&equs, &agg, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+    }
+
+    // Proves `count` instances of e(X_1, P_2) * e(X_2, -P_2) = 1, which have no Y variables,
+    // where X_1 != X_2 in instance `i` if `unsatisfied == Some(i)`
+    fn equality_instances(
+        crs: &CRS<F>,
+        count: usize,
+        unsatisfied: Option<usize>,
+        rng: &mut impl Rng,
+    ) -> Vec<ProofWithStatement<F>> {
+        (0..count)
+            .map(|i| {
+                let x = crs.g1_gen.mul(Fr::rand(rng)).into_affine();
+                let other = if unsatisfied == Some(i) {
+                    crs.g1_gen.mul(Fr::rand(rng)).into_affine()
+                } else {
+                    x
+                };
+                let xvars = vec![x, other];
+                let equ = PPE::<F>::equality_G1(crs);
+                let xcoms = batch_commit_G1(&xvars, crs, rng);
+                let ycoms = batch_commit_G2(&[], crs, rng);
+                let proof = prove_ppe(&equ, &xvars, &[], &xcoms, &ycoms, crs, rng);
+                ProofWithStatement {
+                    equ,
+                    xcoms: xcoms.coms,
+                    ycoms: ycoms.coms,
+                    proof,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aggregate_verifies_one_sided_instances() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let insts = equality_instances(&crs, 4, None, &mut rng);
+        let equs = equations(&insts);
+
+        let agg = aggregate_ppe::<Sha256, F>(&insts, &crs).unwrap();
+        assert_eq!(verify_aggregate::<Sha256, F>(&equs, &agg, &crs), Ok(()));
+    }
+
+    #[test]
+    fn test_aggregate_rejects_unsatisfied_one_sided_instance() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let insts = equality_instances(&crs, 4, Some(2), &mut rng);
+        let equs = equations(&insts);
+        assert!(verify_ppe(
+            &insts[2].equ,
+            &insts[2].xcoms,
+            &insts[2].ycoms,
+            &insts[2].proof,
+            &crs
+        )
+        .is_err());
+
+        let agg = aggregate_ppe::<Sha256, F>(&insts, &crs).unwrap();
+        assert_eq!(
+            verify_aggregate::<Sha256, F>(&equs, &agg, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+
+        // Nor does an all-zero proof show that the commitments open to equal points
+        let forged = AggregateProof::<F> {
+            pi: vec![Com2::<F>::zero(); 2],
+            theta: vec![Com1::<F>::zero(); 2],
+            ..agg
+        };
+        assert_eq!(
+            verify_aggregate::<Sha256, F>(&equs, &forged, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+    }
+
+    #[test]
+    fn test_aggregate_requires_same_structure() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let mut insts = instances(&crs, 2, None, &mut rng);
+        assert!(aggregate_ppe::<Sha256, F>(&[], &crs).is_none());
+
+        insts[1].equ.gamma[0][0] = Fr::rand(&mut rng);
+        assert!(aggregate_ppe::<Sha256, F>(&insts, &crs).is_none());
+    }
+}
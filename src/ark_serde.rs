@@ -0,0 +1,162 @@
+//! Implements [`serde`] support for the public Groth-Sahai data structures (behind the `serde`
+//! feature).
+//!
+//! Rather than exposing the internal representation of the curve points, each type is encoded as
+//! the byte string produced by its compressed [`CanonicalSerialize`] form, so that any `serde`
+//! format (e.g. JSON) carries exactly the same bytes as the arkworks serialization.
+
+use ark_ec::pairing::Pairing;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{fmt, vec::Vec};
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser, Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::data_structures::{Com1, Com2};
+use crate::generator::CRS;
+use crate::prover::EquProof;
+
+/// Serialize any arkworks-serializable value as its compressed canonical bytes.
+fn serialize_canonical<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: CanonicalSerialize,
+    S: Serializer,
+{
+    let mut bytes = Vec::with_capacity(value.compressed_size());
+    value
+        .serialize_compressed(&mut bytes)
+        .map_err(ser::Error::custom)?;
+    serializer.serialize_bytes(&bytes)
+}
+
+/// Deserialize any arkworks-deserializable value from its compressed canonical bytes.
+fn deserialize_canonical<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: CanonicalDeserialize,
+    D: Deserializer<'de>,
+{
+    let bytes = deserializer.deserialize_bytes(BytesVisitor)?;
+    T::deserialize_compressed(&bytes[..]).map_err(de::Error::custom)
+}
+
+// Accepts both native byte strings and sequences of bytes (e.g. JSON arrays).
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a canonically serialized byte string")
+    }
+
+    fn visit_bytes<Err: de::Error>(self, v: &[u8]) -> Result<Self::Value, Err> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<Err: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, Err> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element::<u8>()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+}
+
+macro_rules! impl_serde_via_canonical {
+    ($( $type:ident ),*) => {
+        $(
+            impl<E: Pairing> Serialize for $type<E> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serialize_canonical(self, serializer)
+                }
+            }
+
+            impl<'de, E: Pairing> Deserialize<'de> for $type<E> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserialize_canonical(deserializer)
+                }
+            }
+        )*
+    }
+}
+// Commit1 and Commit2 are deliberately left out: they hold the opening randomness, which would let
+// anyone open the commitments. Their `coms` serialize as sequences of Com1 and Com2.
+impl_serde_via_canonical!(Com1, Com2, CRS, EquProof);
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::CurveGroup;
+    use ark_ff::UniformRand;
+    use ark_std::ops::Mul;
+    use ark_std::test_rng;
+
+    use super::*;
+    use crate::generator::AbstractCrs;
+    use crate::prover::{batch_commit_G1, batch_commit_G2, Commit1, Commit2, Provable};
+    use crate::statement::PPE;
+
+    type Fr = <F as Pairing>::ScalarField;
+
+    #[test]
+    fn test_serde_json_round_trip() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let xvars = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let yvars = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+        let equ = PPE::<F> {
+            a_consts: vec![crs.g1_gen],
+            b_consts: vec![crs.g2_gen],
+            gamma: vec![vec![Fr::rand(&mut rng)]],
+            target: crs.gt_gen,
+        };
+        let proof: EquProof<F> = equ.prove(&xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+
+        let json = serde_json::to_string(&crs).unwrap();
+        let crs_de: CRS<F> = serde_json::from_str(&json).unwrap();
+        assert_eq!(crs.u, crs_de.u);
+        assert_eq!(crs.v, crs_de.v);
+        assert_eq!(crs.gt_gen, crs_de.gt_gen);
+
+        let json = serde_json::to_string(&xcoms.coms[0]).unwrap();
+        assert_eq!(
+            xcoms.coms[0],
+            serde_json::from_str::<Com1<F>>(&json).unwrap()
+        );
+        let json = serde_json::to_string(&ycoms.coms[0]).unwrap();
+        assert_eq!(
+            ycoms.coms[0],
+            serde_json::from_str::<Com2<F>>(&json).unwrap()
+        );
+
+        // Only the commitments are published, not their openings
+        let json = serde_json::to_string(&xcoms.coms).unwrap();
+        assert_eq!(
+            xcoms.coms,
+            serde_json::from_str::<Vec<Com1<F>>>(&json).unwrap()
+        );
+        let json = serde_json::to_string(&ycoms.coms).unwrap();
+        assert_eq!(
+            ycoms.coms,
+            serde_json::from_str::<Vec<Com2<F>>>(&json).unwrap()
+        );
+
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(proof, serde_json::from_str::<EquProof<F>>(&json).unwrap());
+    }
+
+    #[test]
+    fn test_serde_json_rejects_malformed() {
+        assert!(serde_json::from_str::<Com1<F>>("[1, 2, 3]").is_err());
+    }
+}
@@ -0,0 +1,66 @@
+//! Contains concrete type aliases of the generic API for the supported pairing-friendly curves.
+//!
+//! The rest of the crate is generic over the [`Pairing`](ark_ec::pairing::Pairing) and pulls in
+//! no curve at all. Each curve is behind its own cargo feature, so that only the arkworks curve
+//! that is actually used gets compiled:
+//!
+//! - `bls12_381`:&emsp;[`bls12_381`] over [`ark_bls12_381::Bls12_381`],
+//! - `bn254`:&emsp;&emsp;&ensp;&nbsp;[`bn254`] over [`ark_bn254::Bn254`], and
+//! - `bw6_761`:&emsp;&ensp;[`bw6_761`] over [`ark_bw6_761::BW6_761`].
+//!
+//! The CRS of each curve is also available directly as [`Bls12_381CRS`], [`Bn254CRS`] and
+//! [`BW6_761CRS`].
+
+macro_rules! curve_aliases {
+    ($engine:ty) => {
+        /// The pairing engine of this curve.
+        pub type E = $engine;
+
+        pub type G1Affine = <E as ark_ec::pairing::Pairing>::G1Affine;
+        pub type G2Affine = <E as ark_ec::pairing::Pairing>::G2Affine;
+        pub type Fr = <E as ark_ec::pairing::Pairing>::ScalarField;
+        pub type GT = ark_ec::pairing::PairingOutput<E>;
+
+        pub type CRS = crate::generator::CRS<E>;
+        pub type Com1 = crate::data_structures::Com1<E>;
+        pub type Com2 = crate::data_structures::Com2<E>;
+        pub type Commit1 = crate::prover::Commit1<E>;
+        pub type Commit2 = crate::prover::Commit2<E>;
+        pub type EquProof = crate::prover::EquProof<E>;
+
+        pub type PPE = crate::statement::PPE<E>;
+        pub type MSMEG1 = crate::statement::MSMEG1<E>;
+        pub type MSMEG2 = crate::statement::MSMEG2<E>;
+        pub type QuadEqu = crate::statement::QuadEqu<E>;
+    };
+}
+
+/// The type aliases for BLS12-381.
+#[cfg(feature = "bls12_381")]
+pub mod bls12_381 {
+    curve_aliases!(ark_bls12_381::Bls12_381);
+}
+
+/// The CRS over BLS12-381.
+#[cfg(feature = "bls12_381")]
+pub type Bls12_381CRS = bls12_381::CRS;
+
+/// The type aliases for BN254.
+#[cfg(feature = "bn254")]
+pub mod bn254 {
+    curve_aliases!(ark_bn254::Bn254);
+}
+
+/// The CRS over BN254.
+#[cfg(feature = "bn254")]
+pub type Bn254CRS = bn254::CRS;
+
+/// The type aliases for BW6-761.
+#[cfg(feature = "bw6_761")]
+pub mod bw6_761 {
+    curve_aliases!(ark_bw6_761::BW6_761);
+}
+
+/// The CRS over BW6-761.
+#[cfg(feature = "bw6_761")]
+pub type BW6_761CRS = bw6_761::CRS;
@@ -20,33 +20,196 @@
 //! well.
 
 use ark_ec::{
-    pairing::{Pairing, PairingOutput},
-    AffineRepr, CurveGroup,
+    pairing::{MillerLoopOutput, Pairing, PairingOutput},
+    AffineRepr, CurveGroup, VariableBaseMSM,
 };
 use ark_ff::{Field, One, Zero};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress};
 use ark_std::{
-    fmt::Debug,
+    fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
     iter::Sum,
-    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    vec::Vec,
 };
+#[cfg(feature = "std")]
 use rayon::prelude::*;
 
+// Without `std` there is no thread pool, so the "parallel" matrix products run sequentially
+#[cfg(not(feature = "std"))]
+trait IntoParallelIterator: IntoIterator + Sized {
+    fn into_par_iter(self) -> Self::IntoIter {
+        self.into_iter()
+    }
+}
+#[cfg(not(feature = "std"))]
+impl<T: IntoIterator> IntoParallelIterator for T {}
+
 use crate::generator::CRS;
 
+/// Matrix arithmetic over field elements and commitment group elements.
+///
+/// The `is_parallel` flag of the products only selects the evaluation strategy: if set, the rows
+/// and entries of the product are computed on the rayon thread pool (sequentially without the
+/// `std` feature). The result is the same either way, so it is only worth setting for matrices
+/// large enough to amortize the scheduling overhead.
 pub trait Mat<Elem: Clone>: Eq + Clone + Debug {
     type Other;
 
+    /// Entry-wise addition, panicking on mismatched dimensions (see [`try_add`](Mat::try_add)).
     fn add(&self, other: &Self) -> Self;
+    /// Entry-wise addition of two matrices of the same dimensions.
+    fn try_add(&self, other: &Self) -> Result<Self, MatrixError>;
     fn neg(&self) -> Self;
+    /// Scales every entry by the scalar `other`.
+    #[doc(alias = "scale")]
     fn scalar_mul(&self, other: &Self::Other) -> Self;
     fn transpose(&self) -> Self;
+    /// Computes `lhs * self`, panicking on mismatched dimensions and returning the empty matrix
+    /// if either operand is empty (see [`try_left_mul`](Mat::try_left_mul)).
+    ///
+    /// Neither operand is transposed: e.g. `vec_to_col_vec(&key.u).left_mul(&R, false)` is the
+    /// column vector `R u` for an `m x 2` matrix `R`, computed sequentially.
     fn left_mul(&self, lhs: &Matrix<Self::Other>, is_parallel: bool) -> Self;
+    /// Computes `lhs * self`, where the number of columns of `lhs` must equal the number of rows of `self`.
+    fn try_left_mul(
+        &self,
+        lhs: &Matrix<Self::Other>,
+        is_parallel: bool,
+    ) -> Result<Self, MatrixError>;
+    /// Computes `self * rhs`, panicking on mismatched dimensions and returning the empty matrix
+    /// if either operand is empty (see [`try_right_mul`](Mat::try_right_mul)).
     fn right_mul(&self, rhs: &Matrix<Self::Other>, is_parallel: bool) -> Self;
+    /// Computes `self * rhs`, where the number of columns of `self` must equal the number of rows of `rhs`.
+    fn try_right_mul(
+        &self,
+        rhs: &Matrix<Self::Other>,
+        is_parallel: bool,
+    ) -> Result<Self, MatrixError>;
+
+    /// Writes `self + other` into `out`, reusing the rows `out` already holds instead of
+    /// allocating new ones. Panics on mismatched dimensions, like [`add`](Mat::add).
+    fn add_into(&self, other: &Self, out: &mut Self);
+    /// Adds `other` to `self` entry-wise without allocating. Panics on mismatched dimensions.
+    fn add_in_place(&mut self, other: &Self);
+    /// Sequentially writes `lhs * self` into `out`, reusing the rows `out` already holds. Panics
+    /// on mismatched dimensions and clears `out` if either operand is empty, like
+    /// [`left_mul`](Mat::left_mul).
+    fn left_mul_into(&self, lhs: &Matrix<Self::Other>, out: &mut Self);
+    /// Sequentially writes `self * rhs` into `out`, reusing the rows `out` already holds. Panics
+    /// on mismatched dimensions and clears `out` if either operand is empty, like
+    /// [`right_mul`](Mat::right_mul).
+    fn right_mul_into(&self, rhs: &Matrix<Self::Other>, out: &mut Self);
+}
+
+/// Describes why an operation on (a pair of) [`Matrix`] values is undefined.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatrixError {
+    /// A dimension of the second operand does not line up with the first.
+    DimensionMismatch { expected: usize, found: usize },
+    /// One of the operands has no rows or no columns.
+    EmptyMatrix,
+    /// The entry `(row, col)` lies outside of the matrix.
+    IndexOutOfBounds { row: usize, col: usize },
+}
+
+impl ark_std::fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut ark_std::fmt::Formatter<'_>) -> ark_std::fmt::Result {
+        match self {
+            Self::DimensionMismatch { expected, found } => write!(
+                f,
+                "matrix dimension mismatch: expected {}, found {}",
+                expected, found
+            ),
+            Self::EmptyMatrix => write!(f, "matrix is empty"),
+            Self::IndexOutOfBounds { row, col } => {
+                write!(f, "matrix entry ({}, {}) is out of bounds", row, col)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MatrixError {}
+
+// Both matrices must be non-empty and have the same number of rows, and every pair of rows the same length
+fn check_add_dims<T>(a: &Matrix<T>, b: &Matrix<T>) -> Result<(), MatrixError> {
+    if a.is_empty() || b.is_empty() {
+        return Err(MatrixError::EmptyMatrix);
+    }
+    if a.len() != b.len() {
+        return Err(MatrixError::DimensionMismatch {
+            expected: a.len(),
+            found: b.len(),
+        });
+    }
+    match a.iter().zip(b).find(|(ra, rb)| ra.len() != rb.len()) {
+        Some((ra, rb)) => Err(MatrixError::DimensionMismatch {
+            expected: ra.len(),
+            found: rb.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
+// The number of columns in lhs must equal the number of rows in rhs
+fn check_mul_dims<T, U>(lhs: &Matrix<T>, rhs: &Matrix<U>) -> Result<(), MatrixError> {
+    if lhs.is_empty() || lhs[0].is_empty() || rhs.is_empty() || rhs[0].is_empty() {
+        return Err(MatrixError::EmptyMatrix);
+    }
+    if lhs[0].len() != rhs.len() {
+        return Err(MatrixError::DimensionMismatch {
+            expected: lhs[0].len(),
+            found: rhs.len(),
+        });
+    }
+    Ok(())
 }
 
 pub type Matrix<E> = Vec<Vec<E>>;
 
+// Overwrites `out` with the `rows x cols` matrix with entries `entry(i, j)`, clearing and refilling
+// the rows `out` already holds so that only missing rows are allocated.
+fn fill_matrix<T>(
+    out: &mut Matrix<T>,
+    rows: usize,
+    cols: usize,
+    mut entry: impl FnMut(usize, usize) -> T,
+) {
+    out.truncate(rows);
+    out.resize_with(rows, || Vec::with_capacity(cols));
+    for (i, row) in out.iter_mut().enumerate() {
+        row.clear();
+        row.extend((0..cols).map(|j| entry(i, j)));
+    }
+}
+
+// Overwrites `out` with the entry-wise sum of `a` and `b`, which must have matching dimensions.
+fn add_matrices_into<T: Copy + Add<Output = T>>(a: &Matrix<T>, b: &Matrix<T>, out: &mut Matrix<T>) {
+    if let Err(e) = check_add_dims(a, b) {
+        panic!("{}", e);
+    }
+    out.truncate(a.len());
+    out.resize_with(a.len(), Vec::new);
+    for ((row, ra), rb) in out.iter_mut().zip(a).zip(b) {
+        row.clear();
+        row.extend(ra.iter().zip(rb).map(|(x, y)| *x + *y));
+    }
+}
+
+// Adds `b` to `a` entry-wise, where both must have matching dimensions.
+fn add_matrices_in_place<T: Copy + AddAssign>(a: &mut Matrix<T>, b: &Matrix<T>) {
+    if let Err(e) = check_add_dims(a, b) {
+        panic!("{}", e);
+    }
+    for (ra, rb) in a.iter_mut().zip(b) {
+        for (x, y) in ra.iter_mut().zip(rb) {
+            *x += *y;
+        }
+    }
+}
+
 /// Encapsulates arithmetic traits for Groth-Sahai's bilinear group for commitments.
 pub trait B<E: Pairing>:
     Eq
@@ -65,17 +228,57 @@ pub trait B<E: Pairing>:
 
 /// Provides linear maps and vector conversions for the base of the GS commitment group.
 pub trait B1<E: Pairing>:
-    B<E>
-//    + MulAssign<E::ScalarField>
-    + From<Matrix<E::G1Affine>>
+    B<E> + Mul<E::ScalarField, Output = Self> + MulAssign<E::ScalarField> + From<Matrix<E::G1Affine>>
 {
     fn as_col_vec(&self) -> Matrix<E::G1Affine>;
     fn as_vec(&self) -> Vec<E::G1Affine>;
-    /// The linear map from G1 to B1 for pairing-product and multi-scalar multiplication equations.
+    /// The linear map `ι_1(x) = (O, x)` from G1 to B1 for pairing-product and multi-scalar
+    /// multiplication equations.
+    ///
+    /// It does not depend on the CRS, and is how group constants and targets of an equation enter
+    /// the verification equation. A commitment to `x` is `ι_1(x)` plus randomness:
+    ///
+    /// ```
+    /// use ark_bls12_381::{Bls12_381 as F, Fr};
+    /// use ark_ec::{AffineRepr, CurveGroup};
+    /// use ark_std::{test_rng, UniformRand};
+    /// use groth_sahai::prover::commit_G1_with_randomness;
+    /// use groth_sahai::{AbstractCrs, Com1, B1, CRS};
+    ///
+    /// let mut rng = test_rng();
+    /// let crs = CRS::<F>::generate_crs(&mut rng);
+    /// let x = (crs.g1_gen * Fr::rand(&mut rng)).into_affine();
+    /// let (r1, r2) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    ///
+    /// let com = Com1::<F>::linear_map(&x) + crs.u[0] * r1 + crs.u[1] * r2;
+    /// assert_eq!(com, commit_G1_with_randomness(&x, &crs, r1, r2));
+    /// assert_eq!(Com1::<F>::linear_map(&x), Com1::<F>(AffineRepr::zero(), x));
+    /// ```
     fn linear_map(x: &E::G1Affine) -> Self;
+    /// Applies [`linear_map`](B1::linear_map) to every element of `x_vec`.
     fn batch_linear_map(x_vec: &[E::G1Affine]) -> Vec<Self>;
-    /// The linear map from scalar field to B1 for multi-scalar multiplication and quadratic equations.
+    /// The linear map `ι_1'(x) = x (u_2 + (O, P))` from the scalar field to B1 for multi-scalar
+    /// multiplication and quadratic equations, where `P` is the generator of G1.
+    ///
+    /// Unlike [`linear_map`](B1::linear_map), this depends on the CRS: under a hiding CRS,
+    /// `u_2 + (O, P)` lies in the span of `u_1`, which is what makes scalar commitments hiding.
+    /// A commitment to the scalar `x` is `ι_1'(x)` plus randomness along `u_1` only:
+    ///
+    /// ```
+    /// use ark_bls12_381::{Bls12_381 as F, Fr};
+    /// use ark_std::{test_rng, UniformRand};
+    /// use groth_sahai::prover::commit_scalar_to_B1_with_randomness;
+    /// use groth_sahai::{AbstractCrs, Com1, B1, CRS};
+    ///
+    /// let mut rng = test_rng();
+    /// let crs = CRS::<F>::generate_crs(&mut rng);
+    /// let (x, r) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    ///
+    /// let com = Com1::<F>::scalar_linear_map(&x, &crs) + crs.u[0] * r;
+    /// assert_eq!(com, commit_scalar_to_B1_with_randomness(&x, &crs, r));
+    /// ```
     fn scalar_linear_map(x: &E::ScalarField, key: &CRS<E>) -> Self;
+    /// Applies [`scalar_linear_map`](B1::scalar_linear_map) to every element of `x_vec`.
     fn batch_scalar_linear_map(x_vec: &[E::ScalarField], key: &CRS<E>) -> Vec<Self>;
 
     fn scalar_mul(&self, other: &E::ScalarField) -> Self;
@@ -83,28 +286,73 @@ pub trait B1<E: Pairing>:
 
 /// Provides linear maps and vector conversions for the extension of the GS commitment group.
 pub trait B2<E: Pairing>:
-    B<E>
-//    + MulAssign<E::ScalarField>
-    + From<Matrix<E::G2Affine>>
+    B<E> + Mul<E::ScalarField, Output = Self> + MulAssign<E::ScalarField> + From<Matrix<E::G2Affine>>
 {
     fn as_col_vec(&self) -> Matrix<E::G2Affine>;
     fn as_vec(&self) -> Vec<E::G2Affine>;
-    /// The linear map from G2 to B2 for pairing-product and multi-scalar multiplication equations.
+    /// The linear map `ι_2(y) = (O, y)` from G2 to B2 for pairing-product and multi-scalar
+    /// multiplication equations (see [`B1::linear_map`]).
+    ///
+    /// ```
+    /// use ark_bls12_381::{Bls12_381 as F, Fr};
+    /// use ark_ec::CurveGroup;
+    /// use ark_std::{test_rng, UniformRand};
+    /// use groth_sahai::prover::commit_G2_with_randomness;
+    /// use groth_sahai::{AbstractCrs, Com2, B2, CRS};
+    ///
+    /// let mut rng = test_rng();
+    /// let crs = CRS::<F>::generate_crs(&mut rng);
+    /// let y = (crs.g2_gen * Fr::rand(&mut rng)).into_affine();
+    /// let (s1, s2) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    ///
+    /// let com = Com2::<F>::linear_map(&y) + crs.v[0] * s1 + crs.v[1] * s2;
+    /// assert_eq!(com, commit_G2_with_randomness(&y, &crs, s1, s2));
+    /// ```
     fn linear_map(y: &E::G2Affine) -> Self;
+    /// Applies [`linear_map`](B2::linear_map) to every element of `y_vec`.
     fn batch_linear_map(y_vec: &[E::G2Affine]) -> Vec<Self>;
-    /// The linear map from scalar field to B2 for multi-scalar multiplication and quadratic equations.
+    /// The CRS-dependent linear map `ι_2'(y) = y (v_2 + (O, P))` from the scalar field to B2 for
+    /// multi-scalar multiplication and quadratic equations, where `P` is the generator of G2
+    /// (see [`B1::scalar_linear_map`]).
+    ///
+    /// ```
+    /// use ark_bls12_381::{Bls12_381 as F, Fr};
+    /// use ark_std::{test_rng, UniformRand};
+    /// use groth_sahai::prover::commit_scalar_to_B2_with_randomness;
+    /// use groth_sahai::{AbstractCrs, Com2, B2, CRS};
+    ///
+    /// let mut rng = test_rng();
+    /// let crs = CRS::<F>::generate_crs(&mut rng);
+    /// let (y, s) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+    ///
+    /// let com = Com2::<F>::scalar_linear_map(&y, &crs) + crs.v[0] * s;
+    /// assert_eq!(com, commit_scalar_to_B2_with_randomness(&y, &crs, s));
+    /// ```
     fn scalar_linear_map(y: &E::ScalarField, key: &CRS<E>) -> Self;
+    /// Applies [`scalar_linear_map`](B2::scalar_linear_map) to every element of `y_vec`.
     fn batch_scalar_linear_map(y_vec: &[E::ScalarField], key: &CRS<E>) -> Vec<Self>;
 
     fn scalar_mul(&self, other: &E::ScalarField) -> Self;
 }
 
 /// Provides linear maps and matrix conversions for the target of the GS commitment group, as well as the equipped pairing.
-pub trait BT<E: Pairing, C1: B1<E>, C2: B2<E>>: B<E> + From<Matrix<PairingOutput<E>>> {
+///
+/// Like [`PairingOutput`], `BT` is written additively: `+` is the entry-wise group operation of
+/// `GT` (multiplication in `GT`'s usual notation), [`Zero`] is the identity and `* Fr` is
+/// entry-wise exponentiation.
+pub trait BT<E: Pairing, C1: B1<E>, C2: B2<E>>:
+    B<E>
+    + Mul<E::ScalarField, Output = Self>
+    + MulAssign<E::ScalarField>
+    + From<Matrix<PairingOutput<E>>>
+{
     fn as_matrix(&self) -> Matrix<PairingOutput<E>>;
 
-    /// The bilinear pairing over the GS commitment group (B1, B2, BT) is the tensor product.
+    /// The bilinear pairing over the GS commitment group (B1, B2, BT) is the tensor product
     /// with respect to the bilinear pairing over the bilinear group (G1, G2, GT).
+    ///
+    /// For SXDH, this is the map `F((X_1, X_2), (Y_1, Y_2)) = [[e(X_1, Y_1), e(X_1, Y_2)],
+    /// [e(X_2, Y_1), e(X_2, Y_2)]]`.
     fn pairing(x: C1, y: C2) -> Self;
     /// The entry-wise sum of bilinear pairings over the GS commitment group.
     fn pairing_sum(x_vec: &[C1], y_vec: &[C2]) -> Self;
@@ -125,11 +373,11 @@ pub trait BT<E: Pairing, C1: B1<E>, C2: B2<E>>: B<E> + From<Matrix<PairingOutput
 // SXDH instantiation's bilinear group for commitments
 
 /// Base [`B1`](crate::data_structures::B1) for the commitment group in the SXDH instantiation.
-#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Copy, Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Com1<E: Pairing>(pub E::G1Affine, pub E::G1Affine);
 
 /// Extension [`B2`](crate::data_structures::B2) for the commitment group in the SXDH instantiation.
-#[derive(Copy, Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Copy, Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct Com2<E: Pairing>(pub E::G2Affine, pub E::G2Affine);
 
 /// Target [`BT`](crate::data_structures::BT) for the commitment group in the SXDH instantiation.
@@ -159,6 +407,101 @@ pub fn vec_to_col_vec<F: Clone>(vec: &[F]) -> Matrix<F> {
     mat
 }
 
+/// Transpose a (possibly non-square) matrix.
+///
+/// An empty matrix, or one whose rows are all empty, transposes to the empty matrix.
+pub fn transpose<T: Clone>(mat: &Matrix<T>) -> Matrix<T> {
+    let cols = mat.first().map_or(0, |row| row.len());
+    assert!(
+        mat.iter().all(|row| row.len() == cols),
+        "cannot transpose a matrix with rows of differing lengths"
+    );
+
+    (0..cols)
+        .map(|j| mat.iter().map(|row| row[j].clone()).collect())
+        .collect()
+}
+
+/// Entry-wise (Hadamard) product of two [scalar field](ark_ec::Pairing::Fr) matrices of the same dimensions.
+pub fn hadamard<F: Field>(a: &Matrix<F>, b: &Matrix<F>) -> Result<Matrix<F>, MatrixError> {
+    check_add_dims(a, b)?;
+    Ok(a.iter()
+        .zip(b)
+        .map(|(ra, rb)| ra.iter().zip(rb).map(|(x, y)| *x * y).collect())
+        .collect())
+}
+
+/// Build a dense `rows x cols` matrix from sparse `(i, j, value)` entries, zero-filling the rest.
+///
+/// This is handy for the pairing exponent matrix `Γ` of an equation, which is mostly zero.
+/// If an entry is given more than once, the last value is used.
+pub fn matrix_from_entries<F: Zero + Clone>(
+    rows: usize,
+    cols: usize,
+    entries: &[(usize, usize, F)],
+) -> Result<Matrix<F>, MatrixError> {
+    let mut mat = vec![vec![F::zero(); cols]; rows];
+    for (row, col, value) in entries {
+        let entry = mat.get_mut(*row).and_then(|r| r.get_mut(*col)).ok_or(
+            MatrixError::IndexOutOfBounds {
+                row: *row,
+                col: *col,
+            },
+        )?;
+        *entry = value.clone();
+    }
+    Ok(mat)
+}
+
+/// Multiply two [scalar field](ark_ec::Pairing::Fr) matrices, i.e. compute the product `a b`.
+///
+/// # Panics
+///
+/// Panics if the number of columns of `a` differs from the number of rows of `b`.
+pub fn mat_mul<F: Field>(a: &Matrix<F>, b: &Matrix<F>) -> Matrix<F> {
+    let (a_rows, a_cols) = (a.len(), a.first().map_or(0, |row| row.len()));
+    let (b_rows, b_cols) = (b.len(), b.first().map_or(0, |row| row.len()));
+    assert_eq!(
+        a_cols, b_rows,
+        "cannot multiply a {} x {} matrix by a {} x {} matrix",
+        a_rows, a_cols, b_rows, b_cols
+    );
+
+    a.iter()
+        .map(|row| {
+            (0..b_cols)
+                .map(|j| row.iter().zip(b).map(|(x, b_row)| *x * b_row[j]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+// Formats the canonical serialization of `elem` as a lowercase hex string, which for curve
+// points (uncompressed) consists of their coordinates.
+pub(crate) fn fmt_hex<T: CanonicalSerialize>(
+    elem: &T,
+    compress: Compress,
+    f: &mut Formatter<'_>,
+) -> fmt::Result {
+    let mut bytes = Vec::with_capacity(elem.serialized_size(compress));
+    elem.serialize_with_mode(&mut bytes, compress)
+        .map_err(|_| fmt::Error)?;
+    write!(f, "0x")?;
+    for b in bytes {
+        write!(f, "{:02x}", b)?;
+    }
+    Ok(())
+}
+
+// Wraps an element so that its `Debug` output is its (uncompressed) hex serialization.
+pub(crate) struct Hex<'a, T: CanonicalSerialize>(pub(crate) &'a T);
+
+impl<T: CanonicalSerialize> Debug for Hex<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_hex(self.0, Compress::No, f)
+    }
+}
+
 macro_rules! impl_base_commit_groups {
     (
         $(
@@ -177,6 +520,36 @@ macro_rules! impl_base_commit_groups {
             }
             impl<E: Pairing> Eq for $com<E> {}
 
+            // Prints the coordinates of both group elements in hex
+            impl<E: Pairing> Debug for $com<E> {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    f.debug_tuple(stringify!($com))
+                        .field(&Hex(&self.0))
+                        .field(&Hex(&self.1))
+                        .finish()
+                }
+            }
+
+            // Prints the compressed encodings of both group elements in hex
+            impl<E: Pairing> Display for $com<E> {
+                fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                    write!(f, "(")?;
+                    fmt_hex(&self.0, Compress::Yes, f)?;
+                    write!(f, ", ")?;
+                    fmt_hex(&self.1, Compress::Yes, f)?;
+                    write!(f, ")")
+                }
+            }
+
+            // Hashing for Com group, consistent with entry-wise equality
+            impl<E: Pairing> Hash for $com<E> {
+                #[inline]
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.0.hash(state);
+                    self.1.hash(state);
+                }
+            }
+
             // Addition for Com group
             impl<E: Pairing> Add<$com<E>> for $com<E> {
                 type Output = Self;
@@ -225,22 +598,94 @@ macro_rules! impl_base_commit_groups {
                     *self += -other;
                 }
             }
-            /*
+            // Reference variants of the above, for convenience
+            impl<'a, E: Pairing> Add<&'a $com<E>> for $com<E> {
+                type Output = Self;
+
+                #[inline]
+                fn add(self, other: &'a Self) -> Self {
+                    self + *other
+                }
+            }
+            impl<'a, 'b, E: Pairing> Add<&'b $com<E>> for &'a $com<E> {
+                type Output = $com<E>;
+
+                #[inline]
+                fn add(self, other: &'b $com<E>) -> $com<E> {
+                    *self + *other
+                }
+            }
+            impl<'a, E: Pairing> AddAssign<&'a $com<E>> for $com<E> {
+
+                #[inline]
+                fn add_assign(&mut self, other: &'a Self) {
+                    *self += *other;
+                }
+            }
+            impl<'a, E: Pairing> Neg for &'a $com<E> {
+                type Output = $com<E>;
+
+                #[inline]
+                fn neg(self) -> $com<E> {
+                    -*self
+                }
+            }
+            impl<'a, E: Pairing> Sub<&'a $com<E>> for $com<E> {
+                type Output = Self;
+
+                #[inline]
+                fn sub(self, other: &'a Self) -> Self {
+                    self - *other
+                }
+            }
+            impl<'a, 'b, E: Pairing> Sub<&'b $com<E>> for &'a $com<E> {
+                type Output = $com<E>;
+
+                #[inline]
+                fn sub(self, other: &'b $com<E>) -> $com<E> {
+                    *self - *other
+                }
+            }
+            impl<'a, E: Pairing> SubAssign<&'a $com<E>> for $com<E> {
+
+                #[inline]
+                fn sub_assign(&mut self, other: &'a Self) {
+                    *self -= *other;
+                }
+            }
             // Entry-wise scalar point-multiplication
-            impl <E: Pairing> MulAssign<E::ScalarField> for $com<E> {
+            impl<E: Pairing> Mul<E::ScalarField> for $com<E> {
+                type Output = Self;
+
+                #[inline]
+                fn mul(self, rhs: E::ScalarField) -> Self {
+                    self.scalar_mul(&rhs)
+                }
+            }
+            impl<E: Pairing> MulAssign<E::ScalarField> for $com<E> {
+                #[inline]
                 fn mul_assign(&mut self, rhs: E::ScalarField) {
+                    self.scalar_mul_assign(&rhs);
+                }
+            }
+            impl<E: Pairing> $com<E> {
+                /// In-place entry-wise scalar point-multiplication.
+                #[inline]
+                pub fn scalar_mul_assign(&mut self, rhs: &E::ScalarField) {
+                    *self = self.scalar_mul(rhs);
+                }
 
-                    let mut s1p = self.0.into_projective();
-                    let mut s2p = self.1.into_projective();
-                    s1p *= rhs;
-                    s2p *= rhs;
-                    *self = Self (
-                        s1p.into_affine().clone(),
-                        s2p.into_affine().clone()
-                    )
+                /// Homomorphically add two commitments.
+                ///
+                /// Commitments are additively homomorphic: if `self` opens to `x` under randomness
+                /// `(r1, r2)` and `other` opens to `y` under `(s1, s2)`, then the result opens to
+                /// `x + y` under `(r1 + s1, r2 + s2)` with respect to the same CRS. This is equivalent
+                /// to `self + other`.
+                #[inline]
+                pub fn add_homomorphic(&self, other: &Self) -> Self {
+                    self + other
                 }
             }
-            */
             impl<E: Pairing> Sum for $com<E> {
                 fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
                     iter.fold(
@@ -254,6 +699,211 @@ macro_rules! impl_base_commit_groups {
 }
 impl_base_commit_groups!(Com1, Com2);
 
+impl<E: Pairing> Com1<E> {
+    /// Computes `Σ_i F(coms1[i], coms2[i])` in [`BT`], where `F: B1 x B2 -> BT` is the bilinear map
+    /// of the SXDH commitment group used by the verification equations:
+    ///
+    /// `F((X_1, X_2), (Y_1, Y_2)) = [[e(X_1, Y_1), e(X_1, Y_2)], [e(X_2, Y_1), e(X_2, Y_2)]]`
+    ///
+    /// Each of the four entries is computed with a single multi-pairing. This is
+    /// [`BT::pairing_sum`] for [`ComT`], exposed for building custom verification equations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `coms1` and `coms2` differ in length.
+    pub fn pairing_sum(coms1: &[Com1<E>], coms2: &[Com2<E>]) -> ComT<E> {
+        ComT::<E>::pairing_sum(coms1, coms2)
+    }
+
+    /// Lifts the public element `x` to the trivial commitment `ι_1(x) = (O, x)`, which opens to
+    /// `x` with zero randomness.
+    ///
+    /// Unlike [`commit_G1`](crate::prover::commit_G1), this does **not** hide `x`: anyone can read
+    /// it off the commitment. Only use it for values the verifier knows anyway, e.g. to pass a
+    /// public element where an equation expects a committed variable.
+    pub fn trivial_commit(x: &E::G1Affine) -> Self {
+        Self::linear_map(x)
+    }
+
+    /// The two [`G1`](ark_ec::Pairing::G1Affine) elements of the commitment, i.e. `(self.0, self.1)`.
+    pub fn as_pair(&self) -> (E::G1Affine, E::G1Affine) {
+        (self.0, self.1)
+    }
+
+    /// The commitment consisting of the two given elements, the inverse of [`as_pair`](Self::as_pair).
+    pub fn from_pair((c1, c2): (E::G1Affine, E::G1Affine)) -> Self {
+        Self(c1, c2)
+    }
+
+    /// Computes the linear combination `Σ_i coeffs[i] coms[i]` with one multi-scalar
+    /// multiplication per coordinate, e.g. to aggregate proofs with random challenges.
+    ///
+    /// Returns [`MatrixError::DimensionMismatch`] if `coeffs` and `coms` differ in length.
+    pub fn linear_combination(
+        coms: &[Com1<E>],
+        coeffs: &[E::ScalarField],
+    ) -> Result<Self, MatrixError> {
+        if coms.len() != coeffs.len() {
+            return Err(MatrixError::DimensionMismatch {
+                expected: coms.len(),
+                found: coeffs.len(),
+            });
+        }
+        let (fst, snd): (Vec<_>, Vec<_>) = coms.iter().map(Self::as_pair).unzip();
+        Ok(Self(
+            E::G1::msm_unchecked(&fst, coeffs).into_affine(),
+            E::G1::msm_unchecked(&snd, coeffs).into_affine(),
+        ))
+    }
+}
+
+impl<E: Pairing> Com2<E> {
+    /// Lifts the public element `y` to the trivial commitment `ι_2(y) = (O, y)`, which opens to
+    /// `y` with zero randomness (see [`Com1::trivial_commit`]).
+    ///
+    /// This does **not** hide `y`.
+    pub fn trivial_commit(y: &E::G2Affine) -> Self {
+        Self::linear_map(y)
+    }
+
+    /// The two [`G2`](ark_ec::Pairing::G2Affine) elements of the commitment, i.e. `(self.0, self.1)`.
+    pub fn as_pair(&self) -> (E::G2Affine, E::G2Affine) {
+        (self.0, self.1)
+    }
+
+    /// The commitment consisting of the two given elements, the inverse of [`as_pair`](Self::as_pair).
+    pub fn from_pair((d1, d2): (E::G2Affine, E::G2Affine)) -> Self {
+        Self(d1, d2)
+    }
+
+    /// Computes the linear combination `Σ_i coeffs[i] coms[i]` with one multi-scalar
+    /// multiplication per coordinate (see [`Com1::linear_combination`]).
+    ///
+    /// Returns [`MatrixError::DimensionMismatch`] if `coeffs` and `coms` differ in length.
+    pub fn linear_combination(
+        coms: &[Com2<E>],
+        coeffs: &[E::ScalarField],
+    ) -> Result<Self, MatrixError> {
+        if coms.len() != coeffs.len() {
+            return Err(MatrixError::DimensionMismatch {
+                expected: coms.len(),
+                found: coeffs.len(),
+            });
+        }
+        let (fst, snd): (Vec<_>, Vec<_>) = coms.iter().map(Self::as_pair).unzip();
+        Ok(Self(
+            E::G2::msm_unchecked(&fst, coeffs).into_affine(),
+            E::G2::msm_unchecked(&snd, coeffs).into_affine(),
+        ))
+    }
+}
+
+macro_rules! impl_compressed_commit_groups {
+    (
+        $(
+            ($com:ident, $compressed:ident)
+        ),*
+    ) => {
+        $(
+            #[doc = concat!(
+                "A [`", stringify!($com), "`] stored as the compressed encodings of its two group elements."
+            )]
+            ///
+            /// This takes roughly half the memory of the affine representation, for storing large
+            /// sets of commitments, at the cost of a point decompression per element whenever the
+            /// commitment is used. Convert with `compress` and [`decompress`](Self::decompress).
+            #[derive(Clone, Debug, PartialEq, Eq)]
+            pub struct $compressed<E: Pairing> {
+                bytes: Vec<u8>,
+                _engine: PhantomData<E>,
+            }
+
+            impl<E: Pairing> $com<E> {
+                /// Compresses the commitment into its compact in-memory representation.
+                pub fn compress(&self) -> $compressed<E> {
+                    let mut bytes = Vec::with_capacity(self.compressed_size());
+                    self.serialize_compressed(&mut bytes)
+                        .expect("serializing into a Vec cannot fail");
+                    $compressed {
+                        bytes,
+                        _engine: PhantomData,
+                    }
+                }
+            }
+
+            impl<E: Pairing> $compressed<E> {
+                /// Recovers the commitment.
+                ///
+                /// The encoding was produced by `compress` from valid group elements, so the
+                /// subgroup checks of deserialization are skipped.
+                pub fn decompress(&self) -> $com<E> {
+                    $com::<E>::deserialize_compressed_unchecked(&self.bytes[..])
+                        .expect("compressed commitments always hold a valid encoding")
+                }
+
+                /// The compressed encodings of both group elements, i.e. the compressed
+                /// serialization of the commitment.
+                pub fn as_bytes(&self) -> &[u8] {
+                    &self.bytes
+                }
+            }
+
+            impl<E: Pairing> From<$com<E>> for $compressed<E> {
+                fn from(com: $com<E>) -> Self {
+                    com.compress()
+                }
+            }
+
+            impl<E: Pairing> From<&$compressed<E>> for $com<E> {
+                fn from(com: &$compressed<E>) -> Self {
+                    com.decompress()
+                }
+            }
+        )*
+    }
+}
+impl_compressed_commit_groups!((Com1, CompressedCom1), (Com2, CompressedCom2));
+
+/// Homomorphically sum a slice of commitments (see [`Com1::add_homomorphic`]).
+///
+/// The result opens to the sum of the committed values under the sum of the randomness.
+/// An empty slice sums to the zero commitment.
+pub fn sum_commitments<E: Pairing, C: B<E>>(coms: &[C]) -> C {
+    coms.iter().copied().sum()
+}
+
+// The product of the Miller loops of all pairs `(g1_i, g2_i)`, before the final exponentiation.
+//
+// ark-ec 0.5 miscomputes the multi-Miller loop of BW6 curves for more than four (non-trivial)
+// pairs. There is no way to name all BW6 engines in a bound, so for every engine whose target
+// field has degree 6, as for BW6 (and MNT6, where this is merely unnecessary), the pairs are
+// processed in chunks of four; the product of their Miller loops is the same.
+pub(crate) fn multi_miller_loop<E: Pairing>(
+    g1: impl IntoIterator<Item = E::G1Affine>,
+    g2: impl IntoIterator<Item = E::G2Affine>,
+) -> MillerLoopOutput<E> {
+    if E::TargetField::extension_degree() != 6 {
+        return E::multi_miller_loop(g1, g2);
+    }
+    let g1: Vec<E::G1Affine> = g1.into_iter().collect();
+    let g2: Vec<E::G2Affine> = g2.into_iter().collect();
+    assert_eq!(g1.len(), g2.len());
+    MillerLoopOutput(
+        g1.chunks(4)
+            .zip(g2.chunks(4))
+            .map(|(a, b)| E::multi_miller_loop(a.iter().copied(), b.iter().copied()).0)
+            .product(),
+    )
+}
+
+// The product of the pairings of all pairs `(g1_i, g2_i)`, see [`multi_miller_loop`].
+pub(crate) fn multi_pairing<E: Pairing>(
+    g1: impl IntoIterator<Item = E::G1Affine>,
+    g2: impl IntoIterator<Item = E::G2Affine>,
+) -> PairingOutput<E> {
+    E::final_exponentiation(multi_miller_loop(g1, g2)).unwrap()
+}
+
 impl<E: Pairing> Zero for Com1<E> {
     #[inline]
     fn zero() -> Self {
@@ -322,7 +972,7 @@ impl<E: Pairing> B1<E> for Com1<E> {
     #[inline]
     fn scalar_linear_map(x: &E::ScalarField, key: &CRS<E>) -> Self {
         // = xu, where u = u_2 + (O, P) is a commitment group element
-        (key.u[1] + Com1::<E>::linear_map(&key.g1_gen)).scalar_mul(x)
+        (*key.u2() + Com1::<E>::linear_map(&key.g1_gen)).scalar_mul(x)
     }
 
     #[inline]
@@ -367,7 +1017,7 @@ impl<E: Pairing> B2<E> for Com2<E> {
     #[inline]
     fn scalar_linear_map(y: &E::ScalarField, key: &CRS<E>) -> Self {
         // = yv, where v = v_2 + (O, P) is a commitment group element
-        (key.v[1] + Com2::<E>::linear_map(&key.g2_gen)).scalar_mul(y)
+        (*key.v2() + Com2::<E>::linear_map(&key.g2_gen)).scalar_mul(y)
     }
 
     #[inline]
@@ -464,6 +1114,23 @@ impl<E: Pairing> SubAssign<ComT<E>> for ComT<E> {
         self.3 -= other.3;
     }
 }
+impl<E: Pairing> Mul<E::ScalarField> for ComT<E> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: E::ScalarField) -> Self {
+        Self(self.0 * rhs, self.1 * rhs, self.2 * rhs, self.3 * rhs)
+    }
+}
+impl<E: Pairing> MulAssign<E::ScalarField> for ComT<E> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: E::ScalarField) {
+        self.0 *= rhs;
+        self.1 *= rhs;
+        self.2 *= rhs;
+        self.3 *= rhs;
+    }
+}
 impl<E: Pairing> From<Matrix<PairingOutput<E>>> for ComT<E> {
     fn from(mat: Matrix<PairingOutput<E>>) -> Self {
         assert_eq!(mat.len(), 2);
@@ -494,10 +1161,10 @@ impl<E: Pairing> BT<E, Com1<E>, Com2<E>> for ComT<E> {
     fn pairing_sum(x_vec: &[Com1<E>], y_vec: &[Com2<E>]) -> Self {
         assert_eq!(x_vec.len(), y_vec.len());
         Self(
-            E::multi_pairing(x_vec.iter().map(|x| x.0), y_vec.iter().map(|y| y.0)),
-            E::multi_pairing(x_vec.iter().map(|x| x.0), y_vec.iter().map(|y| y.1)),
-            E::multi_pairing(x_vec.iter().map(|x| x.1), y_vec.iter().map(|y| y.0)),
-            E::multi_pairing(x_vec.iter().map(|x| x.1), y_vec.iter().map(|y| y.1)),
+            multi_pairing(x_vec.iter().map(|x| x.0), y_vec.iter().map(|y| y.0)),
+            multi_pairing(x_vec.iter().map(|x| x.0), y_vec.iter().map(|y| y.1)),
+            multi_pairing(x_vec.iter().map(|x| x.1), y_vec.iter().map(|y| y.0)),
+            multi_pairing(x_vec.iter().map(|x| x.1), y_vec.iter().map(|y| y.1)),
         )
     }
 
@@ -588,20 +1255,21 @@ macro_rules! impl_base_commit_mats {
                 type Other = E::ScalarField;
 
                 fn add(&self, other: &Self) -> Self {
-                    assert_eq!(self.len(), other.len());
-                    assert_eq!(self[0].len(), other[0].len());
-                    let m = self.len();
-                    let n = self[0].len();
-                    let mut add = Vec::with_capacity(m);
-                    for i in 0..m {
-                        add.push(Vec::with_capacity(n));
-                        for j in 0..n {
-                            add[i].push(self[i][j].clone() + other[i][j].clone());
-                        }
-                    }
+                    let mut add = Vec::with_capacity(self.len());
+                    self.add_into(other, &mut add);
                     add
                 }
 
+                #[inline]
+                fn add_into(&self, other: &Self, out: &mut Self) {
+                    add_matrices_into(self, other, out);
+                }
+
+                #[inline]
+                fn add_in_place(&mut self, other: &Self) {
+                    add_matrices_in_place(self, other);
+                }
+
                 #[inline]
                 fn neg(&self) -> Self {
                    (0..self.len()).map( |i| {
@@ -627,31 +1295,18 @@ macro_rules! impl_base_commit_mats {
                     smul
                 }
 
+                #[inline]
                 fn transpose(&self) -> Self {
-                    let mut trans = Vec::with_capacity(self[0].len());
-                    for _ in 0..self[0].len() {
-                        trans.push(Vec::with_capacity(self.len()));
-                    }
-
-                    for row in self {
-                        for i in 0..row.len() {
-                            // Push rows onto columns
-                            trans[i].push(row[i].clone());
-                        }
-                    }
-                    trans
+                    transpose(self)
                 }
 
                 fn right_mul(&self, rhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
-                    if self.is_empty() || self[0].is_empty() {
-                        return vec![];
-                    }
-                    if rhs.is_empty() || rhs[0].is_empty() {
-                        return vec![];
-                    }
-
                     // Check that every row in a and column in b has the same length
-                    assert_eq!(self[0].len(), rhs.len());
+                    match check_mul_dims(self, rhs) {
+                        Ok(()) => {}
+                        Err(MatrixError::EmptyMatrix) => return vec![],
+                        Err(e) => panic!("{}", e),
+                    }
                     let row_dim = self.len();
 
                     if is_parallel {
@@ -676,33 +1331,49 @@ macro_rules! impl_base_commit_mats {
 
                         rows
                     } else {
-                        (0..row_dim)
-                            .map( |i| {
-                                let row = &self[i];
-                                let dim = rhs.len();
-
-                                // Perform matrix multiplication for single row
-                                // Assuming every column in b has the same length
-                                (0..rhs[0].len())
-                                    .map( |j| {
-                                        (0..dim).map( |k| row[k].scalar_mul(&rhs[k][j]) ).sum()
-                                    })
-                                    .collect::<Vec<$com<E>>>()
-                            })
-                            .collect::<Vec<Vec<$com<E>>>>()
+                        let mut rows = Vec::with_capacity(row_dim);
+                        self.right_mul_into(rhs, &mut rows);
+                        rows
                     }
                 }
 
-                fn left_mul(&self, lhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
-                    if lhs.is_empty() || lhs[0].is_empty() {
-                        return vec![];
-                    }
-                    if self.is_empty() || self[0].is_empty() {
-                        return vec![];
+                fn right_mul_into(&self, rhs: &Matrix<Self::Other>, out: &mut Self) {
+                    match check_mul_dims(self, rhs) {
+                        Ok(()) => {}
+                        Err(MatrixError::EmptyMatrix) => return out.clear(),
+                        Err(e) => panic!("{}", e),
                     }
+                    // Assuming every column in b has the same length
+                    fill_matrix(out, self.len(), rhs[0].len(), |i, j| {
+                        self[i].iter().zip(rhs).map(|(elem, rhs_row)| elem.scalar_mul(&rhs_row[j])).sum()
+                    });
+                }
+
+                #[inline]
+                fn try_add(&self, other: &Self) -> Result<Self, MatrixError> {
+                    check_add_dims(self, other)?;
+                    Ok(self.add(other))
+                }
+
+                #[inline]
+                fn try_left_mul(&self, lhs: &Matrix<Self::Other>, is_parallel: bool) -> Result<Self, MatrixError> {
+                    check_mul_dims(lhs, self)?;
+                    Ok(self.left_mul(lhs, is_parallel))
+                }
+
+                #[inline]
+                fn try_right_mul(&self, rhs: &Matrix<Self::Other>, is_parallel: bool) -> Result<Self, MatrixError> {
+                    check_mul_dims(self, rhs)?;
+                    Ok(self.right_mul(rhs, is_parallel))
+                }
 
+                fn left_mul(&self, lhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
                     // Check that every row in a and column in b has the same length
-                    assert_eq!(lhs[0].len(), self.len());
+                    match check_mul_dims(lhs, self) {
+                        Ok(()) => {}
+                        Err(MatrixError::EmptyMatrix) => return vec![],
+                        Err(e) => panic!("{}", e),
+                    }
                     let row_dim = lhs.len();
 
                     if is_parallel {
@@ -727,18 +1398,21 @@ macro_rules! impl_base_commit_mats {
                         rows
                     }
                     else {
-                        (0..row_dim)
-                            .map( |i| {
-                                let row = &lhs[i];
-                                let dim = self.len();
-                                (0..self[0].len())
-                                    .map( |j| {
-                                        (0..dim).map( |k| self[k][j].scalar_mul(&row[k]) ).sum()
-                                    })
-                                    .collect::<Vec<$com<E>>>()
-                            })
-                            .collect::<Vec<Vec<$com<E>>>>()
+                        let mut rows = Vec::with_capacity(row_dim);
+                        self.left_mul_into(lhs, &mut rows);
+                        rows
+                    }
+                }
+
+                fn left_mul_into(&self, lhs: &Matrix<Self::Other>, out: &mut Self) {
+                    match check_mul_dims(lhs, self) {
+                        Ok(()) => {}
+                        Err(MatrixError::EmptyMatrix) => return out.clear(),
+                        Err(e) => panic!("{}", e),
                     }
+                    fill_matrix(out, lhs.len(), self[0].len(), |i, j| {
+                        lhs[i].iter().zip(self).map(|(scalar, row)| row[j].scalar_mul(scalar)).sum()
+                    });
                 }
             }
         )*
@@ -769,20 +1443,21 @@ impl<F: Field> Mat<F> for Matrix<F> {
     type Other = F;
 
     fn add(&self, other: &Self) -> Self {
-        assert_eq!(self.len(), other.len());
-        assert_eq!(self[0].len(), other[0].len());
-        let m = self.len();
-        let n = self[0].len();
-        let mut add = Vec::with_capacity(m);
-        for i in 0..m {
-            add.push(Vec::with_capacity(n));
-            for j in 0..n {
-                add[i].push(self[i][j] + other[i][j]);
-            }
-        }
+        let mut add = Vec::with_capacity(self.len());
+        self.add_into(other, &mut add);
         add
     }
 
+    #[inline]
+    fn add_into(&self, other: &Self, out: &mut Self) {
+        add_matrices_into(self, other, out);
+    }
+
+    #[inline]
+    fn add_in_place(&mut self, other: &Self) {
+        add_matrices_in_place(self, other);
+    }
+
     #[inline]
     fn neg(&self) -> Self {
         (0..self.len())
@@ -794,43 +1469,23 @@ impl<F: Field> Mat<F> for Matrix<F> {
     }
 
     fn scalar_mul(&self, other: &Self::Other) -> Self {
-        let m = self.len();
-        let n = self[0].len();
-        let mut smul: Matrix<F> = Vec::with_capacity(m);
-        for i in 0..m {
-            smul.push(Vec::with_capacity(n));
-            for j in 0..n {
-                smul[i].push(self[i][j] * other);
-            }
-        }
-        smul
+        self.iter()
+            .map(|row| row.iter().map(|elem| *elem * other).collect::<Vec<F>>())
+            .collect::<Vec<Vec<F>>>()
     }
 
+    #[inline]
     fn transpose(&self) -> Self {
-        let mut trans = Vec::with_capacity(self[0].len());
-        for _ in 0..self[0].len() {
-            trans.push(Vec::with_capacity(self.len()));
-        }
-
-        for row in self {
-            for i in 0..row.len() {
-                // Push rows onto columns
-                trans[i].push(row[i]);
-            }
-        }
-        trans
+        transpose(self)
     }
 
     fn right_mul(&self, rhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
-        if self.is_empty() || self[0].is_empty() {
-            return vec![];
-        }
-        if rhs.is_empty() || rhs[0].is_empty() {
-            return vec![];
-        }
-
         // Check that every row in a and column in b has the same length
-        assert_eq!(self[0].len(), rhs.len());
+        match check_mul_dims(self, rhs) {
+            Ok(()) => {}
+            Err(MatrixError::EmptyMatrix) => return vec![],
+            Err(e) => panic!("{}", e),
+        }
         let row_dim = self.len();
 
         if is_parallel {
@@ -853,31 +1508,61 @@ impl<F: Field> Mat<F> for Matrix<F> {
 
             rows
         } else {
-            (0..row_dim)
-                .map(|i| {
-                    let row = &self[i];
-                    let dim = rhs.len();
-
-                    // Perform matrix multiplication for single row
-                    // Assuming every column in b has the same length
-                    (0..rhs[0].len())
-                        .map(|j| (0..dim).map(|k| row[k] * rhs[k][j]).sum())
-                        .collect::<Vec<F>>()
-                })
-                .collect::<Vec<Vec<F>>>()
+            let mut rows = Vec::with_capacity(row_dim);
+            self.right_mul_into(rhs, &mut rows);
+            rows
         }
     }
 
-    fn left_mul(&self, lhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
-        if lhs.is_empty() || lhs[0].is_empty() {
-            return vec![];
-        }
-        if self.is_empty() || self[0].is_empty() {
-            return vec![];
+    fn right_mul_into(&self, rhs: &Matrix<Self::Other>, out: &mut Self) {
+        match check_mul_dims(self, rhs) {
+            Ok(()) => {}
+            Err(MatrixError::EmptyMatrix) => return out.clear(),
+            Err(e) => panic!("{}", e),
         }
+        // Assuming every column in b has the same length
+        fill_matrix(out, self.len(), rhs[0].len(), |i, j| {
+            self[i]
+                .iter()
+                .zip(rhs)
+                .map(|(elem, rhs_row)| *elem * rhs_row[j])
+                .sum()
+        });
+    }
+
+    #[inline]
+    fn try_add(&self, other: &Self) -> Result<Self, MatrixError> {
+        check_add_dims(self, other)?;
+        Ok(self.add(other))
+    }
+
+    #[inline]
+    fn try_left_mul(
+        &self,
+        lhs: &Matrix<Self::Other>,
+        is_parallel: bool,
+    ) -> Result<Self, MatrixError> {
+        check_mul_dims(lhs, self)?;
+        Ok(self.left_mul(lhs, is_parallel))
+    }
+
+    #[inline]
+    fn try_right_mul(
+        &self,
+        rhs: &Matrix<Self::Other>,
+        is_parallel: bool,
+    ) -> Result<Self, MatrixError> {
+        check_mul_dims(self, rhs)?;
+        Ok(self.right_mul(rhs, is_parallel))
+    }
 
+    fn left_mul(&self, lhs: &Matrix<Self::Other>, is_parallel: bool) -> Self {
         // Check that every row in a and column in b has the same length
-        assert_eq!(lhs[0].len(), self.len());
+        match check_mul_dims(lhs, self) {
+            Ok(()) => {}
+            Err(MatrixError::EmptyMatrix) => return vec![],
+            Err(e) => panic!("{}", e),
+        }
         let row_dim = lhs.len();
 
         if is_parallel {
@@ -899,17 +1584,26 @@ impl<F: Field> Mat<F> for Matrix<F> {
 
             rows
         } else {
-            (0..row_dim)
-                .map(|i| {
-                    let row = &lhs[i];
-                    let dim = self.len();
-                    (0..self[0].len())
-                        .map(|j| (0..dim).map(|k| self[k][j] * row[k]).sum())
-                        .collect::<Vec<F>>()
-                })
-                .collect::<Vec<Vec<F>>>()
+            let mut rows = Vec::with_capacity(row_dim);
+            self.left_mul_into(lhs, &mut rows);
+            rows
         }
     }
+
+    fn left_mul_into(&self, lhs: &Matrix<Self::Other>, out: &mut Self) {
+        match check_mul_dims(lhs, self) {
+            Ok(()) => {}
+            Err(MatrixError::EmptyMatrix) => return out.clear(),
+            Err(e) => panic!("{}", e),
+        }
+        fill_matrix(out, lhs.len(), self[0].len(), |i, j| {
+            lhs[i]
+                .iter()
+                .zip(self)
+                .map(|(scalar, row)| row[j] * scalar)
+                .sum()
+        });
+    }
 }
 
 #[cfg(test)]
@@ -1166,12 +1860,187 @@ mod tests {
             let bneg = -b;
             let zero = b + bneg;
 
-            assert!(zero.is_zero());
+            assert!(zero.is_zero());
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_BT_add_associative() {
+            let mut rng = test_rng();
+            let mut rand_bt = || {
+                ComT::<F>(
+                    GT::rand(&mut rng),
+                    GT::rand(&mut rng),
+                    GT::rand(&mut rng),
+                    GT::rand(&mut rng),
+                )
+            };
+            let (a, b, c) = (rand_bt(), rand_bt(), rand_bt());
+
+            assert_eq!((a + b) + c, a + (b + c));
+            assert_eq!(a + b, b + a);
+            assert_eq!(a + ComT::<F>::zero(), a);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_BT_scalar_mul() {
+            let mut rng = test_rng();
+            let b = ComT::<F>(
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
+                GT::rand(&mut rng),
+            );
+            let (s, t) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+            assert_eq!(b * Fr::from(2u64), b + b);
+            assert_eq!(b * (s + t), b * s + b * t);
+            assert_eq!((b * s) * t, b * (s * t));
+            let mut bs = b;
+            bs *= s;
+            assert_eq!(bs, ComT::<F>(b.0 * s, b.1 * s, b.2 * s, b.3 * s));
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_BT_pairing_bilinear() {
+            let mut rng = test_rng();
+            let mut rand_b1 = || {
+                Com1::<F>(
+                    G1Projective::rand(&mut rng).into_affine(),
+                    G1Projective::rand(&mut rng).into_affine(),
+                )
+            };
+            let (x1, x2) = (rand_b1(), rand_b1());
+            let mut rand_b2 = || {
+                Com2::<F>(
+                    G2Projective::rand(&mut rng).into_affine(),
+                    G2Projective::rand(&mut rng).into_affine(),
+                )
+            };
+            let (y1, y2) = (rand_b2(), rand_b2());
+            let s = Fr::rand(&mut rng);
+
+            assert_eq!(
+                ComT::pairing(x1 + x2, y1),
+                ComT::pairing(x1, y1) + ComT::pairing(x2, y1)
+            );
+            assert_eq!(
+                ComT::pairing(x1, y1 + y2),
+                ComT::pairing(x1, y1) + ComT::pairing(x1, y2)
+            );
+            assert_eq!(ComT::pairing(x1 * s, y1), ComT::pairing(x1, y1) * s);
+            assert_eq!(ComT::pairing(x1, y1 * s), ComT::pairing(x1, y1) * s);
+            assert!(ComT::pairing(Com1::<F>::zero(), y1).is_zero());
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B1_sub() {
+            let mut rng = test_rng();
+            let a = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let b = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let ab = a - b;
+            let ba = b - a;
+
+            assert_eq!(ab, Com1::<F>((a.0 + -b.0).into(), (a.1 + -b.1).into()));
+            assert_eq!(ab, -ba);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B2_sub() {
+            let mut rng = test_rng();
+            let a = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+            let b = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+            let ab = a - b;
+            let ba = b - a;
+
+            assert_eq!(ab, Com2::<F>((a.0 + -b.0).into(), (a.1 + -b.1).into()));
+            assert_eq!(ab, -ba);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B_debug_display() {
+            let mut rng = test_rng();
+            let a = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let b = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+
+            let dbg = format!("{:?}", a);
+            assert!(dbg.starts_with("Com1(0x"));
+            assert_eq!(dbg, format!("{:?}", Com1::<F>(a.0, a.1)));
+            assert_ne!(dbg, format!("{:?}", Com1::<F>(a.1, a.0)));
+            assert!(format!("{:?}", b).starts_with("Com2(0x"));
+            assert_eq!(format!("{:?}", b), format!("{:?}", Com2::<F>(b.0, b.1)));
+
+            // Display shows the compressed encodings, so is shorter than Debug
+            let disp = format!("{}", a);
+            assert!(disp.starts_with("(0x"));
+            assert!(disp.len() < dbg.len());
+            assert_eq!(disp, format!("{}", Com1::<F>(a.0, a.1)));
+            assert_eq!(format!("{}", b), format!("{}", Com2::<F>(b.0, b.1)));
+        }
+
+        #[allow(non_snake_case)]
+        #[cfg(feature = "std")]
+        #[test]
+        fn test_B_hash() {
+            use std::collections::hash_map::DefaultHasher;
+            use std::collections::HashSet;
+
+            fn hash_of<T: Hash>(t: &T) -> u64 {
+                let mut hasher = DefaultHasher::new();
+                t.hash(&mut hasher);
+                hasher.finish()
+            }
+
+            let mut rng = test_rng();
+            let a = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let b = Com1::<F>(a.1, a.0);
+            let c = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+
+            // Equal commitments (however they were obtained) hash equally
+            assert_eq!(hash_of(&a), hash_of(&Com1::<F>(a.0, a.1)));
+            assert_eq!(hash_of(&(a + b - b)), hash_of(&a));
+            assert_eq!(hash_of(&c), hash_of(&(c + Com2::<F>::zero())));
+
+            let set: HashSet<Com1<F>> = vec![a, b, a, Com1::<F>(a.0, a.1)].into_iter().collect();
+            assert_eq!(set.len(), 2);
+            assert!(set.contains(&a) && set.contains(&b));
+
+            let set: HashSet<Com2<F>> = vec![c, c, Com2::<F>::zero()].into_iter().collect();
+            assert_eq!(set.len(), 2);
         }
 
-        #[allow(non_snake_case)]
+        #[allow(non_snake_case, clippy::op_ref)]
         #[test]
-        fn test_B1_sub() {
+        fn test_B1_ref_ops() {
             let mut rng = test_rng();
             let a = Com1::<F>(
                 G1Projective::rand(&mut rng).into_affine(),
@@ -1181,16 +2050,23 @@ mod tests {
                 G1Projective::rand(&mut rng).into_affine(),
                 G1Projective::rand(&mut rng).into_affine(),
             );
-            let ab = a - b;
-            let ba = b - a;
 
-            assert_eq!(ab, Com1::<F>((a.0 + -b.0).into(), (a.1 + -b.1).into()));
-            assert_eq!(ab, -ba);
+            assert!((a + (-a)).is_zero());
+            assert!((&a + &(-&a)).is_zero());
+            assert_eq!((a + b) - b, a);
+            assert_eq!(&(&a + &b) - &b, a);
+            assert_eq!(a + &b, a + b);
+            assert_eq!(a - &b, a - b);
+
+            let mut c = a;
+            c += &b;
+            c -= &b;
+            assert_eq!(c, a);
         }
 
-        #[allow(non_snake_case)]
+        #[allow(non_snake_case, clippy::op_ref)]
         #[test]
-        fn test_B2_sub() {
+        fn test_B2_ref_ops() {
             let mut rng = test_rng();
             let a = Com2::<F>(
                 G2Projective::rand(&mut rng).into_affine(),
@@ -1200,11 +2076,18 @@ mod tests {
                 G2Projective::rand(&mut rng).into_affine(),
                 G2Projective::rand(&mut rng).into_affine(),
             );
-            let ab = a - b;
-            let ba = b - a;
 
-            assert_eq!(ab, Com2::<F>((a.0 + -b.0).into(), (a.1 + -b.1).into()));
-            assert_eq!(ab, -ba);
+            assert!((a + (-a)).is_zero());
+            assert!((&a + &(-&a)).is_zero());
+            assert_eq!((a + b) - b, a);
+            assert_eq!(&(&a + &b) - &b, a);
+            assert_eq!(a + &b, a + b);
+            assert_eq!(a - &b, a - b);
+
+            let mut c = a;
+            c += &b;
+            c -= &b;
+            assert_eq!(c, a);
         }
 
         #[allow(non_snake_case)]
@@ -1264,6 +2147,46 @@ mod tests {
             assert_eq!(bres, bexp);
         }
 
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B1_scalar_mul_ops() {
+            let mut rng = test_rng();
+            let b = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let scalar = Fr::rand(&mut rng);
+            let bexp = b.scalar_mul(&scalar);
+
+            assert_eq!(b * scalar, bexp);
+            let mut bres = b;
+            bres *= scalar;
+            assert_eq!(bres, bexp);
+            let mut bres = b;
+            bres.scalar_mul_assign(&scalar);
+            assert_eq!(bres, bexp);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B2_scalar_mul_ops() {
+            let mut rng = test_rng();
+            let b = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+            let scalar = Fr::rand(&mut rng);
+            let bexp = b.scalar_mul(&scalar);
+
+            assert_eq!(b * scalar, bexp);
+            let mut bres = b;
+            bres *= scalar;
+            assert_eq!(bres, bexp);
+            let mut bres = b;
+            bres.scalar_mul_assign(&scalar);
+            assert_eq!(bres, bexp);
+        }
+
         #[allow(non_snake_case)]
         #[test]
         fn test_B1_serde() {
@@ -1308,6 +2231,50 @@ mod tests {
             assert_eq!(a, a_de);
         }
 
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_batch_B_serde() {
+            let mut rng = test_rng();
+            let coms1: Vec<Com1<F>> = (0..4)
+                .map(|_| {
+                    Com1::<F>(
+                        G1Projective::rand(&mut rng).into_affine(),
+                        G1Projective::rand(&mut rng).into_affine(),
+                    )
+                })
+                .collect();
+            let coms2: Vec<Com2<F>> = (0..4)
+                .map(|_| {
+                    Com2::<F>(
+                        G2Projective::rand(&mut rng).into_affine(),
+                        G2Projective::rand(&mut rng).into_affine(),
+                    )
+                })
+                .collect();
+
+            // Each commitment is serialized as its two group elements in order
+            assert_eq!(
+                coms1[0].compressed_size(),
+                2 * G1Affine::zero().compressed_size()
+            );
+            assert_eq!(
+                coms2[0].uncompressed_size(),
+                2 * G2Affine::zero().uncompressed_size()
+            );
+
+            let mut c_bytes = Vec::with_capacity(coms1.compressed_size());
+            coms1.serialize_compressed(&mut c_bytes).unwrap();
+            assert_eq!(c_bytes.len(), coms1.compressed_size());
+            let coms1_de = Vec::<Com1<F>>::deserialize_compressed(&c_bytes[..]).unwrap();
+            assert_eq!(coms1, coms1_de);
+
+            let mut u_bytes = Vec::with_capacity(coms2.uncompressed_size());
+            coms2.serialize_uncompressed(&mut u_bytes).unwrap();
+            assert_eq!(u_bytes.len(), coms2.uncompressed_size());
+            let coms2_de = Vec::<Com2<F>>::deserialize_uncompressed(&u_bytes[..]).unwrap();
+            assert_eq!(coms2, coms2_de);
+        }
+
         #[allow(non_snake_case)]
         #[test]
         fn test_B_pairing_zero_G1() {
@@ -1406,6 +2373,163 @@ mod tests {
             assert_eq!(exp, res);
         }
 
+        #[allow(non_snake_case)]
+        // The BW6 workaround of `multi_miller_loop` depends on the engine, not on the `bw6_761`
+        // feature
+        #[test]
+        fn test_multi_pairing_BW6() {
+            use ark_bw6_761::BW6_761;
+
+            let mut rng = test_rng();
+            let g1: Vec<_> = (0..6)
+                .map(|_| <BW6_761 as Pairing>::G1::rand(&mut rng).into_affine())
+                .collect();
+            let g2: Vec<_> = (0..6)
+                .map(|_| <BW6_761 as Pairing>::G2::rand(&mut rng).into_affine())
+                .collect();
+            let product: PairingOutput<BW6_761> = g1
+                .iter()
+                .zip(&g2)
+                .map(|(a, b)| BW6_761::pairing(*a, *b))
+                .sum();
+            assert_eq!(multi_pairing::<BW6_761>(g1, g2), product);
+        }
+
+        #[test]
+        fn test_Com1_pairing_sum_small_example() {
+            let (g1, g2) = (G1Affine::generator(), G2Affine::generator());
+            let p1 = |k: u64| g1.mul(Fr::from(k)).into_affine();
+            let p2 = |k: u64| g2.mul(Fr::from(k)).into_affine();
+            let gt = |k: u64| F::pairing(g1, g2).mul(Fr::from(k));
+
+            let coms1 = [Com1::<F>(p1(2), p1(3)), Com1::<F>(p1(1), G1Affine::zero())];
+            let coms2 = [Com2::<F>(p2(5), p2(7)), Com2::<F>(p2(1), p2(11))];
+            let res = Com1::<F>::pairing_sum(&coms1, &coms2);
+
+            // [[2*5 + 1*1, 2*7 + 1*11], [3*5 + 0*1, 3*7 + 0*11]]
+            assert_eq!(res, ComT::<F>(gt(11), gt(25), gt(15), gt(21)));
+            assert_eq!(res, ComT::<F>::pairing_sum(&coms1, &coms2));
+        }
+
+        #[test]
+        fn test_commitment_pair_round_trip() {
+            let mut rng = test_rng();
+            let c = Com1::<F>(
+                G1Projective::rand(&mut rng).into_affine(),
+                G1Projective::rand(&mut rng).into_affine(),
+            );
+            let d = Com2::<F>(
+                G2Projective::rand(&mut rng).into_affine(),
+                G2Projective::rand(&mut rng).into_affine(),
+            );
+
+            assert_eq!(c.as_pair(), (c.0, c.1));
+            assert_eq!(Com1::<F>::from_pair(c.as_pair()), c);
+            assert_eq!(d.as_pair(), (d.0, d.1));
+            assert_eq!(Com2::<F>::from_pair(d.as_pair()), d);
+        }
+
+        #[test]
+        fn test_commitment_linear_combination() {
+            let mut rng = test_rng();
+            let coeffs: Vec<Fr> = (0..5).map(|_| Fr::rand(&mut rng)).collect();
+            let coms1: Vec<Com1<F>> = (0..5)
+                .map(|_| {
+                    Com1::<F>(
+                        G1Projective::rand(&mut rng).into_affine(),
+                        G1Projective::rand(&mut rng).into_affine(),
+                    )
+                })
+                .collect();
+            let coms2: Vec<Com2<F>> = (0..5)
+                .map(|_| {
+                    Com2::<F>(
+                        G2Projective::rand(&mut rng).into_affine(),
+                        G2Projective::rand(&mut rng).into_affine(),
+                    )
+                })
+                .collect();
+
+            // Scalar-multiply and add one commitment at a time
+            let exp1: Com1<F> = coms1.iter().zip(&coeffs).map(|(c, a)| *c * *a).sum();
+            let exp2: Com2<F> = coms2.iter().zip(&coeffs).map(|(d, a)| *d * *a).sum();
+            assert_eq!(Com1::<F>::linear_combination(&coms1, &coeffs), Ok(exp1));
+            assert_eq!(Com2::<F>::linear_combination(&coms2, &coeffs), Ok(exp2));
+            assert_eq!(
+                Com1::<F>::linear_combination(&[], &[]),
+                Ok(Com1::<F>::zero())
+            );
+
+            assert_eq!(
+                Com1::<F>::linear_combination(&coms1, &coeffs[1..]),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 5,
+                    found: 4
+                })
+            );
+            assert_eq!(
+                Com2::<F>::linear_combination(&coms2[1..], &coeffs),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 4,
+                    found: 5
+                })
+            );
+        }
+
+        #[test]
+        fn test_trivial_commit() {
+            use crate::prover::{open_commit_G1, open_commit_G2};
+
+            let mut rng = test_rng();
+            let crs = CRS::<F>::generate_crs(&mut rng);
+            let x = G1Projective::rand(&mut rng).into_affine();
+            let y = G2Projective::rand(&mut rng).into_affine();
+
+            let xcom = Com1::<F>::trivial_commit(&x);
+            assert_eq!(xcom, Com1::<F>::linear_map(&x));
+            assert_eq!(xcom, Com1::<F>(G1Affine::zero(), x));
+            assert!(open_commit_G1(&xcom, &x, Fr::zero(), Fr::zero(), &crs));
+
+            let ycom = Com2::<F>::trivial_commit(&y);
+            assert_eq!(ycom, Com2::<F>::linear_map(&y));
+            assert!(open_commit_G2(&ycom, &y, Fr::zero(), Fr::zero(), &crs));
+        }
+
+        #[test]
+        fn test_compressed_commitment_round_trip() {
+            use crate::prover::{
+                commit_G1_with_randomness, commit_G2_with_randomness, open_commit_G1,
+                open_commit_G2,
+            };
+
+            let mut rng = test_rng();
+            let crs = CRS::<F>::generate_crs(&mut rng);
+            let x = G1Projective::rand(&mut rng).into_affine();
+            let y = G2Projective::rand(&mut rng).into_affine();
+            let (r1, r2) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+            let xcom = commit_G1_with_randomness(&x, &crs, r1, r2);
+            let compressed = xcom.compress();
+            assert_eq!(compressed.as_bytes().len(), xcom.compressed_size());
+            assert!(compressed.as_bytes().len() * 2 <= xcom.uncompressed_size());
+            let xcom_de = compressed.decompress();
+            assert_eq!(xcom_de, xcom);
+            assert!(open_commit_G1(&xcom_de, &x, r1, r2, &crs));
+
+            let ycom = commit_G2_with_randomness(&y, &crs, r1, r2);
+            let compressed = CompressedCom2::from(ycom);
+            assert!(compressed.as_bytes().len() * 2 <= ycom.uncompressed_size());
+            let ycom_de = Com2::<F>::from(&compressed);
+            assert_eq!(ycom_de, ycom);
+            assert!(open_commit_G2(&ycom_de, &y, r1, r2, &crs));
+
+            // A commitment built from decompressed points is the original commitment
+            let zero = Com1::<F>::zero().compress().decompress();
+            assert_eq!(zero, Com1::<F>::zero());
+            let lin = Com1::<F>(zero.0, xcom_de.1) + Com1::<F>(xcom_de.0, zero.1);
+            assert_eq!(lin, xcom);
+        }
+
         #[test]
         fn test_B_into_matrix() {
             let mut rng = test_rng();
@@ -1977,6 +3101,43 @@ mod tests {
             assert_eq!(exp, res);
         }
 
+        // R u for the 3 x 2 matrix R = [[1, 2], [0, 1], [5, 0]] and the column vector
+        // u = [(P, 2P), (3P, 4P)], i.e. [(7P, 10P), (3P, 4P), (5P, 10P)]
+        fn left_mul_case(g1gen: G1Affine) -> (Matrix<Fr>, Matrix<Com1<F>>, Matrix<Com1<F>>) {
+            let fr = |n: u64| Fr::from(n);
+            let com = |a: &str, b: &str| {
+                Com1::<F>(affine_group_new!(g1gen, a), affine_group_new!(g1gen, b))
+            };
+            let lhs = vec![vec![fr(1), fr(2)], vec![fr(0), fr(1)], vec![fr(5), fr(0)]];
+            let rhs = vec![vec![com("1", "2")], vec![com("3", "4")]];
+            let exp = vec![
+                vec![com("7", "10")],
+                vec![com("3", "4")],
+                vec![com("5", "10")],
+            ];
+            (lhs, rhs, exp)
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B1_matrix_left_mul_sequential() {
+            let mut rng = test_rng();
+            let (lhs, rhs, exp) = left_mul_case(G1Projective::rand(&mut rng).into_affine());
+            let res = rhs.left_mul(&lhs, false);
+            assert_matrix_dimensions!(res, 3, 1);
+            assert_eq!(res, exp);
+        }
+
+        #[allow(non_snake_case)]
+        #[test]
+        fn test_B1_matrix_left_mul_parallel() {
+            let mut rng = test_rng();
+            let (lhs, rhs, exp) = left_mul_case(G1Projective::rand(&mut rng).into_affine());
+            let res = rhs.left_mul(&lhs, true);
+            assert_matrix_dimensions!(res, 3, 1);
+            assert_eq!(res, exp);
+        }
+
         #[allow(non_snake_case)]
         #[test]
         fn test_B1_matrix_right_mul_entry() {
@@ -2303,6 +3464,334 @@ mod tests {
             assert_eq!(exp, res);
         }
 
+        #[test]
+        fn test_rectangular_transpose() {
+            // 2 x 3 matrix
+            let mat: Matrix<u32> = vec![vec![1, 2, 3], vec![4, 5, 6]];
+            // 3 x 2 transpose matrix
+            let exp: Matrix<u32> = vec![vec![1, 4], vec![2, 5], vec![3, 6]];
+            let res: Matrix<u32> = transpose(&mat);
+
+            assert_matrix_dimensions!(res, 3, 2);
+            assert_eq!(exp, res);
+            assert_eq!(transpose(&res), mat);
+
+            let mat: Matrix<Fr> = vec![vec![Fr::one(), Fr::from_str("2").unwrap()]];
+            assert_eq!(transpose(&transpose(&mat)), mat);
+            assert_eq!(transpose(&mat), mat.transpose());
+        }
+
+        #[test]
+        fn test_empty_transpose() {
+            let empty: Matrix<Fr> = vec![];
+            assert_eq!(transpose(&empty), empty);
+            assert_eq!(empty.transpose(), empty);
+            assert_eq!(transpose(&vec![Vec::<Fr>::new(); 3]), empty);
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_ragged_transpose() {
+            let _ = transpose(&vec![vec![1, 2], vec![3]]);
+        }
+
+        #[test]
+        fn test_mat_mul() {
+            let fr = |n: &str| Fr::from_str(n).unwrap();
+            // 2 x 3 matrix
+            let a: Matrix<Fr> = vec![
+                vec![fr("1"), fr("2"), fr("3")],
+                vec![fr("4"), fr("5"), fr("6")],
+            ];
+            // 3 x 2 matrix
+            let b: Matrix<Fr> = vec![
+                vec![fr("7"), fr("8")],
+                vec![fr("9"), fr("10")],
+                vec![fr("11"), fr("12")],
+            ];
+
+            // 2 x 2 product
+            let exp: Matrix<Fr> = vec![vec![fr("58"), fr("64")], vec![fr("139"), fr("154")]];
+            let res: Matrix<Fr> = mat_mul(&a, &b);
+            assert_matrix_dimensions!(res, 2, 2);
+            assert_eq!(exp, res);
+            assert_eq!(res, a.right_mul(&b, false));
+
+            // 3 x 3 product
+            let exp: Matrix<Fr> = vec![
+                vec![fr("39"), fr("54"), fr("69")],
+                vec![fr("49"), fr("68"), fr("87")],
+                vec![fr("59"), fr("82"), fr("105")],
+            ];
+            let res: Matrix<Fr> = mat_mul(&b, &a);
+            assert_matrix_dimensions!(res, 3, 3);
+            assert_eq!(exp, res);
+        }
+
+        #[test]
+        fn test_mat_mul_identity() {
+            let mut rng = test_rng();
+            let mat: Matrix<Fr> = vec![
+                vec![Fr::rand(&mut rng), Fr::rand(&mut rng), Fr::rand(&mut rng)],
+                vec![Fr::rand(&mut rng), Fr::rand(&mut rng), Fr::rand(&mut rng)],
+            ];
+            let id2: Matrix<Fr> = vec![vec![Fr::one(), Fr::zero()], vec![Fr::zero(), Fr::one()]];
+            let id3: Matrix<Fr> = vec![
+                vec![Fr::one(), Fr::zero(), Fr::zero()],
+                vec![Fr::zero(), Fr::one(), Fr::zero()],
+                vec![Fr::zero(), Fr::zero(), Fr::one()],
+            ];
+
+            assert_eq!(mat_mul(&id2, &mat), mat);
+            assert_eq!(mat_mul(&mat, &id3), mat);
+        }
+
+        #[test]
+        #[should_panic(expected = "cannot multiply a 2 x 3 matrix by a 2 x 3 matrix")]
+        fn test_mat_mul_dimension_mismatch() {
+            let mat: Matrix<Fr> = vec![vec![Fr::one(); 3]; 2];
+            let _ = mat_mul(&mat, &mat);
+        }
+
+        #[test]
+        fn test_hadamard() {
+            let f = |x: u64| Fr::from(x);
+            let a: Matrix<Fr> = vec![vec![f(1), f(2), f(3)], vec![f(4), f(5), f(6)]];
+            let b: Matrix<Fr> = vec![vec![f(2), f(0), f(1)], vec![f(3), f(1), f(2)]];
+
+            let res = hadamard(&a, &b).unwrap();
+            assert_matrix_dimensions!(res, 2, 3);
+            assert_eq!(res, vec![vec![f(2), f(0), f(3)], vec![f(12), f(5), f(12)]]);
+            assert_eq!(hadamard(&a, &b), hadamard(&b, &a));
+            // Scaling is the Hadamard product with a constant matrix
+            assert_eq!(
+                hadamard(&a, &vec![vec![f(3); 3]; 2]),
+                Ok(a.scalar_mul(&f(3)))
+            );
+        }
+
+        #[test]
+        fn test_hadamard_errors() {
+            let a: Matrix<Fr> = vec![vec![Fr::one(); 3]; 2];
+            let b: Matrix<Fr> = vec![vec![Fr::one(); 2]; 2];
+            let c: Matrix<Fr> = vec![vec![Fr::one(); 3]; 3];
+            assert_eq!(
+                hadamard(&a, &b),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 3,
+                    found: 2
+                })
+            );
+            assert_eq!(
+                hadamard(&a, &c),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 2,
+                    found: 3
+                })
+            );
+            assert_eq!(hadamard(&a, &vec![]), Err(MatrixError::EmptyMatrix));
+        }
+
+        #[test]
+        fn test_matrix_from_entries() {
+            let two = Fr::from(2u64);
+            let five = Fr::from(5u64);
+            let mat =
+                matrix_from_entries(3, 2, &[(0, 1, two), (2, 0, five), (0, 1, five)]).unwrap();
+            assert_matrix_dimensions!(mat, 3, 2);
+            assert_eq!(
+                mat,
+                vec![
+                    vec![Fr::zero(), five],
+                    vec![Fr::zero(), Fr::zero()],
+                    vec![five, Fr::zero()],
+                ]
+            );
+            assert_eq!(
+                matrix_from_entries::<Fr>(2, 2, &[]),
+                Ok(vec![vec![Fr::zero(); 2]; 2])
+            );
+        }
+
+        #[test]
+        fn test_matrix_from_entries_out_of_bounds() {
+            let one = Fr::one();
+            assert_eq!(
+                matrix_from_entries(3, 2, &[(0, 0, one), (3, 0, one)]),
+                Err(MatrixError::IndexOutOfBounds { row: 3, col: 0 })
+            );
+            assert_eq!(
+                matrix_from_entries(3, 2, &[(1, 2, one)]),
+                Err(MatrixError::IndexOutOfBounds { row: 1, col: 2 })
+            );
+            assert_eq!(
+                matrix_from_entries(0, 0, &[(0, 0, one)]),
+                Err(MatrixError::IndexOutOfBounds { row: 0, col: 0 })
+            );
+        }
+
+        #[test]
+        fn test_matrix_try_add_errors() {
+            let a: Matrix<Fr> = vec![vec![Fr::one(); 3]; 2];
+            let b: Matrix<Fr> = vec![vec![Fr::one(); 3]; 3];
+            let c: Matrix<Fr> = vec![vec![Fr::one(); 2]; 2];
+            let empty: Matrix<Fr> = vec![];
+
+            assert_eq!(a.try_add(&a), Ok(a.add(&a)));
+            assert_eq!(
+                a.try_add(&b),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 2,
+                    found: 3
+                })
+            );
+            assert_eq!(
+                a.try_add(&c),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 3,
+                    found: 2
+                })
+            );
+            assert_eq!(a.try_add(&empty), Err(MatrixError::EmptyMatrix));
+            assert_eq!(empty.try_add(&a), Err(MatrixError::EmptyMatrix));
+        }
+
+        #[test]
+        fn test_matrix_try_left_mul_errors() {
+            let mut rng = test_rng();
+            let g1 = G1Projective::rand(&mut rng).into_affine();
+            // 3 x 1 matrix of B1 elements
+            let coms: Matrix<Com1<F>> = vec![vec![Com1::<F>(g1, g1)]; 3];
+            let lhs: Matrix<Fr> = vec![vec![Fr::one(); 3]; 2];
+            let bad_lhs: Matrix<Fr> = vec![vec![Fr::one(); 2]; 2];
+
+            assert_eq!(
+                coms.try_left_mul(&lhs, false),
+                Ok(coms.left_mul(&lhs, false))
+            );
+            assert_eq!(
+                coms.try_left_mul(&bad_lhs, false),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 2,
+                    found: 3
+                })
+            );
+            assert_eq!(
+                coms.try_left_mul(&vec![], false),
+                Err(MatrixError::EmptyMatrix)
+            );
+            // The panicking variant keeps returning the empty matrix on empty input
+            assert!(coms.left_mul(&vec![], false).is_empty());
+
+            let fmat: Matrix<Fr> = vec![vec![Fr::one(); 2]; 3];
+            assert_eq!(
+                fmat.try_left_mul(&bad_lhs, false),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 2,
+                    found: 3
+                })
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "matrix dimension mismatch: expected 2, found 3")]
+        fn test_matrix_left_mul_mismatch_panics() {
+            let fmat: Matrix<Fr> = vec![vec![Fr::one(); 2]; 3];
+            let _ = fmat.left_mul(&vec![vec![Fr::one(); 2]; 2], false);
+        }
+
+        #[test]
+        fn test_matrix_try_right_mul_errors() {
+            let mut rng = test_rng();
+            let g1 = G1Projective::rand(&mut rng).into_affine();
+            // 1 x 3 matrix of B1 elements
+            let coms: Matrix<Com1<F>> = vec![vec![Com1::<F>(g1, g1); 3]];
+            let rhs: Matrix<Fr> = vec![vec![Fr::one(); 2]; 3];
+            let bad_rhs: Matrix<Fr> = vec![vec![Fr::one(); 2]; 2];
+
+            assert_eq!(
+                coms.try_right_mul(&rhs, false),
+                Ok(coms.right_mul(&rhs, false))
+            );
+            assert_eq!(
+                coms.try_right_mul(&bad_rhs, false),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 3,
+                    found: 2
+                })
+            );
+            assert_eq!(
+                coms.try_right_mul(&vec![], false),
+                Err(MatrixError::EmptyMatrix)
+            );
+            assert!(coms.right_mul(&vec![], false).is_empty());
+
+            let fmat: Matrix<Fr> = vec![vec![Fr::one(); 3]; 2];
+            assert_eq!(
+                fmat.try_right_mul(&bad_rhs, false),
+                Err(MatrixError::DimensionMismatch {
+                    expected: 3,
+                    found: 2
+                })
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "matrix dimension mismatch: expected 3, found 2")]
+        fn test_matrix_right_mul_mismatch_panics() {
+            let fmat: Matrix<Fr> = vec![vec![Fr::one(); 3]; 2];
+            let _ = fmat.right_mul(&vec![vec![Fr::one(); 2]; 2], false);
+        }
+
+        // M * R = (R^T * M^T)^T, for both field and commitment matrices and both evaluation strategies
+        #[test]
+        fn test_matrix_mul_against_transpose() {
+            let mut rng = test_rng();
+            let rand_fr = |rows: usize, cols: usize, rng: &mut _| -> Matrix<Fr> {
+                (0..rows)
+                    .map(|_| (0..cols).map(|_| Fr::rand(rng)).collect())
+                    .collect()
+            };
+            let coms: Matrix<Com1<F>> = (0..2)
+                .map(|_| {
+                    (0..3)
+                        .map(|_| {
+                            Com1::<F>(
+                                G1Projective::rand(&mut rng).into_affine(),
+                                G1Projective::rand(&mut rng).into_affine(),
+                            )
+                        })
+                        .collect()
+                })
+                .collect();
+            let fmat = rand_fr(2, 3, &mut rng);
+            let rhs = rand_fr(3, 4, &mut rng);
+            let lhs = rand_fr(4, 2, &mut rng);
+
+            for is_parallel in [false, true] {
+                assert_eq!(
+                    coms.right_mul(&rhs, is_parallel),
+                    coms.transpose()
+                        .left_mul(&rhs.transpose(), is_parallel)
+                        .transpose()
+                );
+                assert_eq!(
+                    coms.left_mul(&lhs, is_parallel),
+                    coms.transpose()
+                        .right_mul(&lhs.transpose(), is_parallel)
+                        .transpose()
+                );
+                assert_eq!(fmat.right_mul(&rhs, is_parallel), mat_mul(&fmat, &rhs));
+                assert_eq!(fmat.left_mul(&lhs, is_parallel), mat_mul(&lhs, &fmat));
+                assert_eq!(
+                    fmat.right_mul(&rhs, is_parallel),
+                    fmat.transpose()
+                        .left_mul(&rhs.transpose(), is_parallel)
+                        .transpose()
+                );
+            }
+        }
+
         #[test]
         fn test_field_matrix_neg() {
             // 3 x 3 matrix
@@ -0,0 +1,288 @@
+//! Contains a Fiat-Shamir transform turning Groth-Sahai proofs for pairing-product equations into
+//! signatures of knowledge over a message.
+//!
+//! A Groth-Sahai proof by itself does not depend on anything but the statement and the
+//! commitments, so it can be detached and reused. To bind a proof to a message `msg`, the prover
+//! additionally commits to an auxiliary variable `Y_0 = 0` in `G2` and hashes the full
+//! [`CRS`], the statement, all commitments and `msg` with a pluggable [`Digest`] to derive
+//! challenges `h = (h_1, ..., h_m)`. It then proves the augmented equation obtained by appending
+//! `h` as the column of `Γ` for `Y_0`, which holds for the same target since `Y_0 = 0`.
+//!
+//! The verifier recomputes `h` from the transcript, so a proof for one message (or a
+//! re-randomized one) does not verify for any other message, CRS or statement.
+
+use ark_ec::pairing::Pairing;
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{rand::Rng, vec::Vec};
+use digest::Digest;
+
+use crate::data_structures::{Com1, Com2};
+use crate::generator::CRS;
+use crate::prover::{batch_commit_G1, batch_commit_G2, prove_ppe, EquProof};
+use crate::statement::PPE;
+use crate::verifier::{verify_ppe, VerificationError};
+
+// Domain separator for the transcript of a signature of knowledge
+const DOMAIN: &[u8] = b"groth-sahai/fiat-shamir/ppe/v1";
+
+/// A signature of knowledge of a witness satisfying a pairing-product equation, as produced by
+/// [`sign_knowledge`].
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct KnowledgeSignature<E: Pairing> {
+    pub xcoms: Vec<Com1<E>>,
+    pub ycoms: Vec<Com2<E>>,
+    /// The commitment to the auxiliary variable `Y_0 = 0`.
+    pub tag_com: Com2<E>,
+    pub proof: EquProof<E>,
+}
+
+// Absorbs the canonical compressed serialization of `value` into the transcript.
+pub(crate) fn absorb<D: Digest, T: CanonicalSerialize>(hasher: &mut D, value: &T) {
+    let mut bytes = Vec::with_capacity(value.compressed_size());
+    value
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a vector cannot fail");
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(&bytes);
+}
+
+// Derives one challenge per `X` variable from the full transcript of a signature of knowledge.
+fn challenges<D: Digest, E: Pairing>(
+    msg: &[u8],
+    equ: &PPE<E>,
+    crs: &CRS<E>,
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    tag_com: &Com2<E>,
+) -> Vec<E::ScalarField> {
+    let mut hasher = D::new();
+    hasher.update(DOMAIN);
+    absorb(&mut hasher, crs);
+    absorb(&mut hasher, equ);
+    absorb(&mut hasher, &xcoms.to_vec());
+    absorb(&mut hasher, &ycoms.to_vec());
+    absorb(&mut hasher, tag_com);
+    hasher.update((msg.len() as u64).to_le_bytes());
+    hasher.update(msg);
+    let seed = hasher.finalize();
+
+    (0..equ.gamma.len() as u64)
+        .map(|i| {
+            let mut hasher = D::new();
+            hasher.update(&seed);
+            hasher.update(i.to_le_bytes());
+            E::ScalarField::from_le_bytes_mod_order(&hasher.finalize())
+        })
+        .collect()
+}
+
+// Appends the auxiliary variable `Y_0` with the challenges as its column of `Γ` to `equ`.
+fn augment<E: Pairing>(equ: &PPE<E>, challenges: &[E::ScalarField]) -> PPE<E> {
+    let mut a_consts = equ.a_consts.clone();
+    a_consts.push(E::G1Affine::zero());
+    let gamma = equ
+        .gamma
+        .iter()
+        .zip(challenges)
+        .map(|(row, h)| {
+            let mut row = row.clone();
+            row.push(*h);
+            row
+        })
+        .collect();
+    PPE::<E> {
+        a_consts,
+        b_consts: equ.b_consts.clone(),
+        gamma,
+        target: equ.target,
+    }
+}
+
+/// Signs `msg` with a proof of knowledge of variables `xvars` and `yvars` satisfying the
+/// pairing-product equation `equ`, hashing the transcript with `D`.
+pub fn sign_knowledge<D, CR, E>(
+    msg: &[u8],
+    xvars: &[E::G1Affine],
+    yvars: &[E::G2Affine],
+    equ: &PPE<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> KnowledgeSignature<E>
+where
+    D: Digest,
+    E: Pairing,
+    CR: Rng,
+{
+    let mut aug_yvars = yvars.to_vec();
+    aug_yvars.push(E::G2Affine::zero());
+
+    let xcoms = batch_commit_G1(xvars, crs, rng);
+    let aug_ycoms = batch_commit_G2(&aug_yvars, crs, rng);
+    let (ycoms, tag_com) = aug_ycoms.coms.split_at(yvars.len());
+    let tag_com = tag_com[0];
+
+    let h = challenges::<D, E>(msg, equ, crs, &xcoms.coms, ycoms, &tag_com);
+    let proof = prove_ppe(
+        &augment(equ, &h),
+        xvars,
+        &aug_yvars,
+        &xcoms,
+        &aug_ycoms,
+        crs,
+        rng,
+    );
+
+    KnowledgeSignature {
+        xcoms: xcoms.coms.clone(),
+        ycoms: ycoms.to_vec(),
+        tag_com,
+        proof,
+    }
+}
+
+/// Verifies a signature of knowledge `sig` of a witness for `equ` over `msg`, produced by
+/// [`sign_knowledge`] with the same digest `D`.
+pub fn verify_knowledge<D, E>(
+    msg: &[u8],
+    sig: &KnowledgeSignature<E>,
+    equ: &PPE<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError>
+where
+    D: Digest,
+    E: Pairing,
+{
+    let h = challenges::<D, E>(msg, equ, crs, &sig.xcoms, &sig.ycoms, &sig.tag_com);
+    let mut aug_ycoms = sig.ycoms.clone();
+    aug_ycoms.push(sig.tag_com);
+    verify_ppe(&augment(equ, &h), &sig.xcoms, &aug_ycoms, &sig.proof, crs)
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::{pairing::PairingOutput, CurveGroup};
+    use ark_ff::{UniformRand, Zero};
+    use ark_std::ops::Mul;
+    use ark_std::test_rng;
+    use sha2::Sha256;
+
+    use super::*;
+    use crate::generator::AbstractCrs;
+    use crate::prover::rerandomize_proof;
+
+    type G1Affine = <F as Pairing>::G1Affine;
+    type G2Affine = <F as Pairing>::G2Affine;
+    type Fr = <F as Pairing>::ScalarField;
+    type GT = PairingOutput<F>;
+
+    // e(X_1, c_1) * e(c_2, Y_1) * e(X_2, Y_1)^g = t
+    fn statement_and_witness(
+        crs: &CRS<F>,
+        rng: &mut impl Rng,
+    ) -> (PPE<F>, Vec<G1Affine>, Vec<G2Affine>) {
+        let xvars: Vec<G1Affine> = vec![
+            crs.g1_gen.mul(Fr::rand(rng)).into_affine(),
+            crs.g1_gen.mul(Fr::rand(rng)).into_affine(),
+        ];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(rng)).into_affine()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(rng)).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![
+            crs.g2_gen.mul(Fr::rand(rng)).into_affine(),
+            G2Affine::zero(),
+        ];
+        let gamma = vec![vec![Fr::zero()], vec![Fr::rand(rng)]];
+        let target: GT = F::pairing(xvars[0], b_consts[0])
+            + F::pairing(a_consts[0], yvars[0])
+            + F::pairing(xvars[1], yvars[0].mul(gamma[1][0]).into_affine());
+        let equ = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+        (equ, xvars, yvars)
+    }
+
+    #[test]
+    fn test_signature_of_knowledge_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let (equ, xvars, yvars) = statement_and_witness(&crs, &mut rng);
+
+        let msg = b"groth-sahai signature of knowledge";
+        let sig = sign_knowledge::<Sha256, _, F>(msg, &xvars, &yvars, &equ, &crs, &mut rng);
+        assert_eq!(verify_knowledge::<Sha256, F>(msg, &sig, &equ, &crs), Ok(()));
+
+        // Survives serialization
+        let mut bytes = Vec::new();
+        sig.serialize_compressed(&mut bytes).unwrap();
+        let sig_de = KnowledgeSignature::<F>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(
+            verify_knowledge::<Sha256, F>(msg, &sig_de, &equ, &crs),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_signature_of_knowledge_binds_message() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let (equ, xvars, yvars) = statement_and_witness(&crs, &mut rng);
+
+        let msg = b"groth-sahai signature of knowledge".to_vec();
+        let sig = sign_knowledge::<Sha256, _, F>(&msg, &xvars, &yvars, &equ, &crs, &mut rng);
+        for i in 0..msg.len() {
+            let mut other = msg.clone();
+            other[i] ^= 1;
+            assert!(verify_knowledge::<Sha256, F>(&other, &sig, &equ, &crs).is_err());
+        }
+        assert!(verify_knowledge::<Sha256, F>(&msg[1..], &sig, &equ, &crs).is_err());
+    }
+
+    #[test]
+    fn test_signature_of_knowledge_binds_statement_and_CRS() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let (equ, xvars, yvars) = statement_and_witness(&crs, &mut rng);
+        let msg = b"msg";
+        let sig = sign_knowledge::<Sha256, _, F>(msg, &xvars, &yvars, &equ, &crs, &mut rng);
+
+        // The same equation up to an additional, trivially satisfied constant
+        let mut other_equ = equ.clone();
+        other_equ.b_consts[1] = crs.g2_gen;
+        other_equ.target += F::pairing(xvars[1], crs.g2_gen);
+        assert!(verify_knowledge::<Sha256, F>(msg, &sig, &other_equ, &crs).is_err());
+
+        // A CRS that only differs in a value the verification equation does not use
+        let mut other_crs = crs.clone();
+        other_crs.gt_gen += crs.gt_gen;
+        assert!(verify_knowledge::<Sha256, F>(msg, &sig, &equ, &other_crs).is_err());
+
+        // Re-randomizing the commitments and the proof changes the challenges
+        let h = challenges::<Sha256, F>(msg, &equ, &crs, &sig.xcoms, &sig.ycoms, &sig.tag_com);
+        let mut aug_ycoms = sig.ycoms.clone();
+        aug_ycoms.push(sig.tag_com);
+        let (xcoms, mut ycoms, proof) = rerandomize_proof(
+            &augment(&equ, &h),
+            &sig.xcoms,
+            &aug_ycoms,
+            &sig.proof,
+            &crs,
+            &mut rng,
+        )
+        .unwrap();
+        let tag_com = ycoms.pop().unwrap();
+        let mauled = KnowledgeSignature {
+            xcoms,
+            ycoms,
+            tag_com,
+            proof,
+        };
+        assert!(verify_knowledge::<Sha256, F>(msg, &mauled, &equ, &crs).is_err());
+    }
+}
@@ -11,15 +11,27 @@
 //!    1) Perfect soundness string (i.e. perfectly binding), or
 //!    2) Composable witness-indistinguishability string (i.e. perfectly hiding)
 
-use crate::data_structures::{Com1, Com2};
+use crate::data_structures::{Com1, Com2, Hex, B, B1, B2};
+use crate::fiat_shamir::absorb;
 
 use ark_ec::{
     pairing::{Pairing, PairingOutput},
-    CurveGroup,
+    scalar_mul::BatchMulPreprocessing,
+    AffineRepr, CurveGroup,
 };
 use ark_ff::{UniformRand, Zero};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{ops::Mul, rand::Rng};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::{
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    ops::{Deref, Mul},
+    rand::{Rng, SeedableRng},
+    vec::Vec,
+};
+use digest::{consts::U32, Digest};
+use rand_chacha::ChaCha20Rng;
 
 /// An abstract trait for denoting how to generate a CRS
 pub trait AbstractCrs<E: Pairing> {
@@ -31,17 +43,567 @@ pub trait AbstractCrs<E: Pairing> {
         R: Rng;
 }
 
+/// The commitment keys of a Groth-Sahai instantiation, abstracting over the dimension of the
+/// commitment groups and the linear maps into them.
+///
+/// A key holds `DIM` elements `u` for committing to [`B1`] and `DIM` elements `v` for committing
+/// to [`B2`]: group elements are committed to with all of them, scalars with the first `DIM - 1`.
+/// [`CRS`] implements this for SXDH, where `DIM = 2`; a DLIN instantiation would use `DIM = 3`.
+///
+/// So far, only the single-element commitments with given randomness, such as
+/// [`commit_G1_with_randomness`](crate::prover::commit_G1_with_randomness), go through this
+/// trait. The batch commitments, the provers and the verifiers are still written against the
+/// two-dimensional keys of [`CRS`], so a DLIN instantiation would have to generalize them too.
+#[allow(non_snake_case)]
+pub trait CommitmentKey<E: Pairing> {
+    /// The base of the commitment group.
+    type C1: B1<E>;
+    /// The extension of the commitment group.
+    type C2: B2<E>;
+    /// The number of random scalars in a commitment to a group element.
+    const DIM: usize;
+
+    /// The key for committing to `B1`.
+    fn u(&self) -> &[Self::C1];
+    /// The key for committing to `B2`.
+    fn v(&self) -> &[Self::C2];
+    /// The linear map from G1 to B1.
+    fn linear_map_G1(&self, x: &E::G1Affine) -> Self::C1;
+    /// The linear map from G2 to B2.
+    fn linear_map_G2(&self, y: &E::G2Affine) -> Self::C2;
+    /// The linear map from the scalar field to B1.
+    fn scalar_linear_map_B1(&self, x: &E::ScalarField) -> Self::C1;
+    /// The linear map from the scalar field to B2.
+    fn scalar_linear_map_B2(&self, y: &E::ScalarField) -> Self::C2;
+
+    /// Commits to `x` using the `DIM` random scalars in `rand`, i.e. `i_1(x) + sum_i r_i u_i`.
+    fn commit_G1(&self, x: &E::G1Affine, rand: &[E::ScalarField]) -> Self::C1 {
+        assert_eq!(rand.len(), Self::DIM);
+        let rand_com = combine(self.u(), rand);
+        // The identity contributes nothing to i_1(x)
+        if x.is_zero() {
+            rand_com
+        } else {
+            self.linear_map_G1(x) + rand_com
+        }
+    }
+
+    /// Commits to `y` using the `DIM` random scalars in `rand`, i.e. `i_2(y) + sum_i s_i v_i`.
+    fn commit_G2(&self, y: &E::G2Affine, rand: &[E::ScalarField]) -> Self::C2 {
+        assert_eq!(rand.len(), Self::DIM);
+        let rand_com = combine(self.v(), rand);
+        // The identity contributes nothing to i_2(y)
+        if y.is_zero() {
+            rand_com
+        } else {
+            self.linear_map_G2(y) + rand_com
+        }
+    }
+
+    /// Commits to the scalar `x` using the `DIM - 1` random scalars in `rand`, i.e.
+    /// `i_1'(x) + sum_i r_i u_i`.
+    fn commit_scalar_to_B1(&self, x: &E::ScalarField, rand: &[E::ScalarField]) -> Self::C1 {
+        assert_eq!(rand.len(), Self::DIM - 1);
+        self.scalar_linear_map_B1(x) + combine(self.u(), rand)
+    }
+
+    /// Commits to the scalar `y` using the `DIM - 1` random scalars in `rand`, i.e.
+    /// `i_2'(y) + sum_i s_i v_i`.
+    fn commit_scalar_to_B2(&self, y: &E::ScalarField, rand: &[E::ScalarField]) -> Self::C2 {
+        assert_eq!(rand.len(), Self::DIM - 1);
+        self.scalar_linear_map_B2(y) + combine(self.v(), rand)
+    }
+}
+
+// Computes sum_i r_i key_i over the first `rand.len()` elements of the key.
+fn combine<E: Pairing, C>(key: &[C], rand: &[E::ScalarField]) -> C
+where
+    C: B<E> + Mul<E::ScalarField, Output = C>,
+{
+    key.iter().zip(rand).map(|(k, r)| *k * *r).sum()
+}
+
+/// The mode in which the commitment keys of a [`CRS`] were instantiated.
+///
+/// The two modes are computationally indistinguishable under SXDH, but give different guarantees:
+/// - [`Binding`](CRSType::Binding): `u_2`, `v_2` lie in the span of `u_1`, `v_1`, so commitments are
+///   perfectly binding. Proofs are perfectly sound and committed group elements can be
+///   extracted with the trapdoor of the setup.
+/// - [`Hiding`](CRSType::Hiding): `u_2`, `v_2` are independent of `u_1`, `v_1`, so commitments are
+///   perfectly hiding and proofs are perfectly witness-indistinguishable (i.e. zero-knowledge
+///   can be simulated), but nothing can be extracted.
+///
+/// A CRS derived by [`CRS::from_hash`] is in neither mode, but
+/// [`Transparent`](CRSType::Transparent): `u_2`, `v_2` are independent of `u_1`, `v_1`, so
+/// commitments to group elements are perfectly hiding, but so are `u_2 + ι_1(P_1)`,
+/// `v_2 + ι_2(P_2)`, so commitments to scalars are perfectly binding (and only computationally
+/// hiding). Nobody knows a trapdoor for extracting or simulating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CRSType {
+    // The order of the variants must be preserved for serialization.
+    Binding,
+    Hiding,
+    Transparent,
+}
+
+// Implement the `Valid` trait required for implementing `CanonicalDeserialize`.
+impl Valid for CRSType {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalSerialize for CRSType {
+    #[inline]
+    fn serialize_with_mode<W: ark_serialize::Write>(
+        &self,
+        writer: W,
+        _compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let b = match self {
+            CRSType::Binding => 0u8,
+            CRSType::Hiding => 1,
+            CRSType::Transparent => 2,
+        };
+        u8::serialize_compressed(&b, writer)
+    }
+
+    fn serialized_size(&self, _compress: Compress) -> usize {
+        1 // 1 byte
+    }
+}
+
+impl CanonicalDeserialize for CRSType {
+    #[inline]
+    fn deserialize_with_mode<R: ark_serialize::Read>(
+        reader: R,
+        _compress: Compress,
+        _validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        match u8::deserialize_compressed(reader)? {
+            0 => Ok(CRSType::Binding),
+            1 => Ok(CRSType::Hiding),
+            2 => Ok(CRSType::Transparent),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
 /// Contains the commitment keys and bilinear group generators
-#[derive(Clone, Debug, CanonicalSerialize, CanonicalDeserialize)]
+#[derive(Clone, CanonicalSerialize, CanonicalDeserialize)]
 pub struct CRS<E: Pairing> {
     pub u: Vec<Com1<E>>,
     pub v: Vec<Com2<E>>,
     pub g1_gen: E::G1Affine,
     pub g2_gen: E::G2Affine,
     pub gt_gen: PairingOutput<E>,
+    pub crs_type: CRSType,
+}
+
+// Prints the commitment keys and the coordinates of the generators in hex
+impl<E: Pairing> Debug for CRS<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CRS")
+            .field("u", &self.u)
+            .field("v", &self.v)
+            .field("g1_gen", &Hex(&self.g1_gen))
+            .field("g2_gen", &Hex(&self.g2_gen))
+            .field("gt_gen", &Hex(&self.gt_gen))
+            .field("crs_type", &self.crs_type)
+            .finish()
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A [`CRSType`] lifted to the type level, implemented by the zero-sized markers [`Binding`] and
+/// [`Hiding`] only.
+pub trait CRSMode: sealed::Sealed + Copy + Debug + 'static {
+    /// The runtime mode corresponding to this marker.
+    const TYPE: CRSType;
+}
+
+/// Marks a [`TypedCRS`] as [binding](CRSType::Binding).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Binding;
+
+/// Marks a [`TypedCRS`] as [hiding](CRSType::Hiding).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Hiding;
+
+impl sealed::Sealed for Binding {}
+impl sealed::Sealed for Hiding {}
+
+impl CRSMode for Binding {
+    const TYPE: CRSType = CRSType::Binding;
+}
+
+impl CRSMode for Hiding {
+    const TYPE: CRSType = CRSType::Hiding;
+}
+
+/// A [`CRS`] whose mode is known at compile time, so that functions which are only meaningful in
+/// one mode, such as [`simulate_ppe`](crate::prover::simulate_ppe), cannot be called with a CRS
+/// of the other.
+///
+/// It dereferences to the underlying [`CRS`], so it can be passed to all mode-agnostic functions,
+/// e.g. for committing, proving and verifying, as it is.
+///
+/// ```compile_fail
+/// use ark_bls12_381::Bls12_381 as F;
+/// use ark_std::test_rng;
+/// use groth_sahai::prover::simulate_ppe;
+/// use groth_sahai::statement::PPE;
+/// use groth_sahai::CRS;
+///
+/// let mut rng = test_rng();
+/// let (binding, _) = CRS::<F>::generate_binding(&mut rng);
+/// let (_, trapdoor) = CRS::<F>::generate_hiding_with_trapdoor(&mut rng);
+/// let equ = PPE::<F>::equality_G1_G2(&binding);
+/// // Proofs can only be simulated under a hiding CRS
+/// simulate_ppe(&equ, &[], &trapdoor, &binding, &mut rng);
+/// ```
+pub struct TypedCRS<E: Pairing, M: CRSMode> {
+    crs: CRS<E>,
+    mode: PhantomData<M>,
+}
+
+/// A [`TypedCRS`] in [binding](CRSType::Binding) mode.
+pub type BindingCRS<E> = TypedCRS<E, Binding>;
+
+/// A [`TypedCRS`] in [hiding](CRSType::Hiding) mode.
+pub type HidingCRS<E> = TypedCRS<E, Hiding>;
+
+impl<E: Pairing, M: CRSMode> TypedCRS<E, M> {
+    /// Wraps `crs` if its [`CRSType`] is the mode `M`, e.g. after deserializing it.
+    ///
+    /// This only checks the type the CRS was marked with; whether the keys are actually binding
+    /// or hiding can only be checked with the corresponding trapdoor.
+    pub fn new(crs: CRS<E>) -> Option<Self> {
+        (crs.crs_type == M::TYPE).then_some(Self {
+            crs,
+            mode: PhantomData,
+        })
+    }
+
+    /// Returns the underlying, untyped [`CRS`].
+    pub fn into_inner(self) -> CRS<E> {
+        self.crs
+    }
+}
+
+impl<E: Pairing, M: CRSMode> Clone for TypedCRS<E, M> {
+    fn clone(&self) -> Self {
+        Self {
+            crs: self.crs.clone(),
+            mode: PhantomData,
+        }
+    }
+}
+
+impl<E: Pairing, M: CRSMode> Debug for TypedCRS<E, M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Debug::fmt(&self.crs, f)
+    }
+}
+
+impl<E: Pairing, M: CRSMode> Deref for TypedCRS<E, M> {
+    type Target = CRS<E>;
+
+    fn deref(&self) -> &CRS<E> {
+        &self.crs
+    }
+}
+
+impl<E: Pairing, M: CRSMode> AsRef<CRS<E>> for TypedCRS<E, M> {
+    fn as_ref(&self) -> &CRS<E> {
+        &self.crs
+    }
+}
+
+impl<E: Pairing, M: CRSMode> From<TypedCRS<E, M>> for CRS<E> {
+    fn from(crs: TypedCRS<E, M>) -> Self {
+        crs.crs
+    }
+}
+
+/// The SXDH commitment keys of a [`CRS`] as named elements, rather than as the lists `u` and
+/// `v` that generic code over [`CommitmentKey`] works with.
+///
+/// Converting from a [`CRS`] with [`CRS::keys`] or [`SXDHKeys::try_from`], and back with
+/// [`CRS::set_keys`], preserves the keys exactly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SXDHKeys<E: Pairing> {
+    pub u1: Com1<E>,
+    pub u2: Com1<E>,
+    pub v1: Com2<E>,
+    pub v2: Com2<E>,
+}
+
+impl<E: Pairing> SXDHKeys<E> {
+    /// The keys for [`B1`] in the list form `u = [u_1, u_2]` of [`CRS::u`].
+    pub fn u(&self) -> [Com1<E>; 2] {
+        [self.u1, self.u2]
+    }
+
+    /// The keys for [`B2`] in the list form `v = [v_1, v_2]` of [`CRS::v`].
+    pub fn v(&self) -> [Com2<E>; 2] {
+        [self.v1, self.v2]
+    }
+}
+
+impl<E: Pairing> TryFrom<&CRS<E>> for SXDHKeys<E> {
+    type Error = CRSError;
+
+    /// Names the elements of `crs.u` and `crs.v`, failing with [`CRSError::KeyLength`] unless
+    /// each consists of exactly two elements.
+    fn try_from(crs: &CRS<E>) -> Result<Self, CRSError> {
+        match (crs.u.as_slice(), crs.v.as_slice()) {
+            ([u1, u2], [v1, v2]) => Ok(Self {
+                u1: *u1,
+                u2: *u2,
+                v1: *v1,
+                v2: *v2,
+            }),
+            _ => Err(CRSError::KeyLength),
+        }
+    }
+}
+
+/// Describes why a [`CRS`] is not a valid commitment key, see [`CRS::validate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CRSError {
+    /// `u` or `v` does not consist of exactly two elements.
+    KeyLength,
+    /// A generator or an element of the commitment keys is the identity, so commitments would
+    /// not be binding or not be hiding in either mode.
+    Degenerate,
+    /// A point is not in the prime-order subgroup.
+    NotInSubgroup,
+    /// `gt_gen` is not the pairing `e(g1_gen, g2_gen)` of the other generators.
+    GeneratorMismatch,
+    /// The bytes passed to [`CRS::from_bytes`] are not the compressed serialization of a CRS.
+    Malformed,
 }
 
+impl fmt::Display for CRSError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KeyLength => write!(f, "commitment keys must consist of two elements"),
+            Self::Degenerate => write!(f, "CRS contains an identity element"),
+            Self::NotInSubgroup => {
+                write!(f, "CRS contains a point outside the prime-order subgroup")
+            }
+            Self::GeneratorMismatch => write!(f, "gt_gen is not e(g1_gen, g2_gen)"),
+            Self::Malformed => write!(f, "bytes are not a serialized CRS"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CRSError {}
+
 impl<E: Pairing> CRS<E> {
+    /// Deterministically generates a CRS from a 32-byte seed, using it to seed a
+    /// [`ChaCha20Rng`](rand_chacha::ChaCha20Rng).
+    ///
+    /// The same seed always yields the same CRS, which is useful for reproducible test vectors.
+    /// A CRS generated this way is only as trustworthy as the secrecy of its seed.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self::generate_crs(&mut ChaCha20Rng::from_seed(seed))
+    }
+
+    /// Transparently derives a CRS from `domain` by hashing to the groups with the digest `D`, so
+    /// that no party knows a discrete logarithm between any of its points.
+    ///
+    /// Every point of `u`, `v` and the generators is derived independently by try-and-increment,
+    /// so the CRS has neither an [`ExtractionTrapdoor`] nor a [`SimulationTrapdoor`]. Commitments
+    /// to group elements are perfectly hiding, as `u_2` (resp. `v_2`) is independent of `u_1`
+    /// (resp. `v_1`). Unlike for [`generate_hiding`](CRS::generate_hiding), `u_2 + ι_1(P_1)` is
+    /// independent of `u_1` as well, so commitments to scalars are perfectly binding and only
+    /// computationally hiding under SXDH. The CRS is therefore marked as
+    /// [transparent](CRSType::Transparent), and cannot be used as a [`HidingCRS`].
+    ///
+    /// The same domain always yields the same CRS; use a distinct domain per application.
+    pub fn from_hash<D: Digest>(domain: &[u8]) -> Self {
+        let g1 = |label: &[u8]| hash_to_group::<D, E::G1Affine>(domain, label);
+        let g2 = |label: &[u8]| hash_to_group::<D, E::G2Affine>(domain, label);
+        let (p1, p2) = (g1(b"P1"), g2(b"P2"));
+
+        let crs = CRS::<E> {
+            u: vec![Com1::<E>(p1, g1(b"Q1")), Com1::<E>(g1(b"U1"), g1(b"V1"))],
+            v: vec![Com2::<E>(p2, g2(b"Q2")), Com2::<E>(g2(b"U2"), g2(b"V2"))],
+            g1_gen: p1,
+            g2_gen: p2,
+            gt_gen: E::pairing(p1, p2),
+            crs_type: CRSType::Transparent,
+        };
+        debug_assert_eq!(crs.validate(), Ok(()));
+        crs
+    }
+
+    /// The compressed [`CanonicalSerialize`] encoding of the CRS: `u_1, u_2, v_1, v_2`, the
+    /// generators `g1_gen, g2_gen, gt_gen` and a final byte for [`crs_type`](CRS::crs_type).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.compressed_size());
+        self.serialize_compressed(&mut bytes)
+            .expect("serializing into a Vec cannot fail");
+        bytes
+    }
+
+    /// Parses a CRS in the encoding of [`to_bytes`](CRS::to_bytes), e.g. as published by an
+    /// external setup ceremony, and [validates](CRS::validate) it.
+    ///
+    /// Returns [`CRSError::Malformed`] if the bytes cannot be parsed (including trailing bytes),
+    /// and the validation error otherwise.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CRSError> {
+        let crs = Self::from_bytes_unchecked(bytes)?;
+        crs.validate()?;
+        Ok(crs)
+    }
+
+    /// Like [`from_bytes`](CRS::from_bytes), but neither checks that the points are in the
+    /// prime-order subgroup nor [validates](CRS::validate) the CRS.
+    ///
+    /// Only use this for bytes from a trusted source, or validate the CRS before use.
+    pub fn from_bytes_unchecked(mut bytes: &[u8]) -> Result<Self, CRSError> {
+        let crs =
+            Self::deserialize_fields_unchecked(&mut bytes).map_err(|_| CRSError::Malformed)?;
+        if !bytes.is_empty() {
+            return Err(CRSError::Malformed);
+        }
+        Ok(crs)
+    }
+
+    /// A 32-byte digest of [`to_bytes`](CRS::to_bytes) under the hash function `D`, identifying
+    /// the CRS, e.g. to tell which CRS of a rotation a proof was generated under.
+    pub fn fingerprint<D: Digest<OutputSize = U32>>(&self) -> [u8; 32] {
+        let mut hasher = D::new();
+        hasher.update(b"groth-sahai/crs/fingerprint/v1");
+        absorb(&mut hasher, self);
+        hasher.finalize().into()
+    }
+
+    // Reads the fields one by one rather than with the derived `CanonicalDeserialize`, so that
+    // the length prefixes of `u` and `v` are checked before allocating for them.
+    fn deserialize_fields_unchecked(reader: &mut &[u8]) -> Result<Self, SerializationError> {
+        fn pair<C: CanonicalDeserialize>(reader: &mut &[u8]) -> Result<Vec<C>, SerializationError> {
+            if u64::deserialize_compressed(&mut *reader)? != 2 {
+                return Err(SerializationError::InvalidData);
+            }
+            (0..2)
+                .map(|_| C::deserialize_compressed_unchecked(&mut *reader))
+                .collect()
+        }
+
+        Ok(CRS::<E> {
+            u: pair(reader)?,
+            v: pair(reader)?,
+            g1_gen: E::G1Affine::deserialize_compressed_unchecked(&mut *reader)?,
+            g2_gen: E::G2Affine::deserialize_compressed_unchecked(&mut *reader)?,
+            gt_gen: PairingOutput::<E>::deserialize_compressed_unchecked(&mut *reader)?,
+            crs_type: CRSType::deserialize_compressed_unchecked(&mut *reader)?,
+        })
+    }
+
+    /// The first commitment key `u_1 = (P_1, a_1 P_1)` for [`B1`](crate::data_structures::B1).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `u` is malformed, see [`validate`](CRS::validate).
+    #[inline]
+    pub fn u1(&self) -> &Com1<E> {
+        &self.u[0]
+    }
+
+    /// The second commitment key `u_2` for [`B1`](crate::data_structures::B1), which determines
+    /// whether the CRS is binding or hiding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `u` is malformed, see [`validate`](CRS::validate).
+    #[inline]
+    pub fn u2(&self) -> &Com1<E> {
+        &self.u[1]
+    }
+
+    /// The first commitment key `v_1 = (P_2, a_2 P_2)` for [`B2`](crate::data_structures::B2).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` is malformed, see [`validate`](CRS::validate).
+    #[inline]
+    pub fn v1(&self) -> &Com2<E> {
+        &self.v[0]
+    }
+
+    /// The second commitment key `v_2` for [`B2`](crate::data_structures::B2), which determines
+    /// whether the CRS is binding or hiding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` is malformed, see [`validate`](CRS::validate).
+    #[inline]
+    pub fn v2(&self) -> &Com2<E> {
+        &self.v[1]
+    }
+
+    /// The commitment keys as named elements, see [`SXDHKeys`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `u` or `v` is malformed, see [`validate`](CRS::validate). Use
+    /// [`SXDHKeys::try_from`] to handle that case.
+    pub fn keys(&self) -> SXDHKeys<E> {
+        SXDHKeys::try_from(self).expect("commitment keys must consist of two elements")
+    }
+
+    /// Replaces the commitment keys `u` and `v` by the named elements of `keys`.
+    pub fn set_keys(&mut self, keys: SXDHKeys<E>) {
+        self.u = keys.u().to_vec();
+        self.v = keys.v().to_vec();
+    }
+
+    /// Checks that the CRS is a well-formed SXDH commitment key, without panicking on malformed input.
+    ///
+    /// This checks that `u` and `v` have two elements each, that all points are in the prime-order
+    /// subgroup, that each coordinate of `u_1`, `v_1` and the generators is non-identity, that
+    /// neither `u_2`, `v_2` nor the keys `u_2 + ι_1(g1)`, `v_2 + ι_2(g2)` used for committing to
+    /// scalars are the identity, and that `gt_gen = e(g1_gen, g2_gen)`.
+    ///
+    /// Whether `u_2`, `v_2` lie in the span of `u_1`, `v_1` as required by [`crs_type`](CRS::crs_type)
+    /// cannot be checked publicly, as the two modes are indistinguishable under SXDH. Use
+    /// [`ExtractionTrapdoor::is_trapdoor_of`] or [`SimulationTrapdoor::is_trapdoor_of`] for that.
+    pub fn validate(&self) -> Result<(), CRSError> {
+        let (u1, u2, v1, v2) = match (self.u.as_slice(), self.v.as_slice()) {
+            ([u1, u2], [v1, v2]) => (u1, u2, v1, v2),
+            _ => return Err(CRSError::KeyLength),
+        };
+
+        let g1s = [u1.0, u1.1, u2.0, u2.1, self.g1_gen];
+        let g2s = [v1.0, v1.1, v2.0, v2.1, self.g2_gen];
+        if g1s.iter().any(|p| p.check().is_err()) || g2s.iter().any(|p| p.check().is_err()) {
+            return Err(CRSError::NotInSubgroup);
+        }
+
+        let degenerate = [u1.0, u1.1, self.g1_gen].iter().any(|p| p.is_zero())
+            || [v1.0, v1.1, self.g2_gen].iter().any(|p| p.is_zero())
+            || u2.is_zero()
+            || v2.is_zero()
+            || (*u2 + Com1::<E>::linear_map(&self.g1_gen)).is_zero()
+            || (*v2 + Com2::<E>::linear_map(&self.g2_gen)).is_zero();
+        if degenerate {
+            return Err(CRSError::Degenerate);
+        }
+
+        if self.gt_gen != E::pairing(self.g1_gen, self.g2_gen) {
+            return Err(CRSError::GeneratorMismatch);
+        }
+        Ok(())
+    }
+
     // Returns intermediate "second" values that are used to construct un-blinded (i.e. binding) committment keys
     #[inline(always)]
     #[allow(unused_variables)]
@@ -61,7 +623,6 @@ impl<E: Pairing> CRS<E> {
 
     // Returns intermediate "second" values that are used to construct blinded (i.e. hiding) committment keys
     #[inline(always)]
-    #[allow(dead_code)]
     fn prepare_simulated_hinding_key(
         g1_gen: E::G1,
         g2_gen: E::G2,
@@ -77,8 +638,116 @@ impl<E: Pairing> CRS<E> {
     }
 }
 
-impl<E: Pairing> AbstractCrs<E> for CRS<E> {
-    fn generate_crs<R>(rng: &mut R) -> CRS<E>
+// Hashes `domain` and `label` to a non-identity point of the prime-order subgroup by
+// try-and-increment: candidate x-coordinates are hashed until one lies on the curve.
+fn hash_to_group<D: Digest, G: AffineRepr>(domain: &[u8], label: &[u8]) -> G {
+    let len = G::generator().compressed_size();
+    let mut counter = 0u64;
+    loop {
+        let mut bytes = Vec::with_capacity(len);
+        let mut block = 0u64;
+        while bytes.len() < len {
+            let mut hasher = D::new();
+            absorb(&mut hasher, &b"groth-sahai/crs/from-hash/v1".as_slice());
+            absorb(&mut hasher, &domain);
+            absorb(&mut hasher, &label);
+            absorb(&mut hasher, &counter);
+            absorb(&mut hasher, &block);
+            bytes.extend_from_slice(&hasher.finalize());
+            block += 1;
+        }
+        bytes.truncate(len);
+
+        if let Some(p) = G::from_random_bytes(&bytes).map(|p| p.clear_cofactor()) {
+            if !p.is_zero() {
+                return p;
+            }
+        }
+        counter += 1;
+    }
+}
+
+/// The extraction trapdoor of a [binding](CRSType::Binding) [`CRS`], i.e. the discrete logarithms
+/// `a_1, a_2` such that `u_1 = (P_1, a_1 P_1)` and `v_1 = (P_2, a_2 P_2)`.
+///
+/// This must be kept secret (and is deliberately not serializable along with the CRS): whoever
+/// holds it can open every commitment to a group element.
+#[derive(Clone)]
+pub struct ExtractionTrapdoor<E: Pairing> {
+    pub(crate) a1: E::ScalarField,
+    pub(crate) a2: E::ScalarField,
+}
+
+/// The simulation trapdoor of a [hiding](CRSType::Hiding) [`CRS`], i.e. the scalars `t_1, t_2`
+/// such that `u_2 = t_1 u_1 - ι_1(P_1)` and `v_2 = t_2 v_1 - ι_2(P_2)`.
+///
+/// This must be kept secret (and is deliberately not serializable along with the CRS): whoever
+/// holds it can open commitments under the CRS to other values than they were made to, and
+/// thereby [simulate](crate::prover::simulate_ppe) zero-knowledge proofs without a witness.
+#[derive(Clone)]
+pub struct SimulationTrapdoor<E: Pairing> {
+    pub(crate) t1: E::ScalarField,
+    pub(crate) t2: E::ScalarField,
+}
+
+impl<E: Pairing> ExtractionTrapdoor<E> {
+    /// Checks whether this is the trapdoor of the binding CRS `crs`, i.e. whether `u_2` and
+    /// `v_2` lie in the span of `u_1` and `v_1`.
+    pub fn is_trapdoor_of(&self, crs: &CRS<E>) -> bool {
+        fn in_span<G: AffineRepr>(p: &G, q: &G, a: G::ScalarField) -> bool {
+            p.mul(a) == q.into_group()
+        }
+        crs.crs_type == CRSType::Binding
+            && crs.u.len() == 2
+            && crs.v.len() == 2
+            && crs.u.iter().all(|u| in_span(&u.0, &u.1, self.a1))
+            && crs.v.iter().all(|v| in_span(&v.0, &v.1, self.a2))
+    }
+}
+
+impl<E: Pairing> SimulationTrapdoor<E> {
+    /// Checks whether this is the trapdoor of the hiding CRS `crs`.
+    pub fn is_trapdoor_of(&self, crs: &CRS<E>) -> bool {
+        crs.crs_type == CRSType::Hiding
+            && crs.u.len() == 2
+            && crs.v.len() == 2
+            && *crs.u2() == crs.u1().scalar_mul(&self.t1) - Com1::<E>::linear_map(&crs.g1_gen)
+            && *crs.v2() == crs.v1().scalar_mul(&self.t2) - Com2::<E>::linear_map(&crs.g2_gen)
+    }
+}
+
+impl<E: Pairing> CRS<E> {
+    /// Generates a perfectly binding CRS, under which proofs are perfectly sound, along with its
+    /// trapdoor for extracting committed group elements.
+    pub fn generate_binding<R>(rng: &mut R) -> (BindingCRS<E>, ExtractionTrapdoor<E>)
+    where
+        R: Rng,
+    {
+        let (crs, ext_td, _) = Self::generate_with_type(rng, CRSType::Binding);
+        (TypedCRS::new(crs).unwrap(), ext_td)
+    }
+
+    /// Generates a perfectly hiding CRS, under which proofs are perfectly witness-indistinguishable.
+    pub fn generate_hiding<R>(rng: &mut R) -> HidingCRS<E>
+    where
+        R: Rng,
+    {
+        Self::generate_hiding_with_trapdoor(rng).0
+    }
+
+    /// Generates a perfectly hiding CRS along with its [`SimulationTrapdoor`].
+    pub fn generate_hiding_with_trapdoor<R>(rng: &mut R) -> (HidingCRS<E>, SimulationTrapdoor<E>)
+    where
+        R: Rng,
+    {
+        let (crs, _, sim_td) = Self::generate_with_type(rng, CRSType::Hiding);
+        (TypedCRS::new(crs).unwrap(), sim_td)
+    }
+
+    fn generate_with_type<R>(
+        rng: &mut R,
+        crs_type: CRSType,
+    ) -> (Self, ExtractionTrapdoor<E>, SimulationTrapdoor<E>)
     where
         R: Rng,
     {
@@ -98,7 +767,11 @@ impl<E: Pairing> AbstractCrs<E> for CRS<E> {
         let u1 = p1.mul(t1);
         let u2 = p2.mul(t2);
 
-        let (v1, v2) = Self::prepare_real_binding_key(p1, p2, q1, t1, q2, t2);
+        let (v1, v2) = match crs_type {
+            CRSType::Binding => Self::prepare_real_binding_key(p1, p2, q1, t1, q2, t2),
+            CRSType::Hiding => Self::prepare_simulated_hinding_key(p1, p2, q1, t1, q2, t2),
+            CRSType::Transparent => unreachable!("transparent CRSs are derived by CRS::from_hash"),
+        };
 
         // B1 commitment key for G1 and Fr
         let u11 = Com1::<E>(p1.into_affine(), q1.into_affine());
@@ -108,12 +781,251 @@ impl<E: Pairing> AbstractCrs<E> for CRS<E> {
         let u21 = Com2::<E>(p2.into_affine(), q2.into_affine());
         let u22 = Com2::<E>(u2.into_affine(), v2.into_affine());
 
-        CRS::<E> {
+        let crs = CRS::<E> {
             u: vec![u11, u12],
             v: vec![u21, u22],
             g1_gen: p1.into_affine(),
             g2_gen: p2.into_affine(),
             gt_gen: E::pairing(p1.into_affine(), p2.into_affine()),
+            crs_type,
+        };
+
+        debug_assert_eq!(crs.validate(), Ok(()));
+        (
+            crs,
+            ExtractionTrapdoor { a1, a2 },
+            SimulationTrapdoor { t1, t2 },
+        )
+    }
+}
+
+impl<E: Pairing> CommitmentKey<E> for CRS<E> {
+    type C1 = Com1<E>;
+    type C2 = Com2<E>;
+    const DIM: usize = 2;
+
+    fn u(&self) -> &[Com1<E>] {
+        &self.u
+    }
+
+    fn v(&self) -> &[Com2<E>] {
+        &self.v
+    }
+
+    fn linear_map_G1(&self, x: &E::G1Affine) -> Com1<E> {
+        Com1::<E>::linear_map(x)
+    }
+
+    fn linear_map_G2(&self, y: &E::G2Affine) -> Com2<E> {
+        Com2::<E>::linear_map(y)
+    }
+
+    fn scalar_linear_map_B1(&self, x: &E::ScalarField) -> Com1<E> {
+        Com1::<E>::scalar_linear_map(x, self)
+    }
+
+    fn scalar_linear_map_B2(&self, y: &E::ScalarField) -> Com2<E> {
+        Com2::<E>::scalar_linear_map(y, self)
+    }
+}
+
+impl<E: Pairing> AbstractCrs<E> for CRS<E> {
+    /// Generates a [binding](CRSType::Binding) CRS, see [`CRS::generate_binding`].
+    fn generate_crs<R>(rng: &mut R) -> CRS<E>
+    where
+        R: Rng,
+    {
+        Self::generate_binding(rng).0.into_inner()
+    }
+}
+
+/// Proves that a [`CRS`] output by [`CRS::contribute`] re-randomizes the previous one, see
+/// [`verify_contribution`].
+///
+/// A contribution scales the trapdoors `a_1, t_1` of `u` by secret scalars `α_1, β_1` and those of
+/// `v` by `α_2, β_2`. The proof publishes these scalars "in the other group", so that the
+/// relation between the old and the new keys can be checked with pairings.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ContributionProof<E: Pairing> {
+    /// `(α_1 P_2, β_1 P_2, α_1 β_1 P_2)` for the re-randomization of `u`.
+    pub u_shift: [E::G2Affine; 3],
+    /// `(α_2 P_1, β_2 P_1, α_2 β_2 P_1)` for the re-randomization of `v`.
+    pub v_shift: [E::G1Affine; 3],
+}
+
+// Maps u_1 = (P, Q) to (P, αQ) and u_2 = (R, S) to (βR, αβ(S + δ) - δ), where δ is the identity
+// for a binding and the generator for a hiding key. This keeps the form of the key, replacing
+// the trapdoors (a, t) by (αa, βt).
+fn rerandomize_key<G: AffineRepr>(
+    (p, q): (G, G),
+    (r, s): (G, G),
+    delta: G,
+    alpha: G::ScalarField,
+    beta: G::ScalarField,
+) -> ((G, G), (G, G)) {
+    let s = (s + delta) * (alpha * beta) - delta;
+    let r = r * beta;
+    let q = q * alpha;
+    ((p, q.into_affine()), (r.into_affine(), s.into_affine()))
+}
+
+// Checks that e(a, b) = e(c, d)
+fn same_pairing<E: Pairing>(
+    a: E::G1Affine,
+    b: E::G2Affine,
+    c: E::G1Affine,
+    d: E::G2Affine,
+) -> bool {
+    E::multi_pairing([a.into_group(), -c.into_group()], [b, d]).is_zero()
+}
+
+impl<E: Pairing> CRS<E> {
+    /// Re-randomizes `prev` as one participant of a multi-party CRS ceremony, returning the new
+    /// CRS and a proof that it was derived from `prev`.
+    ///
+    /// Participants contribute in sequence, each passing the output of the previous one, and
+    /// everybody checks every step with [`verify_contribution`]. The trapdoor of the final CRS is
+    /// the product of the secret scalars of all participants, so it is unknown as long as at
+    /// least one of them sampled its scalars honestly and discarded them. The generators and the
+    /// [`CRSType`] of `prev` are kept.
+    pub fn contribute<R>(prev: &CRS<E>, rng: &mut R) -> (Self, ContributionProof<E>)
+    where
+        R: Rng,
+    {
+        let alpha1 = E::ScalarField::rand(rng);
+        let beta1 = E::ScalarField::rand(rng);
+        let alpha2 = E::ScalarField::rand(rng);
+        let beta2 = E::ScalarField::rand(rng);
+
+        let (delta1, delta2) = match prev.crs_type {
+            CRSType::Binding => (E::G1Affine::zero(), E::G2Affine::zero()),
+            CRSType::Hiding | CRSType::Transparent => (prev.g1_gen, prev.g2_gen),
+        };
+        let (u1, u2) = rerandomize_key(
+            (prev.u1().0, prev.u1().1),
+            (prev.u2().0, prev.u2().1),
+            delta1,
+            alpha1,
+            beta1,
+        );
+        let (v1, v2) = rerandomize_key(
+            (prev.v1().0, prev.v1().1),
+            (prev.v2().0, prev.v2().1),
+            delta2,
+            alpha2,
+            beta2,
+        );
+
+        let crs = CRS::<E> {
+            u: vec![Com1::<E>(u1.0, u1.1), Com1::<E>(u2.0, u2.1)],
+            v: vec![Com2::<E>(v1.0, v1.1), Com2::<E>(v2.0, v2.1)],
+            ..prev.clone()
+        };
+        let u_shift = [
+            prev.g2_gen * alpha1,
+            prev.g2_gen * beta1,
+            prev.g2_gen * (alpha1 * beta1),
+        ]
+        .map(|p| p.into_affine());
+        let v_shift = [
+            prev.g1_gen * alpha2,
+            prev.g1_gen * beta2,
+            prev.g1_gen * (alpha2 * beta2),
+        ]
+        .map(|p| p.into_affine());
+        (crs, ContributionProof { u_shift, v_shift })
+    }
+}
+
+/// Verifies that `next` was derived from `prev` by [`CRS::contribute`], as attested by `proof`.
+///
+/// Besides checking `proof`, this checks that `next` is [valid](CRS::validate) and shares the
+/// generators, the [`CRSType`] and the first coordinates of `u_1`, `v_1` with `prev`. It does not
+/// check `prev` itself, which is the output of the previous step (or the initial CRS) of the
+/// ceremony and must be verified on its own.
+pub fn verify_contribution<E: Pairing>(
+    prev: &CRS<E>,
+    next: &CRS<E>,
+    proof: &ContributionProof<E>,
+) -> bool {
+    if prev.validate().is_err() || next.validate().is_err() {
+        return false;
+    }
+    if next.g1_gen != prev.g1_gen
+        || next.g2_gen != prev.g2_gen
+        || next.crs_type != prev.crs_type
+        || next.u1().0 != prev.u1().0
+        || next.v1().0 != prev.v1().0
+    {
+        return false;
+    }
+    if proof.u_shift.iter().any(|p| p.is_zero()) || proof.v_shift.iter().any(|p| p.is_zero()) {
+        return false;
+    }
+
+    let (g1, g2) = (prev.g1_gen, prev.g2_gen);
+    let (delta1, delta2) = match prev.crs_type {
+        CRSType::Binding => (E::G1::zero(), E::G2::zero()),
+        CRSType::Hiding | CRSType::Transparent => (g1.into_group(), g2.into_group()),
+    };
+    let [a1, b1, ab1] = proof.u_shift;
+    let [a2, b2, ab2] = proof.v_shift;
+    let (prev_u2, next_u2) = (
+        (delta1 + prev.u2().1).into_affine(),
+        (delta1 + next.u2().1).into_affine(),
+    );
+    let (prev_v2, next_v2) = (
+        (delta2 + prev.v2().1).into_affine(),
+        (delta2 + next.v2().1).into_affine(),
+    );
+
+    // u_1 and u_2 are scaled by α_1 and β_1, and the third element of the shift is α_1 β_1 P_2
+    same_pairing::<E>(next.u1().1, g2, prev.u1().1, a1)
+        && same_pairing::<E>(next.u2().0, g2, prev.u2().0, b1)
+        && same_pairing::<E>(next.u1().1, b1, prev.u1().1, ab1)
+        && same_pairing::<E>(next_u2, g2, prev_u2, ab1)
+        // Likewise for v_1 and v_2 with α_2 and β_2
+        && same_pairing::<E>(g1, next.v1().1, a2, prev.v1().1)
+        && same_pairing::<E>(g1, next.v2().0, b2, prev.v2().0)
+        && same_pairing::<E>(b2, next.v1().1, ab2, prev.v1().1)
+        && same_pairing::<E>(g1, next_v2, ab2, prev_v2)
+}
+
+/// Contains fixed-base multiplication tables for the commitment keys of a [`CRS`].
+///
+/// Every commitment multiplies the same four group elements of `u` (resp. `v`) by fresh
+/// randomness, so precomputing windowed tables of their multiples trades memory for faster
+/// commitments. Each table holds roughly `(255 / w) * 2^w` affine points, where the window size
+/// `w` grows logarithmically with the expected number of scalars per batch (`w = 3` below 32
+/// scalars). Building the tables costs about as much as committing to a batch of that size, so
+/// they only pay off when the same CRS is used for many commitments.
+pub struct CRSPrecomp<E: Pairing> {
+    pub crs: CRS<E>,
+    pub(crate) u_tables: [BatchMulPreprocessing<E::G1>; 4],
+    pub(crate) v_tables: [BatchMulPreprocessing<E::G2>; 4],
+}
+
+impl<E: Pairing> CRSPrecomp<E> {
+    /// Precomputes the fixed-base tables for `crs`, with window sizes tuned for batches of
+    /// `num_scalars` commitments.
+    pub fn new(crs: &CRS<E>, num_scalars: usize) -> Self {
+        let u_table = |p: E::G1Affine| BatchMulPreprocessing::new(p.into_group(), num_scalars);
+        let v_table = |p: E::G2Affine| BatchMulPreprocessing::new(p.into_group(), num_scalars);
+
+        Self {
+            crs: crs.clone(),
+            u_tables: [
+                u_table(crs.u[0].0),
+                u_table(crs.u[0].1),
+                u_table(crs.u[1].0),
+                u_table(crs.u[1].1),
+            ],
+            v_tables: [
+                v_table(crs.v[0].0),
+                v_table(crs.v[0].1),
+                v_table(crs.v[1].0),
+                v_table(crs.v[1].1),
+            ],
         }
     }
 }
@@ -126,6 +1038,10 @@ mod tests {
     use ark_std::test_rng;
 
     use super::*;
+    use crate::prover::{
+        commit_G1_with_randomness, commit_G2_with_randomness, commit_scalar_to_B1_with_randomness,
+        commit_scalar_to_B2_with_randomness, extract_G1, extract_G2,
+    };
 
     type G1Projective = <F as Pairing>::G1;
     type G1Affine = <F as Pairing>::G1Affine;
@@ -151,6 +1067,7 @@ mod tests {
     #[allow(non_snake_case)]
     #[test]
     fn test_valid_binding_CRS() {
+        #[cfg(feature = "std")]
         std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
         let mut rng = test_rng();
         let mut rng2 = test_rng();
@@ -205,4 +1122,466 @@ mod tests {
         assert_eq!(crs.g2_gen, crs_deserialized.g2_gen);
         assert_eq!(crs.gt_gen, crs_deserialized.gt_gen);
     }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_fingerprint() {
+        use sha2::Sha256;
+
+        let crs = CRS::<F>::from_seed([1u8; 32]);
+        let same = CRS::<F>::from_bytes(&crs.to_bytes()).unwrap();
+        let other = CRS::<F>::from_seed([2u8; 32]);
+        assert_eq!(crs.fingerprint::<Sha256>(), same.fingerprint::<Sha256>());
+        assert_ne!(crs.fingerprint::<Sha256>(), other.fingerprint::<Sha256>());
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_from_bytes() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let bytes = crs.to_bytes();
+
+        let parsed = CRS::<F>::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.to_bytes(), bytes);
+        assert_eq!(parsed.u, crs.u);
+        assert_eq!(parsed.v, crs.v);
+        assert_eq!(parsed.crs_type, crs.crs_type);
+        assert_eq!(
+            CRS::<F>::from_bytes_unchecked(&bytes).unwrap().to_bytes(),
+            bytes
+        );
+
+        // Parse errors: truncated, trailing and garbage bytes
+        assert_eq!(
+            CRS::<F>::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            CRSError::Malformed
+        );
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            CRS::<F>::from_bytes_unchecked(&trailing).unwrap_err(),
+            CRSError::Malformed
+        );
+        assert_eq!(
+            CRS::<F>::from_bytes(&[0xffu8; 8]).unwrap_err(),
+            CRSError::Malformed
+        );
+
+        // A well-formed encoding of an invalid CRS only fails validation
+        let mut bad = crs.clone();
+        bad.gt_gen = bad.gt_gen + bad.gt_gen;
+        let bad_bytes = bad.to_bytes();
+        assert_eq!(
+            CRS::<F>::from_bytes(&bad_bytes).unwrap_err(),
+            CRSError::GeneratorMismatch
+        );
+        assert!(CRS::<F>::from_bytes_unchecked(&bad_bytes).is_ok());
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_binding_and_hiding_CRS() {
+        #[cfg(feature = "std")]
+        std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
+        let mut rng = test_rng();
+        let mut rng2 = test_rng();
+
+        let (binding, _) = CRS::<F>::generate_binding(&mut rng);
+        let hiding = CRS::<F>::generate_hiding(&mut rng2);
+        assert_eq!(binding.crs_type, CRSType::Binding);
+        assert_eq!(hiding.crs_type, CRSType::Hiding);
+        assert_eq!(
+            CRS::<F>::generate_crs(&mut test_rng()).crs_type,
+            CRSType::Binding
+        );
+
+        // From the same randomness, the keys only differ by the generators in v_1 and v_2
+        assert_eq!(binding.u[0], hiding.u[0]);
+        assert_eq!(binding.v[0], hiding.v[0]);
+        assert_eq!(binding.u[1].0, hiding.u[1].0);
+        assert_eq!(
+            binding.u[1].1.into_group() - hiding.u[1].1.into_group(),
+            binding.g1_gen.into_group()
+        );
+        assert_eq!(
+            binding.v[1].1.into_group() - hiding.v[1].1.into_group(),
+            binding.g2_gen.into_group()
+        );
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_hiding_CRS_simulation_trapdoor() {
+        let mut rng = test_rng();
+        let (hiding, td) = CRS::<F>::generate_hiding_with_trapdoor(&mut rng);
+        assert_eq!(hiding.crs_type, CRSType::Hiding);
+        assert!(td.is_trapdoor_of(&hiding));
+
+        // The trapdoor does not match a binding key generated from the same randomness
+        let (binding, _, binding_td) =
+            CRS::<F>::generate_with_type(&mut test_rng(), CRSType::Binding);
+        assert!(!binding_td.is_trapdoor_of(&binding));
+        assert!(!td.is_trapdoor_of(&binding));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_binding_CRS_extracts() {
+        let mut rng = test_rng();
+        let (binding, td) = CRS::<F>::generate_binding(&mut rng);
+        assert_eq!(binding.u[0].1, binding.g1_gen.mul(td.a1).into_affine());
+        assert_eq!(binding.v[0].1, binding.g2_gen.mul(td.a2).into_affine());
+
+        // Only a binding key lets the trapdoor recover the committed element
+        let (hiding, hiding_td, _) = CRS::<F>::generate_with_type(&mut rng, CRSType::Hiding);
+        let xvar = binding.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let yvar = binding.g2_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let (r1, r2) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        assert_eq!(
+            extract_G1(&commit_G1_with_randomness(&xvar, &binding, r1, r2), &td),
+            xvar
+        );
+        assert_eq!(
+            extract_G2(&commit_G2_with_randomness(&yvar, &binding, r1, r2), &td),
+            yvar
+        );
+        assert_ne!(
+            extract_G1(
+                &commit_G1_with_randomness(&xvar, &hiding, r1, r2),
+                &hiding_td
+            ),
+            xvar
+        );
+        assert_ne!(
+            extract_G2(
+                &commit_G2_with_randomness(&yvar, &hiding, r1, r2),
+                &hiding_td
+            ),
+            yvar
+        );
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_type_serde() {
+        let mut rng = test_rng();
+        let hiding = CRS::<F>::generate_hiding(&mut rng);
+
+        let mut bytes = Vec::new();
+        hiding.serialize_compressed(&mut bytes).unwrap();
+        let deserialized = CRS::<F>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(deserialized.crs_type, CRSType::Hiding);
+
+        // Unknown CRS types are rejected
+        *bytes.last_mut().unwrap() = 3;
+        assert!(CRS::<F>::deserialize_compressed(&bytes[..]).is_err());
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_named_keys() {
+        let mut rng = test_rng();
+        let mut crs = CRS::<F>::generate_crs(&mut rng);
+
+        // The named and list forms agree
+        let keys = crs.keys();
+        assert_eq!(keys.u1, crs.u[0]);
+        assert_eq!(keys.u2, crs.u[1]);
+        assert_eq!(keys.v1, crs.v[0]);
+        assert_eq!(keys.v2, crs.v[1]);
+        assert_eq!(keys.u(), [*crs.u1(), *crs.u2()]);
+        assert_eq!(keys.v(), [*crs.v1(), *crs.v2()]);
+        assert_eq!(SXDHKeys::try_from(&crs), Ok(keys));
+
+        // Converting back round-trips, and replaces the keys of another CRS
+        let bytes = crs.to_bytes();
+        crs.set_keys(keys);
+        assert_eq!(crs.to_bytes(), bytes);
+        let mut other = CRS::<F>::generate_crs(&mut rng);
+        other.set_keys(keys);
+        assert_eq!(other.keys(), keys);
+        assert_eq!(other.u, crs.u);
+        assert_eq!(other.v, crs.v);
+
+        // Malformed keys cannot be named
+        crs.u.push(keys.u1);
+        assert_eq!(SXDHKeys::try_from(&crs), Err(CRSError::KeyLength));
+        crs.u.truncate(1);
+        assert_eq!(SXDHKeys::try_from(&crs), Err(CRSError::KeyLength));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_typed_CRS() {
+        let mut rng = test_rng();
+        let (binding, ext_td) = CRS::<F>::generate_binding(&mut rng);
+        let (hiding, sim_td) = CRS::<F>::generate_hiding_with_trapdoor(&mut rng);
+        assert_eq!(binding.crs_type, Binding::TYPE);
+        assert_eq!(hiding.crs_type, Hiding::TYPE);
+        assert!(ext_td.is_trapdoor_of(&binding));
+        assert!(sim_td.is_trapdoor_of(&hiding));
+
+        // Only a CRS marked with the matching type can be wrapped
+        let untyped = CRS::<F>::deserialize_compressed(&hiding.to_bytes()[..]).unwrap();
+        assert!(BindingCRS::new(untyped.clone()).is_none());
+        let typed = HidingCRS::new(untyped).unwrap();
+        assert_eq!(typed.to_bytes(), hiding.to_bytes());
+        assert_eq!(format!("{:?}", typed), format!("{:?}", *hiding));
+        assert!(HidingCRS::new(binding.clone().into()).is_none());
+        assert_eq!(binding.clone().into_inner().to_bytes(), binding.to_bytes());
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_debug() {
+        let crs = CRS::<F>::from_seed([1u8; 32]);
+        let dbg = format!("{:?}", crs);
+
+        assert!(dbg.starts_with("CRS { u: [Com1(0x"));
+        assert!(dbg.contains("crs_type: Binding"));
+        assert_eq!(dbg, format!("{:?}", CRS::<F>::from_seed([1u8; 32])));
+        assert_eq!(dbg, format!("{:?}", crs.clone()));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_from_seed() {
+        let crs1 = CRS::<F>::from_seed([7u8; 32]);
+        let crs2 = CRS::<F>::from_seed([7u8; 32]);
+        let crs3 = CRS::<F>::from_seed([8u8; 32]);
+
+        let mut bytes1 = Vec::new();
+        let mut bytes2 = Vec::new();
+        let mut bytes3 = Vec::new();
+        crs1.serialize_compressed(&mut bytes1).unwrap();
+        crs2.serialize_compressed(&mut bytes2).unwrap();
+        crs3.serialize_compressed(&mut bytes3).unwrap();
+
+        // Same seed gives byte-identical CRSs, different seeds do not
+        assert_eq!(bytes1, bytes2);
+        assert_ne!(bytes1, bytes3);
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_serde_commit() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let mut c_bytes = Vec::with_capacity(crs.compressed_size());
+        crs.serialize_compressed(&mut c_bytes).unwrap();
+        assert_eq!(c_bytes.len(), crs.compressed_size());
+        let crs_deserialized = CRS::<F>::deserialize_compressed(&c_bytes[..]).unwrap();
+
+        // Commitments under the deserialized CRS match those under the original
+        let xvar = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let yvar = crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let (r1, r2) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        assert_eq!(
+            commit_G1_with_randomness(&xvar, &crs, r1, r2),
+            commit_G1_with_randomness(&xvar, &crs_deserialized, r1, r2)
+        );
+        assert_eq!(
+            commit_G2_with_randomness(&yvar, &crs, r1, r2),
+            commit_G2_with_randomness(&yvar, &crs_deserialized, r1, r2)
+        );
+        assert_eq!(
+            commit_scalar_to_B1_with_randomness(&r1, &crs, r2),
+            commit_scalar_to_B1_with_randomness(&r1, &crs_deserialized, r2)
+        );
+        assert_eq!(
+            commit_scalar_to_B2_with_randomness(&r1, &crs, r2),
+            commit_scalar_to_B2_with_randomness(&r1, &crs_deserialized, r2)
+        );
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_basis_accessors() {
+        let mut rng = test_rng();
+        for crs in [
+            CRS::<F>::generate_crs(&mut rng),
+            CRS::<F>::generate_hiding(&mut rng).into_inner(),
+        ] {
+            assert_eq!(*crs.u1(), crs.u[0]);
+            assert_eq!(*crs.u2(), crs.u[1]);
+            assert_eq!(*crs.v1(), crs.v[0]);
+            assert_eq!(*crs.v2(), crs.v[1]);
+            assert_eq!(crs.u1().0, crs.g1_gen);
+            assert_eq!(crs.v1().0, crs.g2_gen);
+        }
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_validate() {
+        let mut rng = test_rng();
+        let (binding, ext_td) = CRS::<F>::generate_binding(&mut rng);
+        let (hiding, sim_td) = CRS::<F>::generate_hiding_with_trapdoor(&mut rng);
+        assert_eq!(binding.validate(), Ok(()));
+        assert_eq!(hiding.validate(), Ok(()));
+
+        // The span property is only checkable with the trapdoor
+        assert!(ext_td.is_trapdoor_of(&binding));
+        assert!(!ext_td.is_trapdoor_of(&hiding));
+        let mut relabeled = hiding.clone().into_inner();
+        relabeled.crs_type = CRSType::Binding;
+        assert_eq!(relabeled.validate(), Ok(()));
+        assert!(!ext_td.is_trapdoor_of(&relabeled));
+        assert!(!sim_td.is_trapdoor_of(&relabeled));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_degenerate_CRS_fails_validation() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let mut short = crs.clone();
+        short.u.pop();
+        assert_eq!(short.validate(), Err(CRSError::KeyLength));
+        let mut empty = crs.clone();
+        empty.v.clear();
+        assert_eq!(empty.validate(), Err(CRSError::KeyLength));
+
+        // u_1 = (P, O), i.e. a_1 = 0
+        let mut zero_coord = crs.clone();
+        zero_coord.u[0].1 = G1Affine::zero();
+        assert_eq!(zero_coord.validate(), Err(CRSError::Degenerate));
+        let mut zero_key = crs.clone();
+        zero_key.v[1] = Com2::zero();
+        assert_eq!(zero_key.validate(), Err(CRSError::Degenerate));
+        // u_2 + i_1(g1) = O, so scalar commitments ignore the committed value
+        let mut zero_scalar_key = crs.clone();
+        zero_scalar_key.u[1] = -Com1::<F>::linear_map(&crs.g1_gen);
+        assert_eq!(zero_scalar_key.validate(), Err(CRSError::Degenerate));
+        let mut zero_gen = crs.clone();
+        zero_gen.g2_gen = G2Affine::zero();
+        assert_eq!(zero_gen.validate(), Err(CRSError::Degenerate));
+
+        let mut gt_mismatch = crs.clone();
+        gt_mismatch.gt_gen += crs.gt_gen;
+        assert_eq!(gt_mismatch.validate(), Err(CRSError::GeneratorMismatch));
+
+        // A point on the curve outside of the prime-order subgroup
+        let outside = (1u64..)
+            .filter_map(|x| G1Affine::get_point_from_x_unchecked(x.into(), false))
+            .find(|pt| !pt.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        let mut non_subgroup = crs.clone();
+        non_subgroup.u[1].0 = outside;
+        assert_eq!(non_subgroup.validate(), Err(CRSError::NotInSubgroup));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_contribution_chain() {
+        let mut rng = test_rng();
+        let (binding, ext_td) = CRS::<F>::generate_binding(&mut rng);
+        let (hiding, sim_td) = CRS::<F>::generate_hiding_with_trapdoor(&mut rng);
+
+        for initial in [binding.into_inner(), hiding.into_inner()] {
+            let mut crs = initial.clone();
+            for _ in 0..3 {
+                let (next, proof) = CRS::contribute(&crs, &mut rng);
+                assert!(verify_contribution(&crs, &next, &proof));
+                assert_eq!(next.validate(), Ok(()));
+                assert_eq!(next.crs_type, initial.crs_type);
+                crs = next;
+            }
+
+            // The trapdoor of the initial CRS is useless for the final one
+            assert_ne!(crs.u, initial.u);
+            assert_ne!(crs.v, initial.v);
+            assert!(!ext_td.is_trapdoor_of(&crs));
+            assert!(!sim_td.is_trapdoor_of(&crs));
+
+            // The final CRS still commits and opens correctly
+            let xvar = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+            let (r1, r2) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+            let com = commit_G1_with_randomness(&xvar, &crs, r1, r2);
+            assert_eq!(
+                com,
+                Com1::<F>::linear_map(&xvar) + crs.u1().scalar_mul(&r1) + crs.u2().scalar_mul(&r2)
+            );
+        }
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_malformed_CRS_contribution() {
+        let mut rng = test_rng();
+        let (prev, _) = CRS::<F>::generate_binding(&mut rng);
+        let (next, proof) = CRS::contribute(&prev, &mut rng);
+        assert!(verify_contribution(&prev, &next, &proof));
+
+        // Replacing a key element, e.g. by a freshly generated binding key, is detected
+        let (fresh, _) = CRS::<F>::generate_binding(&mut rng);
+        let mut tampered = next.clone();
+        tampered.u[1] = Com1::<F>(next.u2().0, fresh.u2().1);
+        assert!(!verify_contribution(&prev, &tampered, &proof));
+        let mut tampered = next.clone();
+        tampered.v[0] = Com2::<F>(next.v1().0, fresh.v1().1);
+        assert!(!verify_contribution(&prev, &tampered, &proof));
+
+        // Turning a binding key into a hiding one is detected
+        let mut tampered = next.clone();
+        tampered.u[1] = *next.u2() - Com1::<F>::linear_map(&next.g1_gen);
+        assert!(!verify_contribution(&prev, &tampered, &proof));
+        let mut tampered = next.clone();
+        tampered.crs_type = CRSType::Hiding;
+        assert!(!verify_contribution(&prev, &tampered, &proof));
+
+        // A proof for another contribution, or from another previous CRS, does not verify
+        let (other, other_proof) = CRS::contribute(&prev, &mut rng);
+        assert!(verify_contribution(&prev, &other, &other_proof));
+        assert!(!verify_contribution(&prev, &next, &other_proof));
+        assert!(!verify_contribution(&fresh, &next, &proof));
+
+        // Identity shifts are rejected
+        let mut bad_proof = proof.clone();
+        bad_proof.u_shift[0] = G2Affine::zero();
+        assert!(!verify_contribution(&prev, &next, &bad_proof));
+    }
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn test_CRS_from_hash() {
+        use crate::prover::{open_commit_G1, open_commit_G2, open_commit_scalar_to_B1};
+        use sha2::Sha256;
+
+        let crs = CRS::<F>::from_hash::<Sha256>(b"groth-sahai test");
+        let same = CRS::<F>::from_hash::<Sha256>(b"groth-sahai test");
+        let other = CRS::<F>::from_hash::<Sha256>(b"groth-sahai other test");
+        let serialize = |crs: &CRS<F>| {
+            let mut bytes = Vec::new();
+            crs.serialize_compressed(&mut bytes).unwrap();
+            bytes
+        };
+        assert_eq!(serialize(&crs), serialize(&same));
+        assert_ne!(serialize(&crs), serialize(&other));
+        assert_eq!(crs.validate(), Ok(()));
+        assert_eq!(crs.crs_type, CRSType::Transparent);
+        assert!(HidingCRS::new(crs.clone()).is_none());
+        assert!(BindingCRS::new(crs.clone()).is_none());
+        let parsed = CRS::<F>::from_bytes(&crs.to_bytes()).unwrap();
+        assert_eq!(parsed.crs_type, CRSType::Transparent);
+        // All points are derived independently
+        assert_ne!(crs.u1().0, crs.u1().1);
+        assert_ne!(crs.u2().0, crs.u2().1);
+
+        let mut rng = test_rng();
+        let xvar = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let yvar = crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let scalar = Fr::rand(&mut rng);
+        let (r1, r2) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let xcom = commit_G1_with_randomness(&xvar, &crs, r1, r2);
+        let ycom = commit_G2_with_randomness(&yvar, &crs, r1, r2);
+        let scom = commit_scalar_to_B1_with_randomness(&scalar, &crs, r1);
+        assert!(open_commit_G1(&xcom, &xvar, r1, r2, &crs));
+        assert!(open_commit_G2(&ycom, &yvar, r1, r2, &crs));
+        assert!(open_commit_scalar_to_B1(&scom, &scalar, r1, &crs));
+        assert!(!open_commit_G1(&xcom, &crs.g1_gen, r1, r2, &crs));
+    }
 }
@@ -1,5 +1,17 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[macro_use]
+extern crate alloc;
+
+pub mod aggregate;
+#[cfg(feature = "serde")]
+mod ark_serde;
+#[cfg(any(feature = "bls12_381", feature = "bn254", feature = "bw6_761"))]
+pub mod curves;
 pub mod data_structures;
+pub mod fiat_shamir;
 pub mod generator;
+pub mod prelude;
 pub mod prover;
 pub mod statement;
 pub mod verifier;
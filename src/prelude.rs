@@ -0,0 +1,43 @@
+//! Re-exports the commonly used types, traits and functions, so that they can be imported at once.
+//!
+//! ```
+//! use ark_bls12_381::{Bls12_381 as F, Fr};
+//! use ark_ec::CurveGroup;
+//! use ark_std::{test_rng, UniformRand};
+//! use groth_sahai::prelude::*;
+//!
+//! let mut rng = test_rng();
+//! let crs = CRS::<F>::generate_crs(&mut rng);
+//! let com = commit_G1(&crs.g1_gen, &crs, &mut rng);
+//! assert_eq!(com.coms.len(), 1);
+//!
+//! // e(X_1, P_2) * e(-P_1, Y_1) = 1 for X_1 = x P_1 and Y_1 = x P_2
+//! let x = Fr::rand(&mut rng);
+//! let xvars = [(crs.g1_gen * x).into_affine()];
+//! let yvars = [(crs.g2_gen * x).into_affine()];
+//! let equ = PPE::<F>::equality_G1_G2(&crs);
+//! let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+//! let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+//! let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+//! assert_eq!(verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs), Ok(()));
+//! ```
+
+pub use crate::data_structures::{Com1, Com2, ComT, Mat, Matrix, MatrixError, B1, B2, BT};
+pub use crate::generator::{
+    AbstractCrs, Binding, BindingCRS, CRSError, CommitmentKey, Hiding, HidingCRS, SXDHKeys,
+    TypedCRS, CRS,
+};
+pub use crate::prover::{
+    batch_commit_G1, batch_commit_G2, batch_commit_scalar_to_B1, batch_commit_scalar_to_B2,
+    commit_G1, commit_G2, commit_scalar_to_B1, commit_scalar_to_B2, commit_witness, prove_msme_g1,
+    prove_msme_g2, prove_ppe, prove_ppe_checked, prove_quad, prove_system, prove_with_commitments,
+    try_prove_ppe, Commit1, Commit2, EquProof, Provable, ProverError, SystemCommitments,
+    SystemProof, SystemWitness,
+};
+pub use crate::statement::{
+    EquType, Equation, EquationSystem, GtTarget, QuadEqu, SystemEquation, MSMEG1, MSMEG2, PPE,
+};
+pub use crate::verifier::{
+    verify_msme_g1, verify_msme_g2, verify_ppe, verify_quad, verify_system, Verifiable,
+    VerificationError,
+};
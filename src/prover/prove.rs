@@ -4,26 +4,39 @@
 //! with respect to a pre-defined bilinear group `(A1, A2, AT)`:
 //!
 //! - `π`: 1-2 elements in [`B2`](crate::data_structures::Com2) (equiv. 2-4 elements in [`G2`](ark_ec::Pairing::G2Affine))
-//!     which prove about the satisfiability of `A2` variables in the equation, and
+//!   which prove about the satisfiability of `A2` variables in the equation, and
 //! - `θ`: 1-2 elements in [`B1`](crate::data_structures::Com1) (equiv. 2-4 elements in [`G1`](ark_ec::Pairing::G1Affine))
-//!     which prove about the satisfiability of `A1` variables in the equation
+//!   which prove about the satisfiability of `A1` variables in the equation
 //!
 //! Computing these proofs primarily involves matrix multiplication in the [scalar field](ark_ec::Pairing::Fr) and in `B1` and `B2`.
 //!
+//! The commitment randomness `R, S` and the proof randomness `T` are secret, and the group
+//! operations that consume them are not constant-time (see [`commit`](crate::prover::commit#side-channels)).
+//!
 //! See the [`statement`](crate::statement) module for more details about the structure of the equations being proven about.
 
 use ark_ec::pairing::Pairing;
 use ark_ec::pairing::PairingOutput;
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_std::{rand::Rng, UniformRand};
+use ark_ec::AffineRepr;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use ark_std::{fmt, rand::Rng, vec::Vec, One, UniformRand, Zero};
+use digest::{consts::U32, Digest};
 
 use super::commit::{
     batch_commit_G1, batch_commit_G2, batch_commit_scalar_to_B1, batch_commit_scalar_to_B2,
-    Commit1, Commit2,
+    commit_scalar_to_B1_with_randomness, commit_scalar_to_B2_with_randomness, Commit1, Commit2,
+    Secret,
 };
 use crate::data_structures::{col_vec_to_vec, vec_to_col_vec, Com1, Com2, Mat, Matrix, B1, B2};
-use crate::generator::CRS;
-use crate::statement::{EquType, QuadEqu, MSMEG1, MSMEG2, PPE};
+use crate::generator::{HidingCRS, SimulationTrapdoor, CRS};
+use crate::statement::{
+    distinct_elements, EquType, Equation, EquationSystem, GtTarget, QuadEqu, SystemEquation,
+    MSMEG1, MSMEG2, PPE,
+};
+use crate::verifier::{check_dims, VerificationError};
 
 /// A collection  of attributes containing prover functionality for an [`Equation`](crate::statement::Equation).
 pub trait Provable<E: Pairing, A1, A2, AT> {
@@ -51,13 +64,188 @@ pub trait Provable<E: Pairing, A1, A2, AT> {
         CR: Rng;
 }
 
+/// Describes why the prover refused to produce a proof.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProverError {
+    /// `variable` has `found` entries, but the equation's constants require `expected`.
+    DimensionMismatch {
+        variable: &'static str,
+        expected: usize,
+        found: usize,
+    },
+    /// The witness does not satisfy the equation, so any proof for it would fail to verify.
+    Unsatisfied,
+    /// The [`LinearMapCache`] was computed for another equation or CRS.
+    CacheMismatch,
+}
+
+impl fmt::Display for ProverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DimensionMismatch {
+                variable,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{} has {} entries, but the equation requires {}",
+                variable, found, expected
+            ),
+            Self::Unsatisfied => write!(f, "the witness does not satisfy the equation"),
+            Self::CacheMismatch => write!(
+                f,
+                "the cached linear maps were computed for another equation or CRS"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ProverError {}
+
+// Checks that the witness, its commitments and `Γ` have the shape required by an equation with
+// `m = b_len` x variables and `n = a_len` y variables, committed to with `x_cols` and `y_cols`
+// scalars of randomness each (2 for group elements, 1 for scalars).
+#[allow(clippy::too_many_arguments)]
+fn check_prover_dims<E: Pairing>(
+    a_len: usize,
+    b_len: usize,
+    gamma: &Matrix<E::ScalarField>,
+    xvars_len: usize,
+    yvars_len: usize,
+    xcoms: &Commit1<E>,
+    ycoms: &Commit2<E>,
+    x_cols: usize,
+    y_cols: usize,
+) -> Result<(), ProverError> {
+    let check = |variable, expected, found| {
+        if expected == found {
+            Ok(())
+        } else {
+            Err(ProverError::DimensionMismatch {
+                variable,
+                expected,
+                found,
+            })
+        }
+    };
+    check("xvars", b_len, xvars_len)?;
+    check("xcoms", b_len, xcoms.coms.len())?;
+    check("xcoms randomness", b_len, xcoms.rand.len())?;
+    xcoms
+        .rand
+        .iter()
+        .try_for_each(|row| check("xcoms randomness row", x_cols, row.len()))?;
+    check("yvars", a_len, yvars_len)?;
+    check("ycoms", a_len, ycoms.coms.len())?;
+    check("ycoms randomness", a_len, ycoms.rand.len())?;
+    ycoms
+        .rand
+        .iter()
+        .try_for_each(|row| check("ycoms randomness row", y_cols, row.len()))?;
+    check("gamma", b_len, gamma.len())?;
+    gamma
+        .iter()
+        .try_for_each(|row| check("gamma row", a_len, row.len()))
+}
+
 /// A witness-indistinguishable proof for a single [`Equation`](crate::statement::Equation).
-#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+///
+/// Only the public part `(π, θ)` and the equation type make up the proof: they are what is
+/// serialized and compared. The proof randomness `T` is kept for
+/// [`rerandomize_proof`], but it is secret and never serialized, so a deserialized proof
+/// carries `T = 0`.
+#[derive(Clone, Debug)]
 pub struct EquProof<E: Pairing> {
     pub pi: Vec<Com2<E>>,
     pub theta: Vec<Com1<E>>,
     pub equ_type: EquType,
-    rand: Matrix<E::ScalarField>,
+    rand: Secret<E::ScalarField>,
+}
+
+impl<E: Pairing> PartialEq for EquProof<E> {
+    fn eq(&self, other: &Self) -> bool {
+        self.pi == other.pi && self.theta == other.theta && self.equ_type == other.equ_type
+    }
+}
+impl<E: Pairing> Eq for EquProof<E> {}
+
+impl<E: Pairing> CanonicalSerialize for EquProof<E> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.pi.serialize_with_mode(&mut writer, compress)?;
+        self.theta.serialize_with_mode(&mut writer, compress)?;
+        self.equ_type.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.pi.serialized_size(compress)
+            + self.theta.serialized_size(compress)
+            + self.equ_type.serialized_size(compress)
+    }
+}
+
+impl<E: Pairing> Valid for EquProof<E> {
+    fn check(&self) -> Result<(), SerializationError> {
+        self.pi.check()?;
+        self.theta.check()?;
+        self.equ_type.check()
+    }
+}
+
+impl<E: Pairing> CanonicalDeserialize for EquProof<E> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let pi = Vec::<Com2<E>>::deserialize_with_mode(&mut reader, compress, validate)?;
+        let theta = Vec::<Com1<E>>::deserialize_with_mode(&mut reader, compress, validate)?;
+        let equ_type = EquType::deserialize_with_mode(&mut reader, compress, validate)?;
+        // T is a (|θ| x |π|) matrix
+        let (pi_len, theta_len) = equ_type.proof_dims();
+        Ok(Self {
+            pi,
+            theta,
+            equ_type,
+            rand: vec![vec![E::ScalarField::zero(); pi_len]; theta_len].into(),
+        })
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<E: Pairing> zeroize::Zeroize for EquProof<E> {
+    fn zeroize(&mut self) {
+        self.rand.zeroize();
+    }
+}
+#[cfg(feature = "zeroize")]
+impl<E: Pairing> zeroize::ZeroizeOnDrop for EquProof<E> {}
+
+impl<E: Pairing> EquProof<E> {
+    /// Attaches the [`fingerprint`](CRS::fingerprint) of the CRS the proof was generated under,
+    /// so that [`verify_ppe_fingerprinted`](crate::verifier::verify_ppe_fingerprinted) can tell
+    /// a proof for another CRS apart from an invalid one.
+    pub fn with_crs_fingerprint<D: Digest<OutputSize = U32>>(
+        self,
+        crs: &CRS<E>,
+    ) -> FingerprintedProof<E> {
+        FingerprintedProof {
+            proof: self,
+            crs_fingerprint: crs.fingerprint::<D>(),
+        }
+    }
+}
+
+/// An [`EquProof`] together with the [`fingerprint`](CRS::fingerprint) of the CRS it was
+/// generated under, as returned by [`EquProof::with_crs_fingerprint`].
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct FingerprintedProof<E: Pairing> {
+    pub proof: EquProof<E>,
+    pub crs_fingerprint: [u8; 32],
 }
 
 /// A collection of committed variables and proofs for Groth-Sahai compatible bilinear equations.
@@ -101,74 +289,476 @@ impl<E: Pairing> Provable<E, E::G1Affine, E::G2Affine, PairingOutput<E>> for PPE
     where
         CR: Rng,
     {
-        // Gamma is an (m x n) matrix with m x variables and n y variables
-        // x's commit randomness (i.e. R) is a (m x 2) matrix
-        assert_eq!(xvars.len(), xcoms.rand.len());
-        assert_eq!(self.gamma.len(), xcoms.rand.len());
-        assert_eq!(xcoms.rand[0].len(), 2);
-        let _m = xvars.len();
-        // y's commit randomness (i.e. S) is a (n x 2) matrix
-        assert_eq!(yvars.len(), ycoms.rand.len());
-        assert_eq!(self.gamma[0].len(), ycoms.rand.len());
-        assert_eq!(ycoms.rand[0].len(), 2);
-        let _n = yvars.len();
+        prove_ppe_with_maps(
+            self,
+            &Com1::<E>::batch_linear_map(&self.a_consts),
+            &Com2::<E>::batch_linear_map(&self.b_consts),
+            xvars,
+            yvars,
+            xcoms,
+            ycoms,
+            crs,
+            rng,
+        )
+    }
+}
 
-        let is_parallel = true;
+/// Produces a proof `(π, θ)` that the committed `x` and `y` variables satisfy the pairing-product
+/// equation `equ`, where `xcoms` and `ycoms` carry the opening randomness `R` and `S`.
+///
+/// Equivalent to [`Provable::prove`] for [`PPE`]; the proof can be checked with
+/// [`verify_ppe`](crate::verifier::verify_ppe). Either side of the equation may be without
+/// variables. Panics if the variables or commitments do not fit `equ`, see [`try_prove_ppe`].
+pub fn prove_ppe<CR, E>(
+    equ: &PPE<E>,
+    xvars: &[E::G1Affine],
+    yvars: &[E::G2Affine],
+    xcoms: &Commit1<E>,
+    ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    equ.prove(xvars, yvars, xcoms, ycoms, crs, rng)
+}
 
-        // (2 x m) field matrix R^T, in GS parlance
-        let x_rand_trans = xcoms.rand.transpose();
-        // (2 x n) field matrix S^T, in GS parlance
-        let y_rand_trans = ycoms.rand.transpose();
-        // (2 x 2) field matrix T, in GS parlance
-        let pf_rand: Matrix<E::ScalarField> = vec![
-            vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
-            vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
-        ];
+/// Like [`prove_ppe`], but first checks that the variables, their commitments and `Γ` match the
+/// dimensions given by the constants of `equ` (`m = |B|` x variables, `n = |A|` y variables),
+/// instead of producing a meaningless proof or panicking.
+pub fn try_prove_ppe<CR, E>(
+    equ: &PPE<E>,
+    xvars: &[E::G1Affine],
+    yvars: &[E::G2Affine],
+    xcoms: &Commit1<E>,
+    ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> Result<EquProof<E>, ProverError>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    check_prover_dims(
+        equ.a_consts.len(),
+        equ.b_consts.len(),
+        &equ.gamma,
+        xvars.len(),
+        yvars.len(),
+        xcoms,
+        ycoms,
+        2,
+        2,
+    )?;
+    Ok(prove_ppe(equ, xvars, yvars, xcoms, ycoms, crs, rng))
+}
 
-        // (2 x 1) Com2 matrix
-        let x_rand_lin_b = vec_to_col_vec(&Com2::<E>::batch_linear_map(&self.b_consts))
-            .left_mul(&x_rand_trans, is_parallel);
+/// Like [`try_prove_ppe`], but additionally checks that the witness satisfies `equ` (see
+/// [`Equation::is_satisfied`]) and returns [`ProverError::Unsatisfied`] before any proof is made.
+///
+/// This is opt-in and meant for debugging: the check evaluates the equation in the clear, so
+/// its running time depends on the witness.
+pub fn prove_ppe_checked<CR, E>(
+    equ: &PPE<E>,
+    xvars: &[E::G1Affine],
+    yvars: &[E::G2Affine],
+    xcoms: &Commit1<E>,
+    ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> Result<EquProof<E>, ProverError>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    check_prover_dims(
+        equ.a_consts.len(),
+        equ.b_consts.len(),
+        &equ.gamma,
+        xvars.len(),
+        yvars.len(),
+        xcoms,
+        ycoms,
+        2,
+        2,
+    )?;
+    if !equ.is_satisfied(xvars, yvars) {
+        return Err(ProverError::Unsatisfied);
+    }
+    Ok(prove_ppe(equ, xvars, yvars, xcoms, ycoms, crs, rng))
+}
 
-        // (2 x n) field matrix
-        let x_rand_stmt = x_rand_trans.right_mul(&self.gamma, is_parallel);
-        // (2 x 1) Com2 matrix
-        let x_rand_stmt_lin_y =
-            vec_to_col_vec(&Com2::<E>::batch_linear_map(yvars)).left_mul(&x_rand_stmt, is_parallel);
+/// The images `ι_1(A)` and `ι_2(B)` (resp. `ι_1'(a)` and `ι_2'(b)` for quadratic equations) of
+/// the constants of an equation, for proving it repeatedly with [`prove_ppe_cached`] or
+/// [`prove_quad_cached`].
+///
+/// For pairing-product equations, the maps only embed the constants, so caching saves their
+/// allocation; for quadratic equations it also saves two scalar multiplications per constant.
+/// The cache remembers what it was computed for, and the cached provers reject it for any other
+/// equation or CRS.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinearMapCache<E: Pairing> {
+    lin_a: Vec<Com1<E>>,
+    lin_b: Vec<Com2<E>>,
+    computed_for: CachedFor<E>,
+}
 
-        // (2 x 2) field matrix
-        let pf_rand_stmt = x_rand_trans
-            .right_mul(&self.gamma, is_parallel)
-            .right_mul(&ycoms.rand, is_parallel)
-            .add(&pf_rand.transpose().neg());
-        // (2 x 1) Com2 matrix
-        let pf_rand_stmt_com2 = vec_to_col_vec(&crs.v).left_mul(&pf_rand_stmt, is_parallel);
+// What the images of a `LinearMapCache` were computed from, checked before every use: the
+// images of a pairing-product equation's constants determine the constants themselves, while
+// those of a quadratic equation also depend on the bases `u = u_2 + (O, P_1)` and
+// `v = v_2 + (O, P_2)` of the CRS.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CachedFor<E: Pairing> {
+    PairingProduct,
+    Quadratic {
+        a_consts: Vec<E::ScalarField>,
+        b_consts: Vec<E::ScalarField>,
+        u: Com1<E>,
+        v: Com2<E>,
+    },
+}
 
-        let pi = col_vec_to_vec(&x_rand_lin_b.add(&x_rand_stmt_lin_y).add(&pf_rand_stmt_com2));
-        assert_eq!(pi.len(), 2);
+impl<E: Pairing> LinearMapCache<E> {
+    /// Computes the images of the constants of the pairing-product equation `equ`.
+    pub fn for_ppe(equ: &PPE<E>) -> Self {
+        Self {
+            lin_a: Com1::<E>::batch_linear_map(&equ.a_consts),
+            lin_b: Com2::<E>::batch_linear_map(&equ.b_consts),
+            computed_for: CachedFor::PairingProduct,
+        }
+    }
 
-        // (2 x 1) Com1 matrix
-        let y_rand_lin_a = vec_to_col_vec(&Com1::<E>::batch_linear_map(&self.a_consts))
-            .left_mul(&y_rand_trans, is_parallel);
+    /// Computes the images of the constants of the quadratic equation `equ` under `crs`; the
+    /// cache can only be used for proofs under the same CRS.
+    pub fn for_quad(equ: &QuadEqu<E>, crs: &CRS<E>) -> Self {
+        Self {
+            lin_a: Com1::<E>::batch_scalar_linear_map(&equ.a_consts, crs),
+            lin_b: Com2::<E>::batch_scalar_linear_map(&equ.b_consts, crs),
+            computed_for: CachedFor::Quadratic {
+                a_consts: equ.a_consts.clone(),
+                b_consts: equ.b_consts.clone(),
+                u: *crs.u2() + Com1::<E>::linear_map(&crs.g1_gen),
+                v: *crs.v2() + Com2::<E>::linear_map(&crs.g2_gen),
+            },
+        }
+    }
 
-        // (2 x m) field matrix
-        let y_rand_stmt = y_rand_trans.right_mul(&self.gamma.transpose(), is_parallel);
-        // (2 x 1) Com1 matrix
-        let y_rand_stmt_lin_x =
-            vec_to_col_vec(&Com1::<E>::batch_linear_map(xvars)).left_mul(&y_rand_stmt, is_parallel);
+    fn check_ppe(&self, equ: &PPE<E>) -> Result<(), ProverError> {
+        let fits = matches!(self.computed_for, CachedFor::PairingProduct)
+            && self.lin_a.len() == equ.a_consts.len()
+            && self.lin_b.len() == equ.b_consts.len()
+            && (self.lin_a.iter().zip(&equ.a_consts)).all(|(c, a)| *c == Com1::linear_map(a))
+            && (self.lin_b.iter().zip(&equ.b_consts)).all(|(d, b)| *d == Com2::linear_map(b));
+        fits.then_some(()).ok_or(ProverError::CacheMismatch)
+    }
 
-        // (2 x 1) Com1 matrix
-        let pf_rand_com1 = vec_to_col_vec(&crs.u).left_mul(&pf_rand, is_parallel);
+    fn check_quad(&self, equ: &QuadEqu<E>, crs: &CRS<E>) -> Result<(), ProverError> {
+        let fits = match &self.computed_for {
+            CachedFor::Quadratic {
+                a_consts,
+                b_consts,
+                u,
+                v,
+            } => {
+                *a_consts == equ.a_consts
+                    && *b_consts == equ.b_consts
+                    && *u == *crs.u2() + Com1::<E>::linear_map(&crs.g1_gen)
+                    && *v == *crs.v2() + Com2::<E>::linear_map(&crs.g2_gen)
+            }
+            CachedFor::PairingProduct => false,
+        };
+        fits.then_some(()).ok_or(ProverError::CacheMismatch)
+    }
+}
 
-        let theta = col_vec_to_vec(&y_rand_lin_a.add(&y_rand_stmt_lin_x).add(&pf_rand_com1));
-        assert_eq!(theta.len(), 2);
+/// Like [`prove_ppe`], but takes the images of the constants of `equ` from `cache`, which must
+/// have been computed by [`LinearMapCache::for_ppe`] for `equ`; otherwise returns
+/// [`ProverError::CacheMismatch`].
+///
+/// The proof is identical to the one computed by [`prove_ppe`] from the same randomness.
+#[allow(clippy::too_many_arguments)]
+pub fn prove_ppe_cached<CR, E>(
+    equ: &PPE<E>,
+    cache: &LinearMapCache<E>,
+    xvars: &[E::G1Affine],
+    yvars: &[E::G2Affine],
+    xcoms: &Commit1<E>,
+    ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> Result<EquProof<E>, ProverError>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    cache.check_ppe(equ)?;
+    Ok(prove_ppe_with_maps(
+        equ,
+        &cache.lin_a,
+        &cache.lin_b,
+        xvars,
+        yvars,
+        xcoms,
+        ycoms,
+        crs,
+        rng,
+    ))
+}
 
+// Proves `equ` given the images `lin_a = ι_1(A)` and `lin_b = ι_2(B)` of its constants
+#[allow(clippy::too_many_arguments)]
+fn prove_ppe_with_maps<CR, E>(
+    equ: &PPE<E>,
+    lin_a: &[Com1<E>],
+    lin_b: &[Com2<E>],
+    xvars: &[E::G1Affine],
+    yvars: &[E::G2Affine],
+    xcoms: &Commit1<E>,
+    ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    if let Err(err) = check_prover_dims(
+        equ.a_consts.len(),
+        equ.b_consts.len(),
+        &equ.gamma,
+        xvars.len(),
+        yvars.len(),
+        xcoms,
+        ycoms,
+        2,
+        2,
+    ) {
+        panic!("{}", err);
+    }
+    // Gamma is an (m x n) matrix with m x variables and n y variables, and the commit randomness
+    // R and S of x and y are (m x 2) and (n x 2) matrices. Either m or n may be zero, so the
+    // products are summed out per entry instead of going through (possibly empty) matrices.
+    let x_rand: &Matrix<E::ScalarField> = &xcoms.rand;
+    let y_rand: &Matrix<E::ScalarField> = &ycoms.rand;
+    let n = yvars.len();
+
+    // (2 x 2) field matrix T, in GS parlance
+    let pf_rand: Matrix<E::ScalarField> = vec![
+        vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
+        vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
+    ];
+
+    // (2 x n) field matrix R^T Gamma
+    let x_rand_stmt: Matrix<E::ScalarField> = (0..2)
+        .map(|k| {
+            (0..n)
+                .map(|j| {
+                    x_rand
+                        .iter()
+                        .zip(&equ.gamma)
+                        .map(|(r, row)| r[k] * row[j])
+                        .sum()
+                })
+                .collect()
+        })
+        .collect();
+    // (2 x m) field matrix S^T Gamma^T
+    let y_rand_stmt: Matrix<E::ScalarField> = (0..2)
+        .map(|k| {
+            equ.gamma
+                .iter()
+                .map(|row| y_rand.iter().zip(row).map(|(s, g)| s[k] * g).sum())
+                .collect()
+        })
+        .collect();
+    // (2 x 2) field matrix R^T Gamma S - T^T
+    let pf_rand_stmt: Matrix<E::ScalarField> = (0..2)
+        .map(|k| {
+            (0..2)
+                .map(|j| {
+                    x_rand_stmt[k]
+                        .iter()
+                        .zip(y_rand)
+                        .map(|(rg, s)| *rg * s[j])
+                        .sum::<E::ScalarField>()
+                        - pf_rand[j][k]
+                })
+                .collect()
+        })
+        .collect();
+
+    // π := R^T i_2(B) + R^T Gamma i_2(Y) + (R^T Gamma S - T^T) v
+    let lin_y = Com2::<E>::batch_linear_map(yvars);
+    let pi: Vec<Com2<E>> = (0..2)
+        .map(|k| {
+            let r_lin_b: Com2<E> = x_rand.iter().zip(lin_b).map(|(r, b)| *b * r[k]).sum();
+            let r_stmt_lin_y: Com2<E> = x_rand_stmt[k]
+                .iter()
+                .zip(&lin_y)
+                .map(|(rg, y)| *y * *rg)
+                .sum();
+            let stmt_v: Com2<E> = (0..2).map(|j| crs.v[j] * pf_rand_stmt[k][j]).sum();
+            r_lin_b + r_stmt_lin_y + stmt_v
+        })
+        .collect();
+
+    // θ := S^T i_1(A) + S^T Gamma^T i_1(X) + T u
+    let lin_x = Com1::<E>::batch_linear_map(xvars);
+    let theta: Vec<Com1<E>> = (0..2)
+        .map(|k| {
+            let s_lin_a: Com1<E> = y_rand.iter().zip(lin_a).map(|(s, a)| *a * s[k]).sum();
+            let s_stmt_lin_x: Com1<E> = y_rand_stmt[k]
+                .iter()
+                .zip(&lin_x)
+                .map(|(sg, x)| *x * *sg)
+                .sum();
+            let t_u: Com1<E> = (0..2).map(|j| crs.u[j] * pf_rand[k][j]).sum();
+            s_lin_a + s_stmt_lin_x + t_u
+        })
+        .collect();
+
+    EquProof::<E> {
+        pi,
+        theta,
+        equ_type: EquType::PairingProduct,
+        rand: pf_rand.into(),
+    }
+}
+
+/// Commitments to the `x` and `y` variables of an equation together with a proof about them.
+pub type CommittedProof<E> = (Vec<Com1<E>>, Vec<Com2<E>>, EquProof<E>);
+
+/// Re-randomizes the commitments `xcoms` and `ycoms` and a proof `(π, θ)` for the
+/// pairing-product equation `equ` without knowledge of the witness.
+///
+/// Returns fresh commitments to the same variables together with a proof for them which is
+/// distributed like a freshly generated one, so both still verify with
+/// [`verify_ppe`](crate::verifier::verify_ppe). Either side of the equation may be without
+/// variables; commitments or a proof that do not fit `equ` are rejected with
+/// [`VerificationError::DimensionMismatch`].
+pub fn rerandomize_proof<CR, E>(
+    equ: &PPE<E>,
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> Result<CommittedProof<E>, VerificationError>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    check_dims(
+        &equ.a_consts,
+        &equ.b_consts,
+        &equ.gamma,
+        EquType::PairingProduct,
+        xcoms,
+        ycoms,
+        proof,
+    )?;
+
+    // (m x 2) and (n x 2) field matrices R' and S' of additional commitment randomness
+    let x_rand: Matrix<E::ScalarField> = (0..xcoms.len())
+        .map(|_| vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)])
+        .collect();
+    let y_rand: Matrix<E::ScalarField> = (0..ycoms.len())
+        .map(|_| vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)])
+        .collect();
+    // (2 x 2) field matrix T' of additional proof randomness
+    let pf_rand: Matrix<E::ScalarField> = vec![
+        vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
+        vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
+    ];
+
+    // c' := c + R' u, d' := d + S' v
+    let new_xcoms = xcoms
+        .iter()
+        .zip(&x_rand)
+        .map(|(c, r)| *c + crs.u[0] * r[0] + crs.u[1] * r[1])
+        .collect();
+    let new_ycoms = ycoms
+        .iter()
+        .zip(&y_rand)
+        .map(|(d, s)| *d + crs.v[0] * s[0] + crs.v[1] * s[1])
+        .collect();
+
+    // As either m or n may be zero, the products are summed out per entry instead of going
+    // through (possibly empty) matrices
+    // π' := π + R'^T (i_2(B) + Gamma d) + (R'^T Gamma S' - T'^T) v
+    let lin_b_stmt_com_y: Vec<Com2<E>> = Com2::<E>::batch_linear_map(&equ.b_consts)
+        .into_iter()
+        .zip(&equ.gamma)
+        .map(|(b, row)| b + row.iter().zip(ycoms).map(|(g, d)| *d * *g).sum::<Com2<E>>())
+        .collect();
+    // (m x 2) field matrix Gamma S'
+    let stmt_y_rand: Matrix<E::ScalarField> = equ
+        .gamma
+        .iter()
+        .map(|row| {
+            (0..2)
+                .map(|j| row.iter().zip(&y_rand).map(|(g, s)| *g * s[j]).sum())
+                .collect()
+        })
+        .collect();
+    let pi = (0..2)
+        .map(|k| {
+            let r_stmt: Com2<E> = x_rand
+                .iter()
+                .zip(&lin_b_stmt_com_y)
+                .map(|(r, b)| *b * r[k])
+                .sum();
+            let stmt_v: Com2<E> = (0..2)
+                .map(|j| {
+                    let coeff = x_rand
+                        .iter()
+                        .zip(&stmt_y_rand)
+                        .map(|(r, gs)| r[k] * gs[j])
+                        .sum::<E::ScalarField>()
+                        - pf_rand[j][k];
+                    crs.v[j] * coeff
+                })
+                .sum();
+            proof.pi[k] + r_stmt + stmt_v
+        })
+        .collect();
+
+    // θ' := θ + S'^T (i_1(A) + Gamma^T c) + T' u
+    let lin_a_stmt_com_x: Vec<Com1<E>> = Com1::<E>::batch_linear_map(&equ.a_consts)
+        .into_iter()
+        .enumerate()
+        .map(|(l, a)| {
+            a + xcoms
+                .iter()
+                .zip(&equ.gamma)
+                .map(|(c, row)| *c * row[l])
+                .sum::<Com1<E>>()
+        })
+        .collect();
+    let theta = (0..2)
+        .map(|k| {
+            let s_stmt: Com1<E> = y_rand
+                .iter()
+                .zip(&lin_a_stmt_com_x)
+                .map(|(s, a)| *a * s[k])
+                .sum();
+            let t_u: Com1<E> = (0..2).map(|j| crs.u[j] * pf_rand[k][j]).sum();
+            proof.theta[k] + s_stmt + t_u
+        })
+        .collect();
+
+    Ok((
+        new_xcoms,
+        new_ycoms,
         EquProof::<E> {
             pi,
             theta,
             equ_type: EquType::PairingProduct,
-            rand: pf_rand,
-        }
-    }
+            rand: proof.rand.add(&pf_rand).into(),
+        },
+    ))
 }
 
 impl<E: Pairing> Provable<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG1<E> {
@@ -243,10 +833,13 @@ impl<E: Pairing> Provable<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG
             .right_mul(&scalar_ycoms.rand, is_parallel)
             .add(&pf_rand.transpose().neg());
         // (2 x 1) Com2 matrix
-        let v1: Matrix<Com2<E>> = vec![vec![crs.v[0]]];
+        let v1: Matrix<Com2<E>> = vec![vec![*crs.v1()]];
         let pf_rand_stmt_com2 = v1.left_mul(&pf_rand_stmt, is_parallel);
 
-        let pi = col_vec_to_vec(&x_rand_lin_b.add(&x_rand_stmt_lin_y).add(&pf_rand_stmt_com2));
+        let mut pi = x_rand_lin_b;
+        pi.add_in_place(&x_rand_stmt_lin_y);
+        pi.add_in_place(&pf_rand_stmt_com2);
+        let pi = col_vec_to_vec(&pi);
         assert_eq!(pi.len(), 2);
 
         // (1 x 1) Com1 matrix
@@ -262,18 +855,43 @@ impl<E: Pairing> Provable<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG
         // (1 x 1) Com1 matrix
         let pf_rand_com1 = vec_to_col_vec(&crs.u).left_mul(&pf_rand, is_parallel);
 
-        let theta = col_vec_to_vec(&y_rand_lin_a.add(&y_rand_stmt_lin_x).add(&pf_rand_com1));
+        let mut theta = y_rand_lin_a;
+        theta.add_in_place(&y_rand_stmt_lin_x);
+        theta.add_in_place(&pf_rand_com1);
+        let theta = col_vec_to_vec(&theta);
         assert_eq!(theta.len(), 1);
 
         EquProof::<E> {
             pi,
             theta,
             equ_type: EquType::MultiScalarG1,
-            rand: pf_rand,
+            rand: pf_rand.into(),
         }
     }
 }
 
+/// Produces a proof `(π, θ)` that the committed [`G1`](ark_ec::Pairing::G1Affine) variables `x`
+/// and [scalar](ark_ec::Pairing::Fr) variables `y` satisfy the multi-scalar multiplication
+/// equation `equ`, where `xcoms` and `scalar_ycoms` carry the opening randomness `R` and `s`.
+///
+/// Equivalent to [`Provable::prove`] for [`MSMEG1`]; the proof can be checked with
+/// [`verify_msme_g1`](crate::verifier::verify_msme_g1).
+pub fn prove_msme_g1<CR, E>(
+    equ: &MSMEG1<E>,
+    xvars: &[E::G1Affine],
+    scalar_yvars: &[E::ScalarField],
+    xcoms: &Commit1<E>,
+    scalar_ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    equ.prove(xvars, scalar_yvars, xcoms, scalar_ycoms, crs, rng)
+}
+
 impl<E: Pairing> Provable<E, E::ScalarField, E::G2Affine, E::G2Affine> for MSMEG2<E> {
     fn commit_and_prove<CR>(
         &self,
@@ -349,7 +967,10 @@ impl<E: Pairing> Provable<E, E::ScalarField, E::G2Affine, E::G2Affine> for MSMEG
         // (1 x 1) Com2 matrix
         let pf_rand_stmt_com2 = vec_to_col_vec(&crs.v).left_mul(&pf_rand_stmt, is_parallel);
 
-        let pi = col_vec_to_vec(&x_rand_lin_b.add(&x_rand_stmt_lin_y).add(&pf_rand_stmt_com2));
+        let mut pi = x_rand_lin_b;
+        pi.add_in_place(&x_rand_stmt_lin_y);
+        pi.add_in_place(&pf_rand_stmt_com2);
+        let pi = col_vec_to_vec(&pi);
         assert_eq!(pi.len(), 1);
 
         // (2 x 1) Com1 matrix
@@ -364,21 +985,46 @@ impl<E: Pairing> Provable<E, E::ScalarField, E::G2Affine, E::G2Affine> for MSMEG
                 .left_mul(&y_rand_stmt, is_parallel);
 
         // (2 x 1) Com1 matrix
-        let u1: Matrix<Com1<E>> = vec![vec![crs.u[0]]];
+        let u1: Matrix<Com1<E>> = vec![vec![*crs.u1()]];
         let pf_rand_com1 = u1.left_mul(&pf_rand, is_parallel);
 
-        let theta = col_vec_to_vec(&y_rand_lin_a.add(&y_rand_stmt_lin_x).add(&pf_rand_com1));
+        let mut theta = y_rand_lin_a;
+        theta.add_in_place(&y_rand_stmt_lin_x);
+        theta.add_in_place(&pf_rand_com1);
+        let theta = col_vec_to_vec(&theta);
         assert_eq!(theta.len(), 2);
 
         EquProof::<E> {
             pi,
             theta,
             equ_type: EquType::MultiScalarG2,
-            rand: pf_rand,
+            rand: pf_rand.into(),
         }
     }
 }
 
+/// Produces a proof `(π, θ)` that the committed [scalar](ark_ec::Pairing::Fr) variables `x` and
+/// [`G2`](ark_ec::Pairing::G2Affine) variables `y` satisfy the multi-scalar multiplication
+/// equation `equ`, where `scalar_xcoms` and `ycoms` carry the opening randomness `r` and `S`.
+///
+/// Equivalent to [`Provable::prove`] for [`MSMEG2`]; the proof can be checked with
+/// [`verify_msme_g2`](crate::verifier::verify_msme_g2).
+pub fn prove_msme_g2<CR, E>(
+    equ: &MSMEG2<E>,
+    scalar_xvars: &[E::ScalarField],
+    yvars: &[E::G2Affine],
+    scalar_xcoms: &Commit1<E>,
+    ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    equ.prove(scalar_xvars, yvars, scalar_xcoms, ycoms, crs, rng)
+}
+
 impl<E: Pairing> Provable<E, E::ScalarField, E::ScalarField, E::ScalarField> for QuadEqu<E> {
     fn commit_and_prove<CR>(
         &self,
@@ -418,76 +1064,735 @@ impl<E: Pairing> Provable<E, E::ScalarField, E::ScalarField, E::ScalarField> for
     where
         CR: Rng,
     {
-        // Gamma is an (m' x n') matrix with m' x variables and n' y variables
-        // x's commit randomness (i.e. r) is a (m' x 1) matrix (i.e. column vector)
-        assert_eq!(scalar_xvars.len(), scalar_xcoms.rand.len());
-        assert_eq!(self.gamma.len(), scalar_xcoms.rand.len());
-        assert_eq!(scalar_xcoms.rand[0].len(), 1);
-        let _m_prime = scalar_xvars.len();
-        // y's commit randomness (i.e. s) is a (n' x 1) matrix (i.e. column vector)
-        assert_eq!(scalar_yvars.len(), scalar_ycoms.rand.len());
-        assert_eq!(self.gamma[0].len(), scalar_ycoms.rand.len());
-        assert_eq!(scalar_ycoms.rand[0].len(), 1);
-        let _n_prime = scalar_yvars.len();
+        prove_quad_with_maps(
+            self,
+            &Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs),
+            &Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs),
+            scalar_xvars,
+            scalar_yvars,
+            scalar_xcoms,
+            scalar_ycoms,
+            crs,
+            rng,
+        )
+    }
+}
 
-        let is_parallel = true;
+/// Produces a proof `(π, θ)` that the committed [scalar](ark_ec::Pairing::Fr) variables `x` and
+/// `y` satisfy the quadratic equation `equ`, where `scalar_xcoms` and `scalar_ycoms` carry the
+/// opening randomness `r` and `s`.
+///
+/// Equivalent to [`Provable::prove`] for [`QuadEqu`]; the proof can be checked with
+/// [`verify_quad`](crate::verifier::verify_quad).
+pub fn prove_quad<CR, E>(
+    equ: &QuadEqu<E>,
+    scalar_xvars: &[E::ScalarField],
+    scalar_yvars: &[E::ScalarField],
+    scalar_xcoms: &Commit1<E>,
+    scalar_ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    equ.prove(
+        scalar_xvars,
+        scalar_yvars,
+        scalar_xcoms,
+        scalar_ycoms,
+        crs,
+        rng,
+    )
+}
 
-        // (1 x m') field matrix r^T, in GS parlance
-        let x_rand_trans = scalar_xcoms.rand.transpose();
-        // (1 x n') field matrix s^T, in GS parlance
-        let y_rand_trans = scalar_ycoms.rand.transpose();
-        // field element T, in GS parlance
-        let pf_rand: Matrix<E::ScalarField> = vec![vec![E::ScalarField::rand(rng)]];
+/// Like [`prove_quad`], but takes the images of the constants of `equ` from `cache`, which must
+/// have been computed by [`LinearMapCache::for_quad`] for `equ` and `crs`; otherwise returns
+/// [`ProverError::CacheMismatch`].
+///
+/// The proof is identical to the one computed by [`prove_quad`] from the same randomness.
+#[allow(clippy::too_many_arguments)]
+pub fn prove_quad_cached<CR, E>(
+    equ: &QuadEqu<E>,
+    cache: &LinearMapCache<E>,
+    scalar_xvars: &[E::ScalarField],
+    scalar_yvars: &[E::ScalarField],
+    scalar_xcoms: &Commit1<E>,
+    scalar_ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> Result<EquProof<E>, ProverError>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    cache.check_quad(equ, crs)?;
+    Ok(prove_quad_with_maps(
+        equ,
+        &cache.lin_a,
+        &cache.lin_b,
+        scalar_xvars,
+        scalar_yvars,
+        scalar_xcoms,
+        scalar_ycoms,
+        crs,
+        rng,
+    ))
+}
 
-        let x_rand_lin_b = vec_to_col_vec(&Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs))
-            .left_mul(&x_rand_trans, is_parallel);
+// Proves `equ` given the images `lin_a = ι_1'(a)` and `lin_b = ι_2'(b)` of its constants
+#[allow(clippy::too_many_arguments)]
+fn prove_quad_with_maps<CR, E>(
+    equ: &QuadEqu<E>,
+    lin_a: &[Com1<E>],
+    lin_b: &[Com2<E>],
+    scalar_xvars: &[E::ScalarField],
+    scalar_yvars: &[E::ScalarField],
+    scalar_xcoms: &Commit1<E>,
+    scalar_ycoms: &Commit2<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    // Gamma is an (m' x n') matrix with m' x variables and n' y variables
+    // x's commit randomness (i.e. r) is a (m' x 1) matrix (i.e. column vector)
+    assert_eq!(scalar_xvars.len(), scalar_xcoms.rand.len());
+    assert_eq!(equ.gamma.len(), scalar_xcoms.rand.len());
+    assert_eq!(scalar_xcoms.rand[0].len(), 1);
+    let _m_prime = scalar_xvars.len();
+    // y's commit randomness (i.e. s) is a (n' x 1) matrix (i.e. column vector)
+    assert_eq!(scalar_yvars.len(), scalar_ycoms.rand.len());
+    assert_eq!(equ.gamma[0].len(), scalar_ycoms.rand.len());
+    assert_eq!(scalar_ycoms.rand[0].len(), 1);
+    let _n_prime = scalar_yvars.len();
+
+    let is_parallel = true;
+
+    // (1 x m') field matrix r^T, in GS parlance
+    let x_rand_trans = scalar_xcoms.rand.transpose();
+    // (1 x n') field matrix s^T, in GS parlance
+    let y_rand_trans = scalar_ycoms.rand.transpose();
+    // field element T, in GS parlance
+    let pf_rand: Matrix<E::ScalarField> = vec![vec![E::ScalarField::rand(rng)]];
+
+    let x_rand_lin_b = vec_to_col_vec(lin_b).left_mul(&x_rand_trans, is_parallel);
+
+    // (1 x n') field matrix
+    let x_rand_stmt = x_rand_trans.right_mul(&equ.gamma, is_parallel);
+    // (1 x 1) Com2 matrix
+    let x_rand_stmt_lin_y = vec_to_col_vec(&Com2::<E>::batch_scalar_linear_map(scalar_yvars, crs))
+        .left_mul(&x_rand_stmt, is_parallel);
+
+    // (1 x 2) field matrix
+    let pf_rand_stmt = x_rand_trans
+        .right_mul(&equ.gamma, is_parallel)
+        .right_mul(&scalar_ycoms.rand, is_parallel)
+        .add(&pf_rand.transpose().neg());
+    let v1: Matrix<Com2<E>> = vec![vec![*crs.v1()]];
+    // (1 x 1) Com2 matrix
+    let pf_rand_stmt_com2 = v1.left_mul(&pf_rand_stmt, is_parallel);
+
+    let mut pi = x_rand_lin_b;
+    pi.add_in_place(&x_rand_stmt_lin_y);
+    pi.add_in_place(&pf_rand_stmt_com2);
+    let pi = col_vec_to_vec(&pi);
+    assert_eq!(pi.len(), 1);
+
+    // (1 x 1) Com1 matrix
+    let y_rand_lin_a = vec_to_col_vec(lin_a).left_mul(&y_rand_trans, is_parallel);
+
+    // (1 x m') field matrix
+    let y_rand_stmt = y_rand_trans.right_mul(&equ.gamma.transpose(), is_parallel);
+    // (1 x 1) Com1 matrix
+    let y_rand_stmt_lin_x = vec_to_col_vec(&Com1::<E>::batch_scalar_linear_map(scalar_xvars, crs))
+        .left_mul(&y_rand_stmt, is_parallel);
+
+    // (1 x 1) Com1 matrix
+    let u1: Matrix<Com1<E>> = vec![vec![*crs.u1()]];
+    let pf_rand_com1 = u1.left_mul(&pf_rand, is_parallel);
+
+    let mut theta = y_rand_lin_a;
+    theta.add_in_place(&y_rand_stmt_lin_x);
+    theta.add_in_place(&pf_rand_com1);
+    let theta = col_vec_to_vec(&theta);
+    assert_eq!(theta.len(), 1);
+
+    EquProof::<E> {
+        pi,
+        theta,
+        equ_type: EquType::Quadratic,
+        rand: pf_rand.into(),
+    }
+}
 
-        // (1 x n') field matrix
-        let x_rand_stmt = x_rand_trans.right_mul(&self.gamma, is_parallel);
-        // (1 x 1) Com2 matrix
-        let x_rand_stmt_lin_y =
-            vec_to_col_vec(&Com2::<E>::batch_scalar_linear_map(scalar_yvars, crs))
-                .left_mul(&x_rand_stmt, is_parallel);
+// Produces a proof for a pairing-product equation with Γ = 0 from the commitment randomness
+// alone, as the witness only enters a proof through Γ:
+// π := R^T i_2(B) - T^T v, θ := S^T i_1(A) + T u
+fn prove_linear_ppe<CR, E>(
+    equ: &PPE<E>,
+    x_rand: &[[E::ScalarField; 2]],
+    y_rand: &[[E::ScalarField; 2]],
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    debug_assert!(equ.gamma.iter().flatten().all(|g| g.is_zero()));
+    let pf_rand: Matrix<E::ScalarField> = vec![
+        vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
+        vec![E::ScalarField::rand(rng), E::ScalarField::rand(rng)],
+    ];
+    let lin_b = Com2::<E>::batch_linear_map(&equ.b_consts);
+    let lin_a = Com1::<E>::batch_linear_map(&equ.a_consts);
+
+    let pi = (0..2)
+        .map(|k| {
+            let r_lin_b: Com2<E> = x_rand.iter().zip(&lin_b).map(|(r, b)| *b * r[k]).sum();
+            let t_v: Com2<E> = (0..2).map(|j| crs.v[j] * pf_rand[j][k]).sum();
+            r_lin_b - t_v
+        })
+        .collect();
+    let theta = (0..2)
+        .map(|k| {
+            let s_lin_a: Com1<E> = y_rand.iter().zip(&lin_a).map(|(s, a)| *a * s[k]).sum();
+            let t_u: Com1<E> = (0..2).map(|j| crs.u[j] * pf_rand[k][j]).sum();
+            s_lin_a + t_u
+        })
+        .collect();
+
+    EquProof::<E> {
+        pi,
+        theta,
+        equ_type: EquType::PairingProduct,
+        rand: pf_rand.into(),
+    }
+}
 
-        // (1 x 2) field matrix
-        let pf_rand_stmt = x_rand_trans
-            .right_mul(&self.gamma, is_parallel)
-            .right_mul(&scalar_ycoms.rand, is_parallel)
-            .add(&pf_rand.transpose().neg());
-        let v1: Matrix<Com2<E>> = vec![vec![crs.v[0]]];
-        // (1 x 1) Com2 matrix
-        let pf_rand_stmt_com2 = v1.left_mul(&pf_rand_stmt, is_parallel);
+/// Proves that `com_a` and `com_b`, opened by the randomness `r_a` and `r_b`, commit to the same
+/// [`G1`](ark_ec::Pairing::G1Affine) element, without revealing it.
+///
+/// The proof is for the linear pairing-product equation [`PPE::equality_G1`] and only depends on
+/// the randomness. Returns `None` if the commitments do not open to the same element.
+/// The proof can be checked with
+/// [`verify_commitment_equality`](crate::verifier::verify_commitment_equality).
+pub fn prove_commitment_equality<CR, E>(
+    com_a: &Com1<E>,
+    r_a: &[E::ScalarField; 2],
+    com_b: &Com1<E>,
+    r_b: &[E::ScalarField; 2],
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> Option<EquProof<E>>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    // c_a - R_a u = i_1(X) = c_b - R_b u
+    let unblind = |com: &Com1<E>, r: &[E::ScalarField; 2]| {
+        *com - key.u1().scalar_mul(&r[0]) - key.u2().scalar_mul(&r[1])
+    };
+    if unblind(com_a, r_a) != unblind(com_b, r_b) {
+        return None;
+    }
+    Some(prove_linear_ppe(
+        &PPE::equality_G1(key),
+        &[*r_a, *r_b],
+        &[],
+        key,
+        rng,
+    ))
+}
 
-        let pi = col_vec_to_vec(&x_rand_lin_b.add(&x_rand_stmt_lin_y).add(&pf_rand_stmt_com2));
-        assert_eq!(pi.len(), 1);
+/// Proves that `com_x` and `com_y`, opened by the randomness `r` and `s`, commit to `x P_1` and
+/// `x P_2` for the same scalar `x`, where `P_1, P_2` are the generators of `key`, without
+/// revealing them.
+///
+/// The proof is for the linear pairing-product equation [`PPE::equality_G1_G2`] and only depends
+/// on the randomness. Returns `None` if the commitments do not open to such elements.
+/// The proof can be checked with
+/// [`verify_commitment_equality_G1_G2`](crate::verifier::verify_commitment_equality_G1_G2).
+#[allow(non_snake_case)]
+pub fn prove_commitment_equality_G1_G2<CR, E>(
+    com_x: &Com1<E>,
+    r: &[E::ScalarField; 2],
+    com_y: &Com2<E>,
+    s: &[E::ScalarField; 2],
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> Option<EquProof<E>>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    // i_1(X) = (0, X) and i_2(Y) = (0, Y)
+    let lin_x = *com_x - key.u1().scalar_mul(&r[0]) - key.u2().scalar_mul(&r[1]);
+    let lin_y = *com_y - key.v1().scalar_mul(&s[0]) - key.v2().scalar_mul(&s[1]);
+    if !lin_x.0.is_zero()
+        || !lin_y.0.is_zero()
+        || E::pairing(lin_x.1, key.g2_gen) != E::pairing(key.g1_gen, lin_y.1)
+    {
+        return None;
+    }
+    Some(prove_linear_ppe(
+        &PPE::equality_G1_G2(key),
+        &[*r],
+        &[*s],
+        key,
+        rng,
+    ))
+}
 
-        // (1 x 1) Com1 matrix
-        let y_rand_lin_a = vec_to_col_vec(&Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs))
-            .left_mul(&y_rand_trans, is_parallel);
+/// Proves that the scalar `y` committed to in `B2` with randomness `s` is the discrete logarithm
+/// of a point to `base`, i.e. that it satisfies [`MSMEG1::dlog`].
+///
+/// Scalars multiplying `G1` constants are committed to `B2` (see [`MSMEG1`]), so the commitment is a
+/// [`Com2`], e.g. from [`commit_scalar_to_B2`](super::commit::commit_scalar_to_B2). As the
+/// equation is linear, the proof only depends on `base` and `s`. The proof can be checked with
+/// [`verify_dlog_G1`](crate::verifier::verify_dlog_G1).
+#[allow(non_snake_case)]
+pub fn prove_dlog_G1<CR, E>(
+    base: &E::G1Affine,
+    s: &E::ScalarField,
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    // (1 x 2) field matrix T
+    let (t1, t2) = (E::ScalarField::rand(rng), E::ScalarField::rand(rng));
+    // π := -T^T v_1, θ := s i_1(base) + T u
+    let pi = vec![key.v1().scalar_mul(&-t1), key.v1().scalar_mul(&-t2)];
+    let theta = vec![
+        Com1::<E>::linear_map(base).scalar_mul(s)
+            + key.u1().scalar_mul(&t1)
+            + key.u2().scalar_mul(&t2),
+    ];
+    EquProof::<E> {
+        pi,
+        theta,
+        equ_type: EquType::MultiScalarG1,
+        rand: vec![vec![t1, t2]].into(),
+    }
+}
 
-        // (1 x m') field matrix
-        let y_rand_stmt = y_rand_trans.right_mul(&self.gamma.transpose(), is_parallel);
-        // (1 x 1) Com1 matrix
-        let y_rand_stmt_lin_x =
-            vec_to_col_vec(&Com1::<E>::batch_scalar_linear_map(scalar_xvars, crs))
-                .left_mul(&y_rand_stmt, is_parallel);
+/// Proves that the scalar `x` committed to in `B1` with randomness `r` is the discrete logarithm
+/// of a point to `base`, i.e. that it satisfies [`MSMEG2::dlog`].
+///
+/// Scalars multiplying `G2` constants are committed to `B1` (see [`MSMEG2`]), so the commitment is a
+/// [`Com1`], e.g. from [`commit_scalar_to_B1`](super::commit::commit_scalar_to_B1). As the
+/// equation is linear, the proof only depends on `base` and `r`. The proof can be checked with
+/// [`verify_dlog_G2`](crate::verifier::verify_dlog_G2).
+#[allow(non_snake_case)]
+pub fn prove_dlog_G2<CR, E>(
+    base: &E::G2Affine,
+    r: &E::ScalarField,
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> EquProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    // (2 x 1) field matrix T
+    let (t1, t2) = (E::ScalarField::rand(rng), E::ScalarField::rand(rng));
+    // π := r i_2(base) - T^T v, θ := T u_1
+    let pi = vec![
+        Com2::<E>::linear_map(base).scalar_mul(r)
+            - key.v1().scalar_mul(&t1)
+            - key.v2().scalar_mul(&t2),
+    ];
+    let theta = vec![key.u1().scalar_mul(&t1), key.u1().scalar_mul(&t2)];
+    EquProof::<E> {
+        pi,
+        theta,
+        equ_type: EquType::MultiScalarG2,
+        rand: vec![vec![t1], vec![t2]].into(),
+    }
+}
 
-        // (1 x 1) Com1 matrix
-        let u1: Matrix<Com1<E>> = vec![vec![crs.u[0]]];
-        let pf_rand_com1 = u1.left_mul(&pf_rand, is_parallel);
+/// A proof that a committed scalar is an element of a public set, as produced by
+/// [`prove_set_membership`].
+///
+/// It consists of the commitments in `B2` to the partial products `y_k` of
+/// [`EquationSystem::set_membership`] and one proof per equation of that system.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SetMembershipProof<E: Pairing> {
+    pub partial_coms: Vec<Com2<E>>,
+    pub equ_proofs: Vec<EquProof<E>>,
+}
 
-        let theta = col_vec_to_vec(&y_rand_lin_a.add(&y_rand_stmt_lin_x).add(&pf_rand_com1));
-        assert_eq!(theta.len(), 1);
+/// Proves that the scalar `scalar_xvar`, committed to in `B1` with randomness `r` (e.g. by
+/// [`commit_scalar_to_B1`](super::commit::commit_scalar_to_B1)), is an element of the public
+/// `set`, without revealing which one.
+///
+/// The proof is for the quadratic equations [`EquationSystem::set_membership`], so its size is
+/// linear in the number of distinct elements of `set`. Returns `None` if `scalar_xvar` is not in
+/// `set`. The proof can be checked with
+/// [`verify_set_membership`](crate::verifier::verify_set_membership).
+pub fn prove_set_membership<CR, E>(
+    scalar_xvar: &E::ScalarField,
+    r: &E::ScalarField,
+    set: &[E::ScalarField],
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> Option<SetMembershipProof<E>>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    if !set.contains(scalar_xvar) {
+        return None;
+    }
+    let system = EquationSystem::set_membership(set);
+
+    // y_k = prod_{i <= k} (x - s_i) over the elements in the order of the system
+    let partial_products: Vec<E::ScalarField> = distinct_elements(set)
+        .iter()
+        .scan(E::ScalarField::one(), |prod, s| {
+            *prod *= *scalar_xvar - s;
+            Some(*prod)
+        })
+        .collect();
+    let witness = SystemWitness {
+        xvars: Vec::new(),
+        yvars: Vec::new(),
+        scalar_xvars: vec![*scalar_xvar],
+        scalar_yvars: partial_products,
+    };
+    let commitments = SystemCommitments {
+        xcoms: batch_commit_G1(&[], key, rng),
+        ycoms: batch_commit_G2(&[], key, rng),
+        scalar_xcoms: Commit1 {
+            coms: vec![commit_scalar_to_B1_with_randomness(scalar_xvar, key, *r)],
+            rand: vec![vec![*r]].into(),
+        },
+        scalar_ycoms: batch_commit_scalar_to_B2(&witness.scalar_yvars, key, rng),
+    };
+    let proof = prove_with_commitments(&system, &witness, &commitments, key, rng);
+
+    Some(SetMembershipProof {
+        partial_coms: proof.scalar_ycoms,
+        equ_proofs: proof.equ_proofs,
+    })
+}
 
-        EquProof::<E> {
-            pi,
-            theta,
-            equ_type: EquType::Quadratic,
-            rand: pf_rand,
+/// A zero-knowledge proof that a pairing-product equation holds for a target given as a product
+/// of pairings, as produced by [`prove_ppe_zk`] or [`simulate_ppe`].
+///
+/// It consists of the commitments to the `X` and `Y` variables of the equation, the commitments
+/// in `target_coms` to the variables `Z_k` of [`EquationSystem::zero_knowledge_ppe`] and one
+/// proof per equation of that system. The commitment to `δ` is the fixed `ι_2'(1)`, which the
+/// verifier computes itself, so it is not part of the proof.
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct ZkPpeProof<E: Pairing> {
+    pub xcoms: Vec<Com1<E>>,
+    pub ycoms: Vec<Com2<E>>,
+    pub target_coms: Vec<Com1<E>>,
+    pub equ_proofs: Vec<EquProof<E>>,
+}
+
+// Proves the equations of `EquationSystem::zero_knowledge_ppe` for the variables `X` followed by
+// the `Z_k` in `xvars`, `Y` in `yvars` and `δ`, committed to as `ι_2'(1)` with the randomness `s`.
+#[allow(clippy::too_many_arguments)]
+fn prove_zero_knowledge_system<CR, E>(
+    equ: &PPE<E>,
+    target: &[(E::G1Affine, E::G2Affine)],
+    xvars: Vec<E::G1Affine>,
+    yvars: Vec<E::G2Affine>,
+    delta: E::ScalarField,
+    s: E::ScalarField,
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> ZkPpeProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let system = EquationSystem::zero_knowledge_ppe(equ, target);
+    let witness = SystemWitness {
+        xvars,
+        yvars,
+        scalar_xvars: Vec::new(),
+        scalar_yvars: vec![delta],
+    };
+    let commitments = SystemCommitments {
+        xcoms: batch_commit_G1(&witness.xvars, key, rng),
+        ycoms: batch_commit_G2(&witness.yvars, key, rng),
+        scalar_xcoms: batch_commit_scalar_to_B1(&[], key, rng),
+        scalar_ycoms: Commit2 {
+            coms: vec![commit_scalar_to_B2_with_randomness(&delta, key, s)],
+            rand: vec![vec![s]].into(),
+        },
+    };
+    let mut proof = prove_with_commitments(&system, &witness, &commitments, key, rng);
+
+    let target_coms = proof.xcoms.split_off(equ.b_consts.len());
+    ZkPpeProof {
+        xcoms: proof.xcoms,
+        ycoms: proof.ycoms,
+        target_coms,
+        equ_proofs: proof.equ_proofs,
+    }
+}
+
+/// Proves in zero-knowledge that the variables `xvars` and `yvars` satisfy the pairing-product
+/// equation `equ`, whose target is the product of the pairings of `target`, e.g. the pairs a
+/// [`GtTarget`](crate::statement::GtTarget) is built from.
+///
+/// Proofs by [`prove_ppe`] are only witness-indistinguishable if the target is not the identity.
+/// This proves the equations of [`EquationSystem::zero_knowledge_ppe`] for `δ = 1` instead, which
+/// can also be [simulated](simulate_ppe) without a witness, so the proof reveals nothing but
+/// that the equation holds under a hiding CRS. Returns `None` if the target of `equ` is not the
+/// product of the pairings of `target`. The proof can be checked with
+/// [`verify_ppe_zk`](crate::verifier::verify_ppe_zk).
+pub fn prove_ppe_zk<CR, E>(
+    equ: &PPE<E>,
+    target: &[(E::G1Affine, E::G2Affine)],
+    xvars: &[E::G1Affine],
+    yvars: &[E::G2Affine],
+    key: &CRS<E>,
+    rng: &mut CR,
+) -> Option<ZkPpeProof<E>>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    if GtTarget::from_pairs(target).into_inner() != equ.target {
+        return None;
+    }
+    // Z_k = P_k, and δ = 1 is committed to without randomness
+    let xvars = xvars
+        .iter()
+        .copied()
+        .chain(target.iter().map(|(p, _)| *p))
+        .collect();
+    Some(prove_zero_knowledge_system(
+        equ,
+        target,
+        xvars,
+        yvars.to_vec(),
+        E::ScalarField::one(),
+        E::ScalarField::zero(),
+        key,
+        rng,
+    ))
+}
+
+/// Simulates a zero-knowledge proof as made by [`prove_ppe_zk`] for the pairing-product equation
+/// `equ` and the pairs `target`, without knowing a witness.
+///
+/// With the trapdoor `t_2` of the hiding CRS, `ι_2'(1) = t_2 v_1` is also a commitment to `δ = 0`,
+/// for which setting every variable to the identity satisfies all equations of
+/// [`EquationSystem::zero_knowledge_ppe`]. The result is an honest proof for this witness: the
+/// commitments are distributed exactly like commitments to a real witness and, by
+/// witness-indistinguishability, so are the proofs. Returns `None` if the target of `equ` is not
+/// the product of the pairings of `target`, or if `trapdoor` is not the trapdoor of `key`.
+pub fn simulate_ppe<CR, E>(
+    equ: &PPE<E>,
+    target: &[(E::G1Affine, E::G2Affine)],
+    trapdoor: &SimulationTrapdoor<E>,
+    key: &HidingCRS<E>,
+    rng: &mut CR,
+) -> Option<ZkPpeProof<E>>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    if GtTarget::from_pairs(target).into_inner() != equ.target || !trapdoor.is_trapdoor_of(key) {
+        return None;
+    }
+    let xvars = vec![E::G1Affine::zero(); equ.b_consts.len() + target.len()];
+    let yvars = vec![E::G2Affine::zero(); equ.a_consts.len()];
+    Some(prove_zero_knowledge_system(
+        equ,
+        target,
+        xvars,
+        yvars,
+        E::ScalarField::zero(),
+        trapdoor.t2,
+        key,
+        rng,
+    ))
+}
+
+/// The variables of an [`EquationSystem`], see there for which equations use which of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemWitness<E: Pairing> {
+    pub xvars: Vec<E::G1Affine>,
+    pub yvars: Vec<E::G2Affine>,
+    pub scalar_xvars: Vec<E::ScalarField>,
+    pub scalar_yvars: Vec<E::ScalarField>,
+}
+
+/// Groups the variables `(X, Y, x, y)`, i.e. the elements of `G1` and `G2` and the scalars
+/// committed to in `B1` and in `B2`, in this order.
+impl<E: Pairing>
+    From<(
+        Vec<E::G1Affine>,
+        Vec<E::G2Affine>,
+        Vec<E::ScalarField>,
+        Vec<E::ScalarField>,
+    )> for SystemWitness<E>
+{
+    fn from(
+        (xvars, yvars, scalar_xvars, scalar_yvars): (
+            Vec<E::G1Affine>,
+            Vec<E::G2Affine>,
+            Vec<E::ScalarField>,
+            Vec<E::ScalarField>,
+        ),
+    ) -> Self {
+        Self {
+            xvars,
+            yvars,
+            scalar_xvars,
+            scalar_yvars,
         }
     }
 }
 
+impl<E: Pairing> SystemWitness<E> {
+    /// Commits to all variables in one call, see [`commit_witness`].
+    ///
+    /// The commitments and openings are those of [`batch_commit_G1`], [`batch_commit_G2`],
+    /// [`batch_commit_scalar_to_B1`] and [`batch_commit_scalar_to_B2`] called in this order.
+    pub fn commit_all<CR: Rng>(&self, key: &CRS<E>, rng: &mut CR) -> SystemCommitments<E> {
+        commit_witness(self, key, rng)
+    }
+}
+
+/// The commitments to all variables of an [`EquationSystem`] together with one proof per
+/// equation, in the order of [`EquationSystem::equations`].
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct SystemProof<E: Pairing> {
+    pub xcoms: Vec<Com1<E>>,
+    pub ycoms: Vec<Com2<E>>,
+    pub scalar_xcoms: Vec<Com1<E>>,
+    pub scalar_ycoms: Vec<Com2<E>>,
+    pub equ_proofs: Vec<EquProof<E>>,
+}
+
+/// The commitments to all variables of a [`SystemWitness`] together with their openings, as
+/// returned by [`commit_witness`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemCommitments<E: Pairing> {
+    pub xcoms: Commit1<E>,
+    pub ycoms: Commit2<E>,
+    pub scalar_xcoms: Commit1<E>,
+    pub scalar_ycoms: Commit2<E>,
+}
+
+/// Commits to every variable in `witness`, which is the first phase of [`prove_system`].
+///
+/// The commitments can be published before the equations are fixed, and later proven against
+/// with [`prove_with_commitments`] for any system over these variables.
+pub fn commit_witness<CR, E>(
+    witness: &SystemWitness<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> SystemCommitments<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    SystemCommitments {
+        xcoms: batch_commit_G1(&witness.xvars, crs, rng),
+        ycoms: batch_commit_G2(&witness.yvars, crs, rng),
+        scalar_xcoms: batch_commit_scalar_to_B1(&witness.scalar_xvars, crs, rng),
+        scalar_ycoms: batch_commit_scalar_to_B2(&witness.scalar_yvars, crs, rng),
+    }
+}
+
+/// Proves every equation of `system` against the commitments to `witness` from
+/// [`commit_witness`], which is the second phase of [`prove_system`].
+pub fn prove_with_commitments<CR, E>(
+    system: &EquationSystem<E>,
+    witness: &SystemWitness<E>,
+    commitments: &SystemCommitments<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> SystemProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let SystemCommitments {
+        xcoms,
+        ycoms,
+        scalar_xcoms,
+        scalar_ycoms,
+    } = commitments;
+
+    let equ_proofs = system
+        .equations
+        .iter()
+        .map(|equ| match equ {
+            SystemEquation::PairingProduct(equ) => {
+                equ.prove(&witness.xvars, &witness.yvars, xcoms, ycoms, crs, rng)
+            }
+            SystemEquation::MultiScalarG1(equ) => equ.prove(
+                &witness.xvars,
+                &witness.scalar_yvars,
+                xcoms,
+                scalar_ycoms,
+                crs,
+                rng,
+            ),
+            SystemEquation::MultiScalarG2(equ) => equ.prove(
+                &witness.scalar_xvars,
+                &witness.yvars,
+                scalar_xcoms,
+                ycoms,
+                crs,
+                rng,
+            ),
+            SystemEquation::Quadratic(equ) => equ.prove(
+                &witness.scalar_xvars,
+                &witness.scalar_yvars,
+                scalar_xcoms,
+                scalar_ycoms,
+                crs,
+                rng,
+            ),
+        })
+        .collect();
+
+    SystemProof {
+        xcoms: xcoms.coms.clone(),
+        ycoms: ycoms.coms.clone(),
+        scalar_xcoms: scalar_xcoms.coms.clone(),
+        scalar_ycoms: scalar_ycoms.coms.clone(),
+        equ_proofs,
+    }
+}
+
+/// Commits to the variables in `witness` once and proves every equation of `system` against
+/// these shared commitments.
+///
+/// This runs [`commit_witness`] followed by [`prove_with_commitments`]. The proof can be
+/// checked with [`verify_system`](crate::verifier::verify_system).
+pub fn prove_system<CR, E>(
+    system: &EquationSystem<E>,
+    witness: &SystemWitness<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> SystemProof<E>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    let commitments = commit_witness(witness, crs, rng);
+    prove_with_commitments(system, witness, &commitments, crs, rng)
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(non_snake_case)]
@@ -534,8 +1839,43 @@ mod tests {
         assert_eq!(proof.equ_type, EquType::PairingProduct);
     }
 
+    #[test]
+    fn test_PPE_proof_is_bound_to_CRS_fingerprint() {
+        use crate::verifier::{verify_ppe_fingerprinted, VerificationError};
+        use sha2::Sha256;
+
+        let mut rng = test_rng();
+        let crs_a = CRS::<F>::generate_crs(&mut rng);
+        let crs_b = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, P_2) * e(-P_1, Y_1) = 1 with X_1 = x P_1 and Y_1 = x P_2
+        let x = Fr::rand(&mut rng);
+        let xvars = vec![crs_a.g1_gen.mul(x).into_affine()];
+        let yvars = vec![crs_a.g2_gen.mul(x).into_affine()];
+        let equ = PPE::<F>::equality_G1_G2(&crs_a);
+        let xcoms = batch_commit_G1(&xvars, &crs_a, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs_a, &mut rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs_a, &mut rng)
+            .with_crs_fingerprint::<Sha256>(&crs_a);
+        assert_eq!(proof.crs_fingerprint, crs_a.fingerprint::<Sha256>());
+
+        let verify = |proof: &FingerprintedProof<F>, crs: &CRS<F>| {
+            verify_ppe_fingerprinted::<Sha256, F>(&equ, &xcoms.coms, &ycoms.coms, proof, crs)
+        };
+        assert_eq!(verify(&proof, &crs_a), Ok(()));
+        assert_eq!(verify(&proof, &crs_b), Err(VerificationError::CRSMismatch));
+
+        // Claiming the verifier's CRS does not make the proof verify under it
+        let relabeled = proof.proof.clone().with_crs_fingerprint::<Sha256>(&crs_b);
+        assert_eq!(
+            verify(&relabeled, &crs_b),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+    }
+
     #[test]
     fn test_PPE_cproof_is_commit_and_prove() {
+        #[cfg(feature = "std")]
         std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
         let mut rng = test_rng();
         let mut rng2 = test_rng();
@@ -624,6 +1964,128 @@ mod tests {
         assert_eq!(proof, proof_de);
     }
 
+    #[test]
+    fn test_PPE_cached_proof_is_identical() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let xvars = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let yvars = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+        let equ = PPE::<F> {
+            a_consts: vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()],
+            b_consts: vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()],
+            gamma: vec![vec![Fr::one()]],
+            target: GT::rand(&mut rng),
+        };
+        let cache = LinearMapCache::for_ppe(&equ);
+
+        // Both proofs draw the same proof randomness T
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut test_rng());
+        for _ in 0..2 {
+            let cached = prove_ppe_cached(
+                &equ,
+                &cache,
+                &xvars,
+                &yvars,
+                &xcoms,
+                &ycoms,
+                &crs,
+                &mut test_rng(),
+            );
+            assert_eq!(cached, Ok(proof.clone()));
+        }
+
+        // The cache is only valid for the equation it was computed for
+        let mut other_equ = equ.clone();
+        other_equ.a_consts[0] = crs.g1_gen;
+        let other = prove_ppe_cached(
+            &other_equ, &cache, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng,
+        );
+        assert_eq!(other, Err(ProverError::CacheMismatch));
+    }
+
+    #[test]
+    fn test_quadratic_cached_proof_is_identical() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let scalar_xvars = vec![Fr::rand(&mut rng), Fr::rand(&mut rng)];
+        let scalar_yvars = vec![Fr::rand(&mut rng)];
+        let xcoms: Commit1<F> = batch_commit_scalar_to_B1(&scalar_xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_scalar_to_B2(&scalar_yvars, &crs, &mut rng);
+        let equ = QuadEqu::<F> {
+            a_consts: vec![Fr::rand(&mut rng)],
+            b_consts: vec![Fr::rand(&mut rng), Fr::rand(&mut rng)],
+            gamma: vec![vec![Fr::one()], vec![Fr::zero()]],
+            target: Fr::rand(&mut rng),
+        };
+        let cache = LinearMapCache::for_quad(&equ, &crs);
+
+        let proof = prove_quad(
+            &equ,
+            &scalar_xvars,
+            &scalar_yvars,
+            &xcoms,
+            &ycoms,
+            &crs,
+            &mut test_rng(),
+        );
+        let cached = prove_quad_cached(
+            &equ,
+            &cache,
+            &scalar_xvars,
+            &scalar_yvars,
+            &xcoms,
+            &ycoms,
+            &crs,
+            &mut test_rng(),
+        );
+        assert_eq!(cached, Ok(proof));
+
+        // ... and for the CRS it was computed under
+        let other_crs = CRS::<F>::generate_crs(&mut rng);
+        let other = prove_quad_cached(
+            &equ,
+            &cache,
+            &scalar_xvars,
+            &scalar_yvars,
+            &xcoms,
+            &ycoms,
+            &other_crs,
+            &mut rng,
+        );
+        assert_eq!(other, Err(ProverError::CacheMismatch));
+        let other_equ = QuadEqu::<F> {
+            b_consts: vec![Fr::one(), Fr::one()],
+            ..equ.clone()
+        };
+        let other = prove_quad_cached(
+            &other_equ,
+            &cache,
+            &scalar_xvars,
+            &scalar_yvars,
+            &xcoms,
+            &ycoms,
+            &crs,
+            &mut rng,
+        );
+        assert_eq!(other, Err(ProverError::CacheMismatch));
+        let ppe_cache = LinearMapCache::for_ppe(&PPE::<F>::tautology(2, 1));
+        let other = prove_quad_cached(
+            &equ,
+            &ppe_cache,
+            &scalar_xvars,
+            &scalar_yvars,
+            &xcoms,
+            &ycoms,
+            &crs,
+            &mut rng,
+        );
+        assert_eq!(other, Err(ProverError::CacheMismatch));
+    }
+
     #[test]
     fn test_MSMEG1_proof_type() {
         let mut rng = test_rng();
@@ -651,6 +2113,7 @@ mod tests {
 
     #[test]
     fn test_MSMEG1_cproof_is_commit_and_prove() {
+        #[cfg(feature = "std")]
         std::env::set_var("DETERMINISTIC_TEST_RNG", "1");
         let mut rng = test_rng();
         let mut rng2 = test_rng();
@@ -5,7 +5,7 @@
 //! - `A` and `B` are vectors representing public constants in the equation,
 //! - `X` and `Y` are vectors representing private variables in the equation (introduced on prove),
 //! - `Γ` is a matrix of public [scalar](ark_ec::Pairing::Fr) constants defining how to scalar multiply
-//!     the corresponding variables being paired together,
+//!   the corresponding variables being paired together,
 //! - `t` is a public constant representing the RHS of the equation, and
 //! - `*` is the specified pairing, applied entry-wise to the corresponding elements in each vector.
 //!
@@ -13,13 +13,13 @@
 //! and must be one of the following four types, each defined over a bilinear group:
 //!
 //! 1) **Pairing-product equation** ([`PPE`](self::PPE)):&emsp;&emsp;&emsp;&emsp;&emsp;&emsp; `(G1, G2, GT)` with
-//!     [`e`](ark_ec::Pairing::pairing)` : G1 x G2 -> GT` as the equipped pairing.
+//!    [`e`](ark_ec::Pairing::pairing)` : G1 x G2 -> GT` as the equipped pairing.
 //! 2) **Multi-scalar mult. equation in G1** ([`MSMEG1`](self::MSMEG1)):&emsp;`(G1, Fr, G1)`
-//!     with [point-scalar multiplication](ark_ec::AffineCurve::mul) as the equipped pairing.
+//!    with [point-scalar multiplication](ark_ec::AffineCurve::mul) as the equipped pairing.
 //! 3) **Multi-scalar mult. equation in G2** ([`MSMEG2`](self::MSMEG2)):&emsp;`(Fr, G2, G2)`
-//!     with [point-scalar multiplication](ark_ec::AffineCurve::mul) as the equipped pairing.
+//!    with [point-scalar multiplication](ark_ec::AffineCurve::mul) as the equipped pairing.
 //! 4) **Quadratic equation** ([`QuadEqu`](self::QuadEqu)):&emsp;&emsp;&emsp;&emsp;&emsp;&emsp;&emsp;&emsp;&ensp;`(Fr, Fr, Fr)`
-//!     with [scalar](ark_ec::Pairing::Fr) multiplication as the equipped pairing.
+//!    with [scalar](ark_ec::Pairing::Fr) multiplication as the equipped pairing.
 //!
 //! The Groth-Sahai proof system expects that **each** equation is defined with respect to the list of variables
 //! that span across **ALL** equations being proven about. For example, if one wishes to prove
@@ -32,9 +32,13 @@
 //! This API does not provide such functionality.
 
 use ark_ec::pairing::{Pairing, PairingOutput};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Valid};
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Valid};
+use ark_std::{vec::Vec, One, Zero};
 
-use crate::data_structures::Matrix;
+use crate::data_structures::{multi_pairing, Matrix};
+use crate::generator::CRS;
 use crate::prover::Provable;
 use crate::verifier::Verifiable;
 
@@ -96,6 +100,18 @@ impl CanonicalDeserialize for EquType {
     }
 }
 
+impl EquType {
+    /// The number of `(π, θ)` elements in a proof for an equation of this type.
+    pub(crate) const fn proof_dims(&self) -> (usize, usize) {
+        match self {
+            Self::PairingProduct => (2, 2),
+            Self::MultiScalarG1 => (2, 1),
+            Self::MultiScalarG2 => (1, 2),
+            Self::Quadratic => (1, 1),
+        }
+    }
+}
+
 /// A marker trait for an arbitrary Groth-Sahai [`Equation`](self::Equation).
 pub trait Equ {}
 
@@ -103,6 +119,33 @@ pub trait Equ {}
 /// the atomic unit for a Groth-Sahai [`Statement`](self::Statement).
 pub trait Equation<E: Pairing, A1, A2, AT>: Equ + Provable<E, A1, A2, AT> + Verifiable<E> {
     fn get_type(&self) -> EquType;
+
+    /// The number of [`Com1`](crate::data_structures::Com1) and [`Com2`](crate::data_structures::Com2)
+    /// commitments, i.e. the number of `X` and `Y` variables, that a proof for this equation is made against.
+    fn commitment_count(&self) -> (usize, usize);
+
+    /// Whether the witness `(xvars, yvars)` satisfies this equation, evaluated in the clear.
+    ///
+    /// Returns `false` if the witness does not match the dimensions of the equation. This is a
+    /// debugging aid for the prover: the evaluation is not constant-time in the witness.
+    fn is_satisfied(&self, xvars: &[A1], yvars: &[A2]) -> bool;
+
+    /// The exact size in bytes of a serialized [`EquProof`](crate::prover::EquProof) for this
+    /// equation, as later reported by [`CanonicalSerialize::serialized_size`].
+    ///
+    /// The commitments are not included; they add [`commitment_count`](Self::commitment_count)
+    /// `Com1` and `Com2` elements of two group elements each.
+    fn proof_size_bytes(&self, compress: Compress) -> usize {
+        // Every length prefix is serialized as a u64
+        const LEN: usize = 8;
+        let g1 = E::G1Affine::zero().serialized_size(compress);
+        let g2 = E::G2Affine::zero().serialized_size(compress);
+
+        let equ_type = self.get_type();
+        let (pi_len, theta_len) = equ_type.proof_dims();
+        // The secret proof randomness T is not serialized
+        (LEN + pi_len * 2 * g2) + (LEN + theta_len * 2 * g1) + equ_type.serialized_size(compress)
+    }
 }
 
 /// A collection of Groth-Sahai compatible bilinear [`Equations`](self::Equation).
@@ -128,6 +171,125 @@ impl<E: Pairing> Equation<E, E::G1Affine, E::G2Affine, PairingOutput<E>> for PPE
     fn get_type(&self) -> EquType {
         EquType::PairingProduct
     }
+
+    #[inline(always)]
+    fn commitment_count(&self) -> (usize, usize) {
+        (self.b_consts.len(), self.a_consts.len())
+    }
+
+    fn is_satisfied(&self, xvars: &[E::G1Affine], yvars: &[E::G2Affine]) -> bool {
+        if !witness_fits(
+            self.a_consts.len(),
+            self.b_consts.len(),
+            &self.gamma,
+            xvars.len(),
+            yvars.len(),
+        ) {
+            return false;
+        }
+        // (X * Γ Y) is evaluated as e(X_j, Σ_k γ_jk Y_k)
+        let gamma_y = E::G2::normalize_batch(
+            &self
+                .gamma
+                .iter()
+                .map(|row| row.iter().zip(yvars).map(|(g, y)| *y * g).sum())
+                .collect::<Vec<E::G2>>(),
+        );
+        let lhs = multi_pairing::<E>(
+            self.a_consts.iter().chain(xvars).chain(xvars).copied(),
+            yvars.iter().chain(&self.b_consts).chain(&gamma_y).copied(),
+        );
+        lhs == self.target
+    }
+}
+
+#[allow(non_snake_case)]
+impl<E: Pairing> PPE<E> {
+    /// Replaces the target, e.g. by a [`GtTarget`] given as a product of pairings.
+    pub fn with_target(mut self, target: impl Into<PairingOutput<E>>) -> Self {
+        self.target = target.into();
+        self
+    }
+
+    /// The linear equation `e(X_1, P_2) * e(X_2, -P_2) = 1` over two `G1` variables, which holds
+    /// iff `X_1 = X_2`. It has no `Y` variables and can be proved with
+    /// [`prove_ppe`](crate::prover::prove_ppe), or from the commitment randomness alone with
+    /// [`prove_commitment_equality`](crate::prover::prove_commitment_equality).
+    pub fn equality_G1(crs: &CRS<E>) -> Self {
+        Self {
+            a_consts: Vec::new(),
+            b_consts: vec![crs.g2_gen, (-crs.g2_gen.into_group()).into_affine()],
+            gamma: vec![Vec::new(), Vec::new()],
+            target: PairingOutput::zero(),
+        }
+    }
+
+    /// The linear equation `e(X_1, P_2) * e(-P_1, Y_1) = 1`, which holds iff `X_1 = x P_1` and
+    /// `Y_1 = x P_2` for the same scalar `x`, see
+    /// [`prove_commitment_equality_G1_G2`](crate::prover::prove_commitment_equality_G1_G2).
+    pub fn equality_G1_G2(crs: &CRS<E>) -> Self {
+        Self {
+            a_consts: vec![(-crs.g1_gen.into_group()).into_affine()],
+            b_consts: vec![crs.g2_gen],
+            gamma: vec![vec![E::ScalarField::zero()]],
+            target: PairingOutput::zero(),
+        }
+    }
+
+    /// The trivially true equation `1 = 1` over `m` `X` and `n` `Y` variables, which holds for
+    /// every witness, e.g. to pad a system to a fixed number of equations.
+    pub fn tautology(m: usize, n: usize) -> Self {
+        Self {
+            a_consts: vec![E::G1Affine::zero(); n],
+            b_consts: vec![E::G2Affine::zero(); m],
+            gamma: vec![vec![E::ScalarField::zero(); n]; m],
+            target: PairingOutput::zero(),
+        }
+    }
+
+    /// The trivially false equation `1 = e(P_1, P_2)` over `m` `X` and `n` `Y` variables, which
+    /// holds for no witness.
+    pub fn contradiction(m: usize, n: usize) -> Self {
+        Self::tautology(m, n).with_target(E::pairing(
+            E::G1Affine::generator(),
+            E::G2Affine::generator(),
+        ))
+    }
+}
+
+/// The target of a [`PPE`] given as a product of pairings `e(A_1, B_1) * ... * e(A_k, B_k)`.
+///
+/// For example, the target of `e(X_1, P_2) = e(A_1, B_1) * e(A_2, B_2)` is
+/// `GtTarget::from_pairs(&[(A_1, B_1), (A_2, B_2)])`, which converts into the `GT` element expected
+/// by [`PPE::target`] and is evaluated with a single multi-pairing.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GtTarget<E: Pairing>(pub PairingOutput<E>);
+
+impl<E: Pairing> GtTarget<E> {
+    /// Computes the product of the pairings of all `pairs`, which is the identity if there are none.
+    pub fn from_pairs(pairs: &[(E::G1Affine, E::G2Affine)]) -> Self {
+        Self(multi_pairing(
+            pairs.iter().map(|(a, _)| *a),
+            pairs.iter().map(|(_, b)| *b),
+        ))
+    }
+
+    /// Returns the target as an element of `GT`.
+    pub fn into_inner(self) -> PairingOutput<E> {
+        self.0
+    }
+}
+
+impl<E: Pairing> From<GtTarget<E>> for PairingOutput<E> {
+    fn from(target: GtTarget<E>) -> Self {
+        target.0
+    }
+}
+
+impl<E: Pairing> From<PairingOutput<E>> for GtTarget<E> {
+    fn from(target: PairingOutput<E>) -> Self {
+        Self(target)
+    }
 }
 
 /// A multi-scalar multiplication equation in [`G1`](ark_ec::Pairing::G1Affine), equipped with point-scalar multiplication as pairing.
@@ -149,6 +311,51 @@ impl<E: Pairing> Equation<E, E::G1Affine, E::ScalarField, E::G1Affine> for MSMEG
     fn get_type(&self) -> EquType {
         EquType::MultiScalarG1
     }
+
+    #[inline(always)]
+    fn commitment_count(&self) -> (usize, usize) {
+        (self.b_consts.len(), self.a_consts.len())
+    }
+
+    fn is_satisfied(&self, xvars: &[E::G1Affine], yvars: &[E::ScalarField]) -> bool {
+        if !witness_fits(
+            self.a_consts.len(),
+            self.b_consts.len(),
+            &self.gamma,
+            xvars.len(),
+            yvars.len(),
+        ) {
+            return false;
+        }
+        let lhs: E::G1 = self
+            .a_consts
+            .iter()
+            .zip(yvars)
+            .chain(xvars.iter().zip(&self.b_consts))
+            .map(|(p, s)| *p * s)
+            .chain(
+                xvars
+                    .iter()
+                    .zip(&self.gamma)
+                    .map(|(x, row)| *x * gamma_row_dot(row, yvars)),
+            )
+            .sum();
+        lhs == self.target.into_group()
+    }
+}
+
+impl<E: Pairing> MSMEG1<E> {
+    /// The linear equation `y_1 * base = point` over a single scalar variable, which holds iff
+    /// `y_1` is the discrete logarithm of `point` to `base`, see
+    /// [`prove_dlog_G1`](crate::prover::prove_dlog_G1).
+    pub fn dlog(base: E::G1Affine, point: E::G1Affine) -> Self {
+        Self {
+            a_consts: vec![base],
+            b_consts: Vec::new(),
+            gamma: Vec::new(),
+            target: point,
+        }
+    }
 }
 
 /// A multi-scalar multiplication equation in [`G2`](ark_ec::Pairing::G2Affine), equipped with point-scalar multiplication as pairing.
@@ -169,6 +376,50 @@ impl<E: Pairing> Equation<E, E::ScalarField, E::G2Affine, E::G2Affine> for MSMEG
     fn get_type(&self) -> EquType {
         EquType::MultiScalarG2
     }
+
+    #[inline(always)]
+    fn commitment_count(&self) -> (usize, usize) {
+        (self.b_consts.len(), self.a_consts.len())
+    }
+
+    fn is_satisfied(&self, xvars: &[E::ScalarField], yvars: &[E::G2Affine]) -> bool {
+        if !witness_fits(
+            self.a_consts.len(),
+            self.b_consts.len(),
+            &self.gamma,
+            xvars.len(),
+            yvars.len(),
+        ) {
+            return false;
+        }
+        let lhs: E::G2 = yvars
+            .iter()
+            .zip(&self.a_consts)
+            .chain(self.b_consts.iter().zip(xvars))
+            .map(|(p, s)| *p * s)
+            .chain(
+                xvars
+                    .iter()
+                    .zip(&self.gamma)
+                    .flat_map(|(x, row)| row.iter().zip(yvars).map(move |(g, y)| *y * (*x * g))),
+            )
+            .sum();
+        lhs == self.target.into_group()
+    }
+}
+
+impl<E: Pairing> MSMEG2<E> {
+    /// The linear equation `x_1 * base = point` over a single scalar variable, which holds iff
+    /// `x_1` is the discrete logarithm of `point` to `base`, see
+    /// [`prove_dlog_G2`](crate::prover::prove_dlog_G2).
+    pub fn dlog(base: E::G2Affine, point: E::G2Affine) -> Self {
+        Self {
+            a_consts: Vec::new(),
+            b_consts: vec![base],
+            gamma: vec![Vec::new()],
+            target: point,
+        }
+    }
 }
 
 /// A quadratic equation in the [scalar field](ark_ec::Pairing::Fr), equipped with field multiplication as pairing.
@@ -189,6 +440,240 @@ impl<E: Pairing> Equation<E, E::ScalarField, E::ScalarField, E::ScalarField> for
     fn get_type(&self) -> EquType {
         EquType::Quadratic
     }
+
+    #[inline(always)]
+    fn commitment_count(&self) -> (usize, usize) {
+        (self.b_consts.len(), self.a_consts.len())
+    }
+
+    fn is_satisfied(&self, xvars: &[E::ScalarField], yvars: &[E::ScalarField]) -> bool {
+        if !witness_fits(
+            self.a_consts.len(),
+            self.b_consts.len(),
+            &self.gamma,
+            xvars.len(),
+            yvars.len(),
+        ) {
+            return false;
+        }
+        let lhs: E::ScalarField = self
+            .a_consts
+            .iter()
+            .zip(yvars)
+            .chain(self.b_consts.iter().zip(xvars))
+            .map(|(c, v)| *c * v)
+            .chain(
+                xvars
+                    .iter()
+                    .zip(&self.gamma)
+                    .map(|(x, row)| *x * gamma_row_dot(row, yvars)),
+            )
+            .sum();
+        lhs == self.target
+    }
+}
+
+/// A single equation of an [`EquationSystem`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SystemEquation<E: Pairing> {
+    PairingProduct(PPE<E>),
+    MultiScalarG1(MSMEG1<E>),
+    MultiScalarG2(MSMEG2<E>),
+    Quadratic(QuadEqu<E>),
+}
+
+impl<E: Pairing> SystemEquation<E> {
+    pub fn get_type(&self) -> EquType {
+        match self {
+            Self::PairingProduct(equ) => equ.get_type(),
+            Self::MultiScalarG1(equ) => equ.get_type(),
+            Self::MultiScalarG2(equ) => equ.get_type(),
+            Self::Quadratic(equ) => equ.get_type(),
+        }
+    }
+}
+
+impl<E: Pairing> From<PPE<E>> for SystemEquation<E> {
+    fn from(equ: PPE<E>) -> Self {
+        Self::PairingProduct(equ)
+    }
+}
+
+impl<E: Pairing> From<MSMEG1<E>> for SystemEquation<E> {
+    fn from(equ: MSMEG1<E>) -> Self {
+        Self::MultiScalarG1(equ)
+    }
+}
+
+impl<E: Pairing> From<MSMEG2<E>> for SystemEquation<E> {
+    fn from(equ: MSMEG2<E>) -> Self {
+        Self::MultiScalarG2(equ)
+    }
+}
+
+impl<E: Pairing> From<QuadEqu<E>> for SystemEquation<E> {
+    fn from(equ: QuadEqu<E>) -> Self {
+        Self::Quadratic(equ)
+    }
+}
+
+/// A conjunction of equations of any type over one shared set of variables, proven with
+/// [`prove_system`](crate::prover::prove_system) and verified with
+/// [`verify_system`](crate::verifier::verify_system).
+///
+/// The variables are `X` in `G1`, `Y` in `G2`, and scalars `x` and `y` committed to `B1` and `B2`
+/// respectively. Pairing-product equations are over `(X, Y)`, multi-scalar multiplication
+/// equations in `G1` over `(X, y)`, those in `G2` over `(x, Y)` and quadratic equations over
+/// `(x, y)`. As for a [`Statement`], the constants of every equation must cover all variables of
+/// its two types, so that each variable is committed to once and shared by all equations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EquationSystem<E: Pairing> {
+    pub equations: Vec<SystemEquation<E>>,
+}
+
+impl<E: Pairing> EquationSystem<E> {
+    /// Creates an empty system.
+    pub fn new() -> Self {
+        Self {
+            equations: Vec::new(),
+        }
+    }
+
+    /// Adds `equ` to the conjunction.
+    pub fn push(&mut self, equ: impl Into<SystemEquation<E>>) -> &mut Self {
+        self.equations.push(equ.into());
+        self
+    }
+
+    /// The quadratic equations stating that the scalar `x_1` is an element of the public `set`,
+    /// i.e. that `prod (x_1 - s_i) = 0`, see
+    /// [`prove_set_membership`](crate::prover::prove_set_membership).
+    ///
+    /// Quadratic equations are of degree 2, so the product is built up in the `n` variables
+    /// `y_k = prod_{i <= k} (x_1 - s_i)` over the `n` distinct elements of `set` in ascending
+    /// order: `x_1 - y_1 = s_1`, `x_1 y_{k-1} - s_k y_{k-1} - y_k = 0` for `1 < k <= n` and
+    /// `y_n = 0`. Duplicates in `set` are removed, so they do not change the statement.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `set` is empty, as no scalar is an element of it.
+    pub fn set_membership(set: &[E::ScalarField]) -> Self {
+        let set = distinct_elements(set);
+        assert!(!set.is_empty(), "set must not be empty");
+        let n = set.len();
+        let one = E::ScalarField::one();
+
+        let equ = |x_coeff, y_coeffs: &[(usize, E::ScalarField)], xy_index, target| {
+            let mut a_consts = vec![E::ScalarField::zero(); n];
+            for (k, coeff) in y_coeffs {
+                a_consts[*k] = *coeff;
+            }
+            let mut gamma = vec![vec![E::ScalarField::zero(); n]];
+            if let Some(k) = xy_index {
+                gamma[0][k] = one;
+            }
+            QuadEqu::<E> {
+                a_consts,
+                b_consts: vec![x_coeff],
+                gamma,
+                target,
+            }
+        };
+
+        let mut system = Self::new();
+        system.push(equ(one, &[(0, -one)], None, set[0]));
+        for (k, s_k) in set.iter().enumerate().skip(1) {
+            system.push(equ(
+                E::ScalarField::zero(),
+                &[(k - 1, -*s_k), (k, -one)],
+                Some(k - 1),
+                E::ScalarField::zero(),
+            ));
+        }
+        system.push(equ(
+            E::ScalarField::zero(),
+            &[(n - 1, one)],
+            None,
+            E::ScalarField::zero(),
+        ));
+        system
+    }
+
+    /// The equations of a zero-knowledge proof that the pairing-product equation `equ` holds
+    /// for the target `prod e(P_k, Q_k)` over the pairs `(P_k, Q_k)` of `target`, see
+    /// [`prove_ppe_zk`](crate::prover::prove_ppe_zk). The target of `equ` itself is ignored.
+    ///
+    /// Following Groth and Sahai, the target is moved into the equation with a new `G1` variable
+    /// `Z_k` per pair: the first equation is `equ` times `prod e(Z_k, -Q_k)` with the identity as
+    /// target, and it is followed by the multi-scalar multiplication equations
+    /// `δ P_k - Z_k = 0` in the scalar variable `δ` (committed to in `B2`). For `δ = 1` they
+    /// force `Z_k = P_k`, while `δ = 0` and `Z_k = 0` satisfy every equation. The `X` variables
+    /// are those of `equ` followed by the `Z_k`, and the `Y` variables are those of `equ`.
+    pub fn zero_knowledge_ppe(equ: &PPE<E>, target: &[(E::G1Affine, E::G2Affine)]) -> Self {
+        let m = equ.b_consts.len() + target.len();
+        let n = equ.a_consts.len();
+
+        let mut gamma = equ.gamma.clone();
+        gamma.resize(m, vec![E::ScalarField::zero(); n]);
+        let mut system = Self::new();
+        system.push(PPE::<E> {
+            a_consts: equ.a_consts.clone(),
+            b_consts: equ
+                .b_consts
+                .iter()
+                .copied()
+                .chain(target.iter().map(|(_, q)| (-q.into_group()).into_affine()))
+                .collect(),
+            gamma,
+            target: PairingOutput::zero(),
+        });
+        for (k, (p, _)) in target.iter().enumerate() {
+            let mut b_consts = vec![E::ScalarField::zero(); m];
+            b_consts[equ.b_consts.len() + k] = -E::ScalarField::one();
+            system.push(MSMEG1::<E> {
+                a_consts: vec![*p],
+                b_consts,
+                gamma: vec![vec![E::ScalarField::zero()]; m],
+                target: E::G1Affine::zero(),
+            });
+        }
+        system
+    }
+}
+
+// Whether a witness with `x_len` X and `y_len` Y variables fits an equation with the given
+// constants: `|A| = |Y|`, `|B| = |X|` and `Γ` being `|X| x |Y|`.
+fn witness_fits<F>(
+    a_len: usize,
+    b_len: usize,
+    gamma: &Matrix<F>,
+    x_len: usize,
+    y_len: usize,
+) -> bool {
+    a_len == y_len
+        && b_len == x_len
+        && gamma.len() == x_len
+        && gamma.iter().all(|row| row.len() == y_len)
+}
+
+// Computes `Σ_k γ_k y_k` for one row of `Γ`.
+fn gamma_row_dot<F: Field>(row: &[F], yvars: &[F]) -> F {
+    row.iter().zip(yvars).map(|(g, y)| *g * y).sum()
+}
+
+// Returns the distinct elements of `set` in ascending order, which is the order in which
+// `EquationSystem::set_membership` multiplies them up.
+pub(crate) fn distinct_elements<F: Field>(set: &[F]) -> Vec<F> {
+    let mut set = set.to_vec();
+    set.sort_unstable();
+    set.dedup();
+    set
+}
+
+impl<E: Pairing> Default for EquationSystem<E> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[cfg(test)]
@@ -272,6 +757,31 @@ mod tests {
         assert_eq!(equ, equ_de);
     }
 
+    #[test]
+    fn test_GT_target_from_pairs() {
+        let mut rng = test_rng();
+        let pairs: Vec<_> = (0..3)
+            .map(|_| {
+                (
+                    <F as Pairing>::G1::rand(&mut rng).into_affine(),
+                    <F as Pairing>::G2::rand(&mut rng).into_affine(),
+                )
+            })
+            .collect();
+
+        let target = GtTarget::<F>::from_pairs(&pairs);
+        let expected = F::pairing(pairs[0].0, pairs[0].1)
+            + F::pairing(pairs[1].0, pairs[1].1)
+            + F::pairing(pairs[2].0, pairs[2].1);
+        assert_eq!(target.into_inner(), expected);
+        assert_eq!(GT::from(target), expected);
+        assert!(GtTarget::<F>::from_pairs(&[]).into_inner().is_zero());
+
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let equ = PPE::<F>::equality_G1(&crs).with_target(target);
+        assert_eq!(equ.target, expected);
+    }
+
     #[test]
     fn test_MSMEG1_equation_type() {
         let mut rng = test_rng();
@@ -389,4 +899,39 @@ mod tests {
         let equ_de = QuadEqu::<F>::deserialize_uncompressed(&u_bytes[..]).unwrap();
         assert_eq!(equ, equ_de);
     }
+
+    #[test]
+    fn test_set_membership_system() {
+        let set = [Fr::from(5u64), Fr::from(2u64), Fr::from(5u64)];
+        let system = EquationSystem::<F>::set_membership(&set);
+        // Two distinct elements, so two partial products and three equations
+        assert_eq!(system.equations.len(), 3);
+        assert_eq!(
+            distinct_elements(&set),
+            vec![Fr::from(2u64), Fr::from(5u64)]
+        );
+
+        // x = 5 satisfies all of them with y = [x - 2, (x - 2)(x - 5)] = [3, 0]
+        let (x, y) = (Fr::from(5u64), [Fr::from(3u64), Fr::zero()]);
+        for equ in &system.equations {
+            let SystemEquation::Quadratic(equ) = equ else {
+                panic!("set membership is stated with quadratic equations");
+            };
+            assert_eq!(equ.b_consts.len(), 1);
+            let lhs: Fr = equ.a_consts.iter().zip(&y).map(|(a, y)| *a * y).sum::<Fr>()
+                + equ.b_consts[0] * x
+                + equ.gamma[0]
+                    .iter()
+                    .zip(&y)
+                    .map(|(g, y)| *g * x * y)
+                    .sum::<Fr>();
+            assert_eq!(lhs, equ.target);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "set must not be empty")]
+    fn test_set_membership_of_empty_set() {
+        EquationSystem::<F>::set_membership(&[]);
+    }
 }
@@ -5,157 +5,1020 @@
 //!
 //! See the [`prover`](crate::prover) and [`statement`](crate::statement) modules for more details about the structure of the equations and their proofs.
 
-use ark_ec::pairing::Pairing;
-
-use crate::data_structures::{
-    col_vec_to_vec, vec_to_col_vec, Com1, Com2, ComT, Mat, Matrix, B1, B2, BT,
+use ark_ec::{
+    pairing::{Pairing, PairingOutput},
+    AffineRepr, CurveGroup,
 };
+use ark_ff::Field;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::{fmt, rand::Rng, vec::Vec, One, UniformRand, Zero};
+use digest::{consts::U32, Digest};
+
+use crate::data_structures::{multi_miller_loop, Com1, Com2, ComT, Matrix, B1, B2, BT};
 use crate::generator::CRS;
-use crate::prover::CProof;
-use crate::statement::{Equation, QuadEqu, MSMEG1, MSMEG2, PPE};
+use crate::prover::{
+    CProof, EquProof, FingerprintedProof, SetMembershipProof, SystemProof, ZkPpeProof,
+};
+use crate::statement::{
+    EquType, Equation, EquationSystem, GtTarget, QuadEqu, SystemEquation, MSMEG1, MSMEG2, PPE,
+};
+
+/// Describes why a proof was rejected by the verifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationError {
+    /// A commitment or proof element is not a point of the prime-order subgroup.
+    CommitmentMalformed,
+    /// The verification equation does not hold for the equation at `equation_index`.
+    PairingMismatch { equation_index: usize },
+    /// The number of commitments or proof elements does not match the shape of the equation.
+    DimensionMismatch,
+    /// The proof was generated under a CRS with a different fingerprint than the verifier's.
+    CRSMismatch,
+}
+
+impl VerificationError {
+    // Attributes a pairing mismatch to the equation at position `equation_index` of a system.
+    fn at_equation(self, equation_index: usize) -> Self {
+        match self {
+            Self::PairingMismatch { .. } => Self::PairingMismatch { equation_index },
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CommitmentMalformed => write!(f, "commitment or proof element is malformed"),
+            Self::PairingMismatch { equation_index } => {
+                write!(f, "verification of equation {} failed", equation_index)
+            }
+            Self::DimensionMismatch => {
+                write!(
+                    f,
+                    "commitments or proof do not match the equation's dimensions"
+                )
+            }
+            Self::CRSMismatch => write!(f, "proof was generated under a different CRS"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerificationError {}
 
 /// A collection of attributes containing verifier functionality for an [`Equation`](crate::statement::Equation).
 pub trait Verifiable<E: Pairing> {
     /// Verifies that a single Groth-Sahai equation is satisfied using the prover's committed `x` and `y` variables.
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool;
+    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> Result<(), VerificationError>;
 }
 
-impl<E: Pairing> Verifiable<E> for PPE<E> {
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
-        assert_eq!(com_proof.equ_proofs.len(), 1);
-        assert_eq!(self.get_type(), com_proof.equ_proofs[0].equ_type);
-        let is_parallel = true;
+// Checks that the commitments and the proof have the shape required by an equation with the
+// given constants, and that the proof is for an equation of type `equ_type`.
+pub(crate) fn check_dims<E: Pairing, A, B>(
+    a_consts: &[A],
+    b_consts: &[B],
+    gamma: &Matrix<E::ScalarField>,
+    equ_type: EquType,
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+) -> Result<(), VerificationError> {
+    if proof.equ_type != equ_type
+        || (proof.pi.len(), proof.theta.len()) != equ_type.proof_dims()
+        || xcoms.len() != b_consts.len()
+        || xcoms.len() != gamma.len()
+        || ycoms.len() != a_consts.len()
+        || gamma.iter().any(|row| row.len() != ycoms.len())
+    {
+        Err(VerificationError::DimensionMismatch)
+    } else {
+        Ok(())
+    }
+}
 
-        let lin_a_com_y = ComT::<E>::pairing_sum(
-            &Com1::<E>::batch_linear_map(&self.a_consts),
-            &com_proof.ycoms.coms,
-        );
+// Checks that every commitment and proof element is a point of the prime-order subgroup.
+fn check_well_formed<E: Pairing>(
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+) -> Result<(), VerificationError> {
+    check_points_valid(
+        xcoms.iter().chain(proof.theta.iter()),
+        ycoms.iter().chain(proof.pi.iter()),
+    )
+}
+
+// Checks that every element of the given `B1` and `B2` elements is a point of the prime-order subgroup.
+pub(crate) fn check_points_valid<'a, E: Pairing>(
+    b1: impl IntoIterator<Item = &'a Com1<E>>,
+    b2: impl IntoIterator<Item = &'a Com2<E>>,
+) -> Result<(), VerificationError> {
+    fn valid<G: AffineRepr>(p: &G) -> bool {
+        p.check().is_ok()
+    }
+    let b1_ok = b1.into_iter().all(|c| valid(&c.0) && valid(&c.1));
+    let b2_ok = b2.into_iter().all(|c| valid(&c.0) && valid(&c.1));
+    if b1_ok && b2_ok {
+        Ok(())
+    } else {
+        Err(VerificationError::CommitmentMalformed)
+    }
+}
+
+// Computes Gamma D for the commitments D of the Y variables row by row, so that it has one entry
+// per X variable even for equations without Y variables.
+pub(crate) fn stmt_com_y<E: Pairing>(
+    gamma: &Matrix<E::ScalarField>,
+    ycoms: &[Com2<E>],
+) -> Vec<Com2<E>> {
+    gamma
+        .iter()
+        .map(|row| row.iter().zip(ycoms).map(|(g, d)| *d * *g).sum())
+        .collect()
+}
+
+// Checks that a combined proof contains exactly one equation proof.
+fn single_proof<E: Pairing>(com_proof: &CProof<E>) -> Result<&EquProof<E>, VerificationError> {
+    match com_proof.equ_proofs.as_slice() {
+        [proof] => Ok(proof),
+        _ => Err(VerificationError::DimensionMismatch),
+    }
+}
 
-        let com_x_lin_b = ComT::<E>::pairing_sum(
+impl<E: Pairing> Verifiable<E> for PPE<E> {
+    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> Result<(), VerificationError> {
+        verify_ppe(
+            self,
             &com_proof.xcoms.coms,
-            &Com2::<E>::batch_linear_map(&self.b_consts),
-        );
+            &com_proof.ycoms.coms,
+            single_proof(com_proof)?,
+            crs,
+        )
+    }
+}
+
+/// Verifies a proof `(π, θ)` that the variables committed to in `xcoms` and `ycoms` satisfy the
+/// pairing-product equation `equ`.
+///
+/// Unlike [`Verifiable::verify`], this only needs the commitments themselves and not their openings.
+/// All pairings of the verification equation share a Miller loop, so only one final
+/// exponentiation is computed per entry of [`BT`](crate::data_structures::ComT). An all-public
+/// equation, without any committed variables, is checked against its target without pairings.
+pub fn verify_ppe<E: Pairing>(
+    equ: &PPE<E>,
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    check_dims(
+        &equ.a_consts,
+        &equ.b_consts,
+        &equ.gamma,
+        equ.get_type(),
+        xcoms,
+        ycoms,
+        proof,
+    )?;
+    check_well_formed(xcoms, ycoms, proof)?;
+
+    // Without committed variables, there are no pairing terms on the left-hand side, so the
+    // equation holds iff the public target is the identity, regardless of the proof
+    if xcoms.is_empty() && ycoms.is_empty() {
+        return if equ.target.is_zero() {
+            Ok(())
+        } else {
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        };
+    }
 
-        let stmt_com_y: Matrix<Com2<E>> =
-            vec_to_col_vec(&com_proof.ycoms.coms).left_mul(&self.gamma, is_parallel);
-        let com_x_stmt_com_y =
-            ComT::<E>::pairing_sum(&com_proof.xcoms.coms, &col_vec_to_vec(&stmt_com_y));
+    let (g1_terms, g2_terms) = ppe_terms(equ, xcoms, ycoms, proof, crs);
+    let lin_t = ComT::<E>::linear_map_PPE(&equ.target);
 
-        let lin_t = ComT::<E>::linear_map_PPE(&self.target);
+    if check_pairing_product(&g1_terms, &g2_terms, &lin_t) {
+        Ok(())
+    } else {
+        Err(VerificationError::PairingMismatch { equation_index: 0 })
+    }
+}
 
-        let com1_pf2 = ComT::<E>::pairing_sum(&crs.u, &com_proof.equ_proofs[0].pi);
+/// Like [`verify_ppe`], but first checks that the proof was generated under `crs`, returning
+/// [`VerificationError::CRSMismatch`] if its attached fingerprint under `D` differs from that of
+/// `crs`.
+///
+/// The fingerprint only diagnoses a CRS rotation: soundness rests on verifying under the
+/// verifier's own CRS, which [`verify_ppe`] does regardless of the fingerprint.
+pub fn verify_ppe_fingerprinted<D, E>(
+    equ: &PPE<E>,
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &FingerprintedProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError>
+where
+    D: Digest<OutputSize = U32>,
+    E: Pairing,
+{
+    if proof.crs_fingerprint != crs.fingerprint::<D>() {
+        return Err(VerificationError::CRSMismatch);
+    }
+    verify_ppe(equ, xcoms, ycoms, &proof.proof, crs)
+}
 
-        let pf1_com2 = ComT::<E>::pairing_sum(&com_proof.equ_proofs[0].theta, &crs.v);
+/// Like [`verify_ppe`], but always evaluates all four entries of the verification equation in
+/// [`BT`](crate::data_structures::ComT) and combines the comparisons without short-circuiting.
+///
+/// [`verify_ppe`] stops at the first entry that does not match, so its running time reveals how
+/// far a rejected proof got, which an adaptive prover may exploit. This version always computes
+/// the same pairings for equations of the same shape, at the cost of always paying for the
+/// full verification. The comparisons of [`GT`](ark_ec::pairing::PairingOutput) elements
+/// themselves are arkworks' and not guaranteed to be constant-time. Checks on public data (the
+/// dimensions and well-formedness of the inputs) still return early.
+///
+/// Returns the same verdict as [`verify_ppe`]. To hide which equation of a system failed, verify
+/// every equation with this function before combining their results.
+pub fn verify_ppe_ct<E: Pairing>(
+    equ: &PPE<E>,
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    check_dims(
+        &equ.a_consts,
+        &equ.b_consts,
+        &equ.gamma,
+        equ.get_type(),
+        xcoms,
+        ycoms,
+        proof,
+    )?;
+    check_well_formed(xcoms, ycoms, proof)?;
+    // As in `verify_ppe`; whether an equation has variables is public
+    if xcoms.is_empty() && ycoms.is_empty() {
+        return verify_ppe(equ, xcoms, ycoms, proof, crs);
+    }
 
-        let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
-        let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+    let (g1_terms, g2_terms) = ppe_terms(equ, xcoms, ycoms, proof, crs);
+    let lin_t = ComT::<E>::linear_map_PPE(&equ.target);
+    let lhs = ComT::<E>::pairing_sum(&g1_terms, &g2_terms);
 
-        lhs == rhs
+    let entries_match = [
+        (lhs.0, lin_t.0),
+        (lhs.1, lin_t.1),
+        (lhs.2, lin_t.2),
+        (lhs.3, lin_t.3),
+    ]
+    .iter()
+    .fold(true, |acc, (l, r)| acc & (l == r));
+    if entries_match {
+        Ok(())
+    } else {
+        Err(VerificationError::PairingMismatch { equation_index: 0 })
     }
 }
 
-impl<E: Pairing> Verifiable<E> for MSMEG1<E> {
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
-        assert_eq!(com_proof.equ_proofs.len(), 1);
-        assert_eq!(self.get_type(), com_proof.equ_proofs[0].equ_type);
-        let is_parallel = true;
+// The B1 and B2 terms whose pairing sum must equal i_T(t_T) for a proof of `equ`:
+// i_1(A) * D + C * (i_2(B) + Gamma D) - u * pi - theta * v = i_T(t_T)
+fn ppe_terms<E: Pairing>(
+    equ: &PPE<E>,
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> (Vec<Com1<E>>, Vec<Com2<E>>) {
+    let lin_b_stmt_com_y = Com2::<E>::batch_linear_map(&equ.b_consts)
+        .into_iter()
+        .zip(stmt_com_y(&equ.gamma, ycoms))
+        .map(|(b, gd)| b + gd);
 
-        let lin_a_com_y = ComT::<E>::pairing_sum(
-            &Com1::<E>::batch_linear_map(&self.a_consts),
-            &com_proof.ycoms.coms,
-        );
+    let g1_terms: Vec<Com1<E>> = Com1::<E>::batch_linear_map(&equ.a_consts)
+        .into_iter()
+        .chain(xcoms.iter().copied())
+        .chain(crs.u.iter().map(|u| -*u))
+        .chain(proof.theta.iter().map(|theta| -*theta))
+        .collect();
+    let g2_terms: Vec<Com2<E>> = ycoms
+        .iter()
+        .copied()
+        .chain(lin_b_stmt_com_y)
+        .chain(proof.pi.iter().copied())
+        .chain(crs.v.iter().copied())
+        .collect();
+    (g1_terms, g2_terms)
+}
 
-        let com_x_lin_b = ComT::<E>::pairing_sum(
-            &com_proof.xcoms.coms,
-            &Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs),
-        );
+// Checks that the entry-wise sum of pairings of `x_vec` and `y_vec` equals `target`, using a
+// single Miller loop and final exponentiation for each entry of BT rather than a final
+// exponentiation for every pairing.
+pub(crate) fn check_pairing_product<E: Pairing>(
+    x_vec: &[Com1<E>],
+    y_vec: &[Com2<E>],
+    target: &ComT<E>,
+) -> bool {
+    debug_assert_eq!(x_vec.len(), y_vec.len());
+    let entry = |i: usize, j: usize| {
+        let g1 = x_vec.iter().map(|x| if i == 0 { x.0 } else { x.1 });
+        let g2 = y_vec.iter().map(|y| if j == 0 { y.0 } else { y.1 });
+        E::final_exponentiation(multi_miller_loop(g1, g2))
+    };
 
-        let stmt_com_y: Matrix<Com2<E>> =
-            vec_to_col_vec(&com_proof.ycoms.coms).left_mul(&self.gamma, is_parallel);
-        let com_x_stmt_com_y =
-            ComT::<E>::pairing_sum(&com_proof.xcoms.coms, &col_vec_to_vec(&stmt_com_y));
+    entry(0, 0) == Some(target.0)
+        && entry(0, 1) == Some(target.1)
+        && entry(1, 0) == Some(target.2)
+        && entry(1, 1) == Some(target.3)
+}
 
-        let lin_t = ComT::<E>::linear_map_MSMEG1(&self.target, crs);
+/// Like [`verify_ppe`], but only reports whether the proof is valid.
+pub fn verify_ppe_bool<E: Pairing>(
+    equ: &PPE<E>,
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    verify_ppe(equ, xcoms, ycoms, proof, crs).is_ok()
+}
 
-        let com1_pf2 = ComT::<E>::pairing_sum(&crs.u, &com_proof.equ_proofs[0].pi);
+/// The pairing inputs of a pairing-product verification equation, blinded by [`blind_ppe`] so
+/// that the pairings can be computed by an untrusted helper with [`evaluate`](Self::evaluate).
+#[derive(Clone, Debug, PartialEq, Eq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct BlindedPairingProduct<E: Pairing> {
+    pub lhs: Vec<Com1<E>>,
+    pub rhs: Vec<Com2<E>>,
+}
 
-        let pf1_com2 = ComT::<E>::pairing(com_proof.equ_proofs[0].theta[0], crs.v[0]);
+impl<E: Pairing> BlindedPairingProduct<E> {
+    /// Computes `Σ_i F(lhs[i], rhs[i])` in [`BT`](crate::data_structures::ComT), which is all
+    /// the helper needs to do. It does not need the CRS or the statement.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lhs` and `rhs` differ in length.
+    pub fn evaluate(&self) -> ComT<E> {
+        ComT::<E>::pairing_sum(&self.lhs, &self.rhs)
+    }
+}
 
-        let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
-        let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+/// The verifier's secret state for a [`BlindedPairingProduct`], consumed by [`unblind`].
+///
+/// It only holds the value the helper's result must equal, i.e. the target of the verification
+/// equation scaled by the blinding factor `ρ` plus the secret mask, and must not be shared with
+/// the helper.
+#[derive(Clone, Debug)]
+pub struct BlindingKey<E: Pairing> {
+    expected: ComT<E>,
+}
 
-        lhs == rhs
+// Samples a uniformly random invertible scalar.
+fn rand_nonzero<CR: Rng, F: UniformRand + Zero>(rng: &mut CR) -> F {
+    loop {
+        let x = F::rand(rng);
+        if !x.is_zero() {
+            return x;
+        }
     }
 }
 
-impl<E: Pairing> Verifiable<E> for MSMEG2<E> {
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
-        assert_eq!(com_proof.equ_proofs.len(), 1);
-        assert_eq!(self.get_type(), com_proof.equ_proofs[0].equ_type);
-        let is_parallel = true;
+/// Prepares the verification of a pairing-product equation proof for an untrusted helper, which
+/// computes the pairings without learning the statement, see [`verify_ppe`] for the checks.
+///
+/// The verification equation is `Σ_i F(c_i, d_i) = ι_T(t)` for the terms `c_i` in `B1` and `d_i`
+/// in `B2`, which the verifier computes from the statement, the commitments, the proof and the
+/// CRS. For a fresh scalar `ρ` and a fresh scalar `β_i` per term, all non-zero and sampled from
+/// `rng`, the helper receives `c_i' = ρ β_i c_i` and `d_i' = β_i^-1 d_i`. By bilinearity,
+/// `Σ_i F(c_i', d_i') = ρ Σ_i F(c_i, d_i)`.
+///
+/// Three of the four entries of `ι_T(t)` are the identity, as is all of it for the common target
+/// `t = 1`, so the helper could pass any proof by returning the identity. To rule this out, the
+/// verifier adds the secret mask `m = (γ_1 P_1, γ_2 P_1)` for fresh non-zero `γ_1, γ_2` to the
+/// `B1` side of one of the terms `(-θ_k, v_k)`, chosen at random among those with `θ_k ≠ 0`, and
+/// [`unblind`] accepts the helper's result iff it is `ρ ι_T(t) + F(m, d_k')`. The verifier
+/// computes the mask term with two pairings. It is random and non-zero, and it is not the value
+/// of any subset of the pairs the helper receives, since `m` is hidden in `c_k' + m`.
+///
+/// The factors `β_i` re-randomize every pair, so that neither the constants of the statement nor
+/// the commitments are sent in the clear, and `ρ` hides the target and the value of the pairing
+/// product. The helper still learns the number of terms and which coordinates are the identity,
+/// e.g. those of the images `ι_1(a)`, `ι_2(b)` of the constants.
+///
+/// The public checks of [`verify_ppe`] on the dimensions and well-formedness of the inputs are
+/// done here, returning their error early. A proof whose `θ` is the identity, which an honest
+/// proof is only with negligible probability, leaves nowhere to hide the mask and is rejected as
+/// [`VerificationError::CommitmentMalformed`]. As in [`verify_ppe`], an equation without
+/// committed variables only checks its target, which the verifier does itself: it gets an empty
+/// request if the target is the identity and [`VerificationError::PairingMismatch`] otherwise.
+pub fn blind_ppe<CR, E>(
+    equ: &PPE<E>,
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+    rng: &mut CR,
+) -> Result<(BlindedPairingProduct<E>, BlindingKey<E>), VerificationError>
+where
+    E: Pairing,
+    CR: Rng,
+{
+    check_dims(
+        &equ.a_consts,
+        &equ.b_consts,
+        &equ.gamma,
+        equ.get_type(),
+        xcoms,
+        ycoms,
+        proof,
+    )?;
+    check_well_formed(xcoms, ycoms, proof)?;
 
-        let lin_a_com_y = ComT::<E>::pairing_sum(
-            &Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs),
-            &com_proof.ycoms.coms,
-        );
+    if xcoms.is_empty() && ycoms.is_empty() {
+        return if equ.target.is_zero() {
+            Ok((
+                BlindedPairingProduct {
+                    lhs: Vec::new(),
+                    rhs: Vec::new(),
+                },
+                BlindingKey {
+                    expected: ComT::<E>::zero(),
+                },
+            ))
+        } else {
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        };
+    }
 
-        let com_x_lin_b = ComT::<E>::pairing_sum(
-            &com_proof.xcoms.coms,
-            &Com2::<E>::batch_linear_map(&self.b_consts),
-        );
+    // The terms (-θ_k, v_k) come last
+    let (lhs, rhs) = ppe_terms(equ, xcoms, ycoms, proof, crs);
+    let candidates: Vec<usize> = (0..proof.theta.len())
+        .filter(|&k| !proof.theta[k].is_zero())
+        .map(|k| lhs.len() - proof.theta.len() + k)
+        .collect();
+    if candidates.is_empty() {
+        return Err(VerificationError::CommitmentMalformed);
+    }
+    let masked = candidates[rng.gen_range(0..candidates.len())];
+    let (gamma1, gamma2): (E::ScalarField, E::ScalarField) = (rand_nonzero(rng), rand_nonzero(rng));
+    let mask = Com1::<E>(
+        (crs.g1_gen * gamma1).into_affine(),
+        (crs.g1_gen * gamma2).into_affine(),
+    );
 
-        let stmt_com_y: Matrix<Com2<E>> =
-            vec_to_col_vec(&com_proof.ycoms.coms).left_mul(&self.gamma, is_parallel);
-        let com_x_stmt_com_y =
-            ComT::<E>::pairing_sum(&com_proof.xcoms.coms, &col_vec_to_vec(&stmt_com_y));
+    let rho: E::ScalarField = rand_nonzero(rng);
+    let (lhs, rhs): (Vec<Com1<E>>, Vec<Com2<E>>) = lhs
+        .iter()
+        .zip(&rhs)
+        .enumerate()
+        .map(|(i, (c, d))| {
+            let beta: E::ScalarField = rand_nonzero(rng);
+            let c = c.scalar_mul(&(rho * beta));
+            (
+                if i == masked { c + mask } else { c },
+                d.scalar_mul(&beta.inverse().unwrap()),
+            )
+        })
+        .unzip();
 
-        let lin_t = ComT::<E>::linear_map_MSMEG2(&self.target, crs);
+    // F(m, d) = (γ_1 e(P_1, d_1), γ_1 e(P_1, d_2), γ_2 e(P_1, d_1), γ_2 e(P_1, d_2))
+    let (e1, e2) = (
+        E::pairing(crs.g1_gen, rhs[masked].0),
+        E::pairing(crs.g1_gen, rhs[masked].1),
+    );
+    let mask_term = ComT::<E>(e1 * gamma1, e2 * gamma1, e1 * gamma2, e2 * gamma2);
 
-        let com1_pf2 = ComT::<E>::pairing(crs.u[0], com_proof.equ_proofs[0].pi[0]);
+    Ok((
+        BlindedPairingProduct { lhs, rhs },
+        BlindingKey {
+            expected: ComT::<E>::linear_map_PPE(&equ.target) * rho + mask_term,
+        },
+    ))
+}
+
+/// Recovers the verdict of [`verify_ppe`] from the `result` of
+/// [`BlindedPairingProduct::evaluate`] returned by the helper, see [`blind_ppe`].
+pub fn unblind<E: Pairing>(key: BlindingKey<E>, result: &ComT<E>) -> Result<(), VerificationError> {
+    check_equal(*result, key.expected)
+}
 
-        let pf1_com2 = ComT::<E>::pairing_sum(&com_proof.equ_proofs[0].theta, &crs.v);
+/// Verifies a system of pairing-product equations over the same committed variables, where
+/// `proofs[i]` is the proof for `equs[i]`.
+///
+/// A failing verification equation is reported with its position in `equs`.
+pub fn verify_ppe_system<E: Pairing>(
+    equs: &[PPE<E>],
+    xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proofs: &[EquProof<E>],
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    if equs.len() != proofs.len() {
+        return Err(VerificationError::DimensionMismatch);
+    }
+    for (i, (equ, proof)) in equs.iter().zip(proofs).enumerate() {
+        verify_ppe(equ, xcoms, ycoms, proof, crs).map_err(|err| err.at_equation(i))?;
+    }
+    Ok(())
+}
 
-        let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
-        let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+/// Verifies a proof for every equation of `system` against the shared commitments in `proof`,
+/// as produced by [`prove_system`](crate::prover::prove_system).
+///
+/// Use [`verify_system_detailed`] to find out which equations failed.
+pub fn verify_system<E: Pairing>(
+    system: &EquationSystem<E>,
+    proof: &SystemProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    verify_system_detailed(system, proof, crs)
+        .iter()
+        .all(|res| res.is_ok())
+}
 
-        lhs == rhs
+/// Like [`verify_system`], but returns the result of verifying each equation, in the order of
+/// `system.equations`.
+///
+/// A failing verification equation is reported with its position in `system.equations`. If
+/// `proof` does not hold one proof per equation, every equation fails with
+/// [`DimensionMismatch`](VerificationError::DimensionMismatch).
+pub fn verify_system_detailed<E: Pairing>(
+    system: &EquationSystem<E>,
+    proof: &SystemProof<E>,
+    crs: &CRS<E>,
+) -> Vec<Result<(), VerificationError>> {
+    if system.equations.len() != proof.equ_proofs.len() {
+        return vec![Err(VerificationError::DimensionMismatch); system.equations.len()];
     }
+    system
+        .equations
+        .iter()
+        .zip(&proof.equ_proofs)
+        .enumerate()
+        .map(|(i, (equ, equ_proof))| {
+            verify_system_equation(equ, equ_proof, proof, crs).map_err(|err| err.at_equation(i))
+        })
+        .collect()
 }
 
-impl<E: Pairing> Verifiable<E> for QuadEqu<E> {
-    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> bool {
-        assert_eq!(com_proof.equ_proofs.len(), 1);
-        assert_eq!(self.get_type(), com_proof.equ_proofs[0].equ_type);
-        let is_parallel = true;
+/// Like [`verify_system`], but verifies the equations in parallel on rayon's global thread pool.
+///
+/// The equations of a system are independent given the shared commitments, so they can be
+/// checked in any order; the verdict is the same as that of [`verify_system`].
+#[cfg(feature = "parallel")]
+pub fn verify_system_parallel<E: Pairing>(
+    system: &EquationSystem<E>,
+    proof: &SystemProof<E>,
+    crs: &CRS<E>,
+) -> bool {
+    use rayon::prelude::*;
 
-        let lin_a_com_y = ComT::<E>::pairing_sum(
-            &Com1::<E>::batch_scalar_linear_map(&self.a_consts, crs),
+    system.equations.len() == proof.equ_proofs.len()
+        && system
+            .equations
+            .par_iter()
+            .zip(&proof.equ_proofs)
+            .all(|(equ, equ_proof)| verify_system_equation(equ, equ_proof, proof, crs).is_ok())
+}
+
+// Verifies `equ_proof` for a single equation of a system with the commitments of `proof`.
+fn verify_system_equation<E: Pairing>(
+    equ: &SystemEquation<E>,
+    equ_proof: &EquProof<E>,
+    proof: &SystemProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    match equ {
+        SystemEquation::PairingProduct(equ) => {
+            verify_ppe(equ, &proof.xcoms, &proof.ycoms, equ_proof, crs)
+        }
+        SystemEquation::MultiScalarG1(equ) => {
+            verify_msme_g1(equ, &proof.xcoms, &proof.scalar_ycoms, equ_proof, crs)
+        }
+        SystemEquation::MultiScalarG2(equ) => {
+            verify_msme_g2(equ, &proof.scalar_xcoms, &proof.ycoms, equ_proof, crs)
+        }
+        SystemEquation::Quadratic(equ) => verify_quad(
+            equ,
+            &proof.scalar_xcoms,
+            &proof.scalar_ycoms,
+            equ_proof,
+            crs,
+        ),
+    }
+}
+
+// Compares the two sides of a single equation's verification equation.
+fn check_equal<E: Pairing>(lhs: ComT<E>, rhs: ComT<E>) -> Result<(), VerificationError> {
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err(VerificationError::PairingMismatch { equation_index: 0 })
+    }
+}
+
+/// A pairing-product equation together with the commitments and proof claimed to satisfy it,
+/// as verified by [`batch_verify_ppe`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofWithStatement<E: Pairing> {
+    pub equ: PPE<E>,
+    pub xcoms: Vec<Com1<E>>,
+    pub ycoms: Vec<Com2<E>>,
+    pub proof: EquProof<E>,
+}
+
+/// Verifies many pairing-product equation proofs under the same CRS at once.
+///
+/// Each proof's verification equation is scaled by a fresh random scalar sampled from `rng` and
+/// their sum is checked, so all pairings share a Miller loop and a final exponentiation per
+/// entry of [`BT`](crate::data_structures::ComT). A batch containing an invalid proof is rejected,
+/// except with probability about `1 / |Fr|`, as long as `rng` is not controlled by the prover.
+pub fn batch_verify_ppe<CR, E>(proofs: &[ProofWithStatement<E>], crs: &CRS<E>, rng: &mut CR) -> bool
+where
+    E: Pairing,
+    CR: Rng,
+{
+    // Signed and scaled pairs (x, y), contributing e(x_i, y_j) to each entry (i, j) of BT
+    let mut lhs: Vec<E::G1> = Vec::new();
+    let mut rhs: Vec<Com2<E>> = Vec::new();
+    let mut target = PairingOutput::<E>::zero();
+
+    for stmt in proofs {
+        let (equ, proof) = (&stmt.equ, &stmt.proof);
+        let well_formed = check_dims(
+            &equ.a_consts,
+            &equ.b_consts,
+            &equ.gamma,
+            equ.get_type(),
+            &stmt.xcoms,
+            &stmt.ycoms,
+            proof,
+        )
+        .and_then(|_| check_well_formed(&stmt.xcoms, &stmt.ycoms, proof));
+        if well_formed.is_err() {
+            return false;
+        }
+        // Like in verify_ppe, an equation without committed variables holds iff its target is
+        // the identity, regardless of the proof
+        if stmt.xcoms.is_empty() && stmt.ycoms.is_empty() {
+            if !equ.target.is_zero() {
+                return false;
+            }
+            continue;
+        }
+        let rho = E::ScalarField::rand(rng);
+        let mut push = |x: Com1<E>, y: Com2<E>, neg: bool| {
+            let rho = if neg { -rho } else { rho };
+            lhs.push(x.0 * rho);
+            lhs.push(x.1 * rho);
+            rhs.push(y);
+        };
+
+        // i_1(A) * D + C * (i_2(B) + Gamma D)
+        for (a, d) in equ.a_consts.iter().zip(stmt.ycoms.iter()) {
+            push(Com1::<E>::linear_map(a), *d, false);
+        }
+        let gamma_d = stmt_com_y(&equ.gamma, &stmt.ycoms);
+        for ((c, b), gd) in stmt.xcoms.iter().zip(equ.b_consts.iter()).zip(gamma_d) {
+            push(*c, Com2::<E>::linear_map(b) + gd, false);
+        }
+        // - (u * pi + theta * v)
+        for (u, pi) in crs.u.iter().zip(proof.pi.iter()) {
+            push(*u, *pi, true);
+        }
+        for (theta, v) in proof.theta.iter().zip(crs.v.iter()) {
+            push(*theta, *v, true);
+        }
+        // i_T(t_T) = [[0, 0], [0, t_T]]
+        target += equ.target * rho;
+    }
+
+    let lhs: Vec<Com1<E>> = E::G1::normalize_batch(&lhs)
+        .chunks(2)
+        .map(|x| Com1::<E>(x[0], x[1]))
+        .collect();
+    check_pairing_product(&lhs, &rhs, &ComT::<E>::linear_map_PPE(&target))
+}
+
+/// Verifies pairing-product equation proofs one by one as they are yielded by `proofs`, e.g.
+/// while they are deserialized from a network stream.
+///
+/// The returned iterator is lazy: each proof is only taken from `proofs` and checked with
+/// [`verify_ppe`] when the next result is requested, so a caller can stop at the first failure
+/// without receiving the remaining proofs. Unlike [`batch_verify_ppe`], every proof is checked
+/// on its own, so a failure is attributed to the proof that caused it.
+pub fn verify_stream<'a, E, I>(
+    proofs: I,
+    crs: &'a CRS<E>,
+) -> impl Iterator<Item = Result<(), VerificationError>> + 'a
+where
+    E: Pairing,
+    I: IntoIterator<Item = ProofWithStatement<E>>,
+    I::IntoIter: 'a,
+{
+    proofs
+        .into_iter()
+        .map(move |stmt| verify_ppe(&stmt.equ, &stmt.xcoms, &stmt.ycoms, &stmt.proof, crs))
+}
+
+/// Verifies a proof that `com_a` and `com_b` commit to the same [`G1`](ark_ec::Pairing::G1Affine)
+/// element, as produced by [`prove_commitment_equality`](crate::prover::prove_commitment_equality).
+pub fn verify_commitment_equality<E: Pairing>(
+    com_a: &Com1<E>,
+    com_b: &Com1<E>,
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    verify_ppe(&PPE::equality_G1(crs), &[*com_a, *com_b], &[], proof, crs)
+}
+
+/// Verifies a proof that `com_x` and `com_y` commit to `x P_1` and `x P_2` for the same scalar
+/// `x`, as produced by
+/// [`prove_commitment_equality_G1_G2`](crate::prover::prove_commitment_equality_G1_G2).
+#[allow(non_snake_case)]
+pub fn verify_commitment_equality_G1_G2<E: Pairing>(
+    com_x: &Com1<E>,
+    com_y: &Com2<E>,
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    verify_ppe(&PPE::equality_G1_G2(crs), &[*com_x], &[*com_y], proof, crs)
+}
+
+/// Verifies a proof that the scalar committed to in `com_scalar` is the discrete logarithm of
+/// `point` to `base`, as produced by [`prove_dlog_G1`](crate::prover::prove_dlog_G1).
+#[allow(non_snake_case)]
+pub fn verify_dlog_G1<E: Pairing>(
+    base: &E::G1Affine,
+    point: &E::G1Affine,
+    com_scalar: &Com2<E>,
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    verify_msme_g1(
+        &MSMEG1::dlog(*base, *point),
+        &[],
+        &[*com_scalar],
+        proof,
+        crs,
+    )
+}
+
+/// Verifies a proof that the scalar committed to in `com_scalar` is the discrete logarithm of
+/// `point` to `base`, as produced by [`prove_dlog_G2`](crate::prover::prove_dlog_G2).
+#[allow(non_snake_case)]
+pub fn verify_dlog_G2<E: Pairing>(
+    base: &E::G2Affine,
+    point: &E::G2Affine,
+    com_scalar: &Com1<E>,
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    verify_msme_g2(
+        &MSMEG2::dlog(*base, *point),
+        &[*com_scalar],
+        &[],
+        proof,
+        crs,
+    )
+}
+
+/// Verifies a proof that the scalar committed to in `com_scalar` is an element of `set`, as
+/// produced by [`prove_set_membership`](crate::prover::prove_set_membership).
+///
+/// No scalar is an element of the empty set, so any proof for it is rejected with
+/// [`DimensionMismatch`](VerificationError::DimensionMismatch).
+pub fn verify_set_membership<E: Pairing>(
+    com_scalar: &Com1<E>,
+    set: &[E::ScalarField],
+    proof: &SetMembershipProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    if set.is_empty() {
+        return Err(VerificationError::DimensionMismatch);
+    }
+    let system = EquationSystem::set_membership(set);
+    let proof = SystemProof {
+        xcoms: Vec::new(),
+        ycoms: Vec::new(),
+        scalar_xcoms: vec![*com_scalar],
+        scalar_ycoms: proof.partial_coms.clone(),
+        equ_proofs: proof.equ_proofs.clone(),
+    };
+    verify_system_detailed(&system, &proof, crs)
+        .into_iter()
+        .collect()
+}
+
+/// Verifies a zero-knowledge proof that the pairing-product equation `equ` holds for the
+/// product of the pairings of `target`, as produced by
+/// [`prove_ppe_zk`](crate::prover::prove_ppe_zk) or [`simulate_ppe`](crate::prover::simulate_ppe).
+///
+/// The proof is checked against the equations of [`EquationSystem::zero_knowledge_ppe`], with
+/// `ι_2'(1)` as the commitment to `δ`. If the target of `equ` is not the product of the pairings
+/// of `target`, the equation cannot hold and this fails with
+/// [`PairingMismatch`](VerificationError::PairingMismatch) at `equation_index` 0.
+pub fn verify_ppe_zk<E: Pairing>(
+    equ: &PPE<E>,
+    target: &[(E::G1Affine, E::G2Affine)],
+    proof: &ZkPpeProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    if GtTarget::from_pairs(target).into_inner() != equ.target {
+        return Err(VerificationError::PairingMismatch { equation_index: 0 });
+    }
+    if proof.target_coms.len() != target.len() {
+        return Err(VerificationError::DimensionMismatch);
+    }
+    let system = EquationSystem::zero_knowledge_ppe(equ, target);
+    let proof = SystemProof {
+        xcoms: proof
+            .xcoms
+            .iter()
+            .chain(proof.target_coms.iter())
+            .copied()
+            .collect(),
+        ycoms: proof.ycoms.clone(),
+        scalar_xcoms: Vec::new(),
+        scalar_ycoms: vec![Com2::<E>::scalar_linear_map(&E::ScalarField::one(), crs)],
+        equ_proofs: proof.equ_proofs.clone(),
+    };
+    verify_system_detailed(&system, &proof, crs)
+        .into_iter()
+        .collect()
+}
+
+impl<E: Pairing> Verifiable<E> for MSMEG1<E> {
+    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> Result<(), VerificationError> {
+        verify_msme_g1(
+            self,
+            &com_proof.xcoms.coms,
             &com_proof.ycoms.coms,
-        );
+            single_proof(com_proof)?,
+            crs,
+        )
+    }
+}
 
-        let com_x_lin_b = ComT::<E>::pairing_sum(
+/// Verifies a proof `(π, θ)` that the [`G1`](ark_ec::Pairing::G1Affine) variables committed to in
+/// `xcoms` and the [scalar](ark_ec::Pairing::Fr) variables committed to in `scalar_ycoms` satisfy
+/// the multi-scalar multiplication equation `equ`.
+///
+/// Unlike [`Verifiable::verify`], this only needs the commitments themselves and not their openings.
+pub fn verify_msme_g1<E: Pairing>(
+    equ: &MSMEG1<E>,
+    xcoms: &[Com1<E>],
+    scalar_ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    check_dims(
+        &equ.a_consts,
+        &equ.b_consts,
+        &equ.gamma,
+        equ.get_type(),
+        xcoms,
+        scalar_ycoms,
+        proof,
+    )?;
+    check_well_formed(xcoms, scalar_ycoms, proof)?;
+
+    let lin_a_com_y =
+        ComT::<E>::pairing_sum(&Com1::<E>::batch_linear_map(&equ.a_consts), scalar_ycoms);
+
+    let com_x_lin_b = ComT::<E>::pairing_sum(
+        xcoms,
+        &Com2::<E>::batch_scalar_linear_map(&equ.b_consts, crs),
+    );
+
+    let com_x_stmt_com_y = ComT::<E>::pairing_sum(xcoms, &stmt_com_y(&equ.gamma, scalar_ycoms));
+
+    let lin_t = ComT::<E>::linear_map_MSMEG1(&equ.target, crs);
+
+    let com1_pf2 = ComT::<E>::pairing_sum(&crs.u, &proof.pi);
+
+    let pf1_com2 = ComT::<E>::pairing(proof.theta[0], *crs.v1());
+
+    let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
+    let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+
+    check_equal(lhs, rhs)
+}
+
+impl<E: Pairing> Verifiable<E> for MSMEG2<E> {
+    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> Result<(), VerificationError> {
+        verify_msme_g2(
+            self,
             &com_proof.xcoms.coms,
-            &Com2::<E>::batch_scalar_linear_map(&self.b_consts, crs),
-        );
+            &com_proof.ycoms.coms,
+            single_proof(com_proof)?,
+            crs,
+        )
+    }
+}
+
+/// Verifies a proof `(π, θ)` that the [scalar](ark_ec::Pairing::Fr) variables committed to in
+/// `scalar_xcoms` and the [`G2`](ark_ec::Pairing::G2Affine) variables committed to in `ycoms`
+/// satisfy the multi-scalar multiplication equation `equ`.
+///
+/// Unlike [`Verifiable::verify`], this only needs the commitments themselves and not their openings.
+pub fn verify_msme_g2<E: Pairing>(
+    equ: &MSMEG2<E>,
+    scalar_xcoms: &[Com1<E>],
+    ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    check_dims(
+        &equ.a_consts,
+        &equ.b_consts,
+        &equ.gamma,
+        equ.get_type(),
+        scalar_xcoms,
+        ycoms,
+        proof,
+    )?;
+    check_well_formed(scalar_xcoms, ycoms, proof)?;
+
+    let lin_a_com_y = ComT::<E>::pairing_sum(
+        &Com1::<E>::batch_scalar_linear_map(&equ.a_consts, crs),
+        ycoms,
+    );
 
-        let stmt_com_y: Matrix<Com2<E>> =
-            vec_to_col_vec(&com_proof.ycoms.coms).left_mul(&self.gamma, is_parallel);
-        let com_x_stmt_com_y =
-            ComT::<E>::pairing_sum(&com_proof.xcoms.coms, &col_vec_to_vec(&stmt_com_y));
+    let com_x_lin_b =
+        ComT::<E>::pairing_sum(scalar_xcoms, &Com2::<E>::batch_linear_map(&equ.b_consts));
 
-        let lin_t = ComT::<E>::linear_map_quad(&self.target, crs);
+    let com_x_stmt_com_y = ComT::<E>::pairing_sum(scalar_xcoms, &stmt_com_y(&equ.gamma, ycoms));
 
-        let com1_pf2 = ComT::<E>::pairing(crs.u[0], com_proof.equ_proofs[0].pi[0]);
+    let lin_t = ComT::<E>::linear_map_MSMEG2(&equ.target, crs);
 
-        let pf1_com2 = ComT::<E>::pairing(com_proof.equ_proofs[0].theta[0], crs.v[0]);
+    let com1_pf2 = ComT::<E>::pairing(*crs.u1(), proof.pi[0]);
 
-        let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
-        let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+    let pf1_com2 = ComT::<E>::pairing_sum(&proof.theta, &crs.v);
 
-        lhs == rhs
+    let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
+    let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+
+    check_equal(lhs, rhs)
+}
+
+impl<E: Pairing> Verifiable<E> for QuadEqu<E> {
+    fn verify(&self, com_proof: &CProof<E>, crs: &CRS<E>) -> Result<(), VerificationError> {
+        verify_quad(
+            self,
+            &com_proof.xcoms.coms,
+            &com_proof.ycoms.coms,
+            single_proof(com_proof)?,
+            crs,
+        )
     }
 }
 
+/// Verifies a proof `(π, θ)` that the [scalar](ark_ec::Pairing::Fr) variables committed to in
+/// `scalar_xcoms` and `scalar_ycoms` satisfy the quadratic equation `equ`.
+///
+/// Unlike [`Verifiable::verify`], this only needs the commitments themselves and not their openings.
+pub fn verify_quad<E: Pairing>(
+    equ: &QuadEqu<E>,
+    scalar_xcoms: &[Com1<E>],
+    scalar_ycoms: &[Com2<E>],
+    proof: &EquProof<E>,
+    crs: &CRS<E>,
+) -> Result<(), VerificationError> {
+    check_dims(
+        &equ.a_consts,
+        &equ.b_consts,
+        &equ.gamma,
+        equ.get_type(),
+        scalar_xcoms,
+        scalar_ycoms,
+        proof,
+    )?;
+    check_well_formed(scalar_xcoms, scalar_ycoms, proof)?;
+
+    let lin_a_com_y = ComT::<E>::pairing_sum(
+        &Com1::<E>::batch_scalar_linear_map(&equ.a_consts, crs),
+        scalar_ycoms,
+    );
+
+    let com_x_lin_b = ComT::<E>::pairing_sum(
+        scalar_xcoms,
+        &Com2::<E>::batch_scalar_linear_map(&equ.b_consts, crs),
+    );
+
+    let com_x_stmt_com_y =
+        ComT::<E>::pairing_sum(scalar_xcoms, &stmt_com_y(&equ.gamma, scalar_ycoms));
+
+    let lin_t = ComT::<E>::linear_map_quad(&equ.target, crs);
+
+    let com1_pf2 = ComT::<E>::pairing(*crs.u1(), proof.pi[0]);
+
+    let pf1_com2 = ComT::<E>::pairing(proof.theta[0], *crs.v1());
+
+    let lhs: ComT<E> = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
+    let rhs: ComT<E> = lin_t + com1_pf2 + pf1_com2;
+
+    check_equal(lhs, rhs)
+}
+
 /*
  * NOTE:
  *
@@ -84,3 +84,138 @@ mod SXDH_commit_tests {
         assert_eq!(bt_lin_bilin, bt_bilin_lin);
     }
 }
+
+// Commitment round-trips written against an arbitrary `E: Pairing`, so that no curve-specific
+// assumption slips into the commitment code. Further engines are added with `engine_tests!`.
+#[cfg(test)]
+mod engine_generic_commit_tests {
+
+    use ark_ec::pairing::Pairing;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::UniformRand;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::ops::Mul;
+    use ark_std::test_rng;
+
+    use groth_sahai::prover::*;
+    use groth_sahai::CRS;
+
+    fn commit_G1_round_trip<E: Pairing>() {
+        let mut rng = test_rng();
+        let (crs, td) = CRS::<E>::generate_binding(&mut rng);
+
+        let xvars: Vec<E::G1Affine> = vec![
+            crs.g1_gen.mul(E::ScalarField::rand(&mut rng)).into_affine(),
+            E::G1Affine::zero(),
+        ];
+        let (coms, R) = batch_commit_G1_with_openings(&xvars, &crs, &mut rng);
+        for ((com, xvar), r) in coms.iter().zip(xvars.iter()).zip(R.iter()) {
+            assert!(open_commit_G1(com, xvar, r[0], r[1], &crs));
+            assert_eq!(extract_G1(com, &td), *xvar);
+
+            let mut bytes = Vec::new();
+            com.serialize_compressed(&mut bytes).unwrap();
+            let com_de = groth_sahai::Com1::<E>::deserialize_compressed(&bytes[..]).unwrap();
+            assert_eq!(*com, com_de);
+        }
+    }
+
+    fn commit_G2_round_trip<E: Pairing>() {
+        let mut rng = test_rng();
+        let (crs, td) = CRS::<E>::generate_binding(&mut rng);
+
+        let yvars: Vec<E::G2Affine> = vec![
+            crs.g2_gen.mul(E::ScalarField::rand(&mut rng)).into_affine(),
+            E::G2Affine::zero(),
+        ];
+        let (coms, S) = batch_commit_G2_with_openings(&yvars, &crs, &mut rng);
+        for ((com, yvar), s) in coms.iter().zip(yvars.iter()).zip(S.iter()) {
+            assert!(open_commit_G2(com, yvar, s[0], s[1], &crs));
+            assert_eq!(extract_G2(com, &td), *yvar);
+
+            let mut bytes = Vec::new();
+            com.serialize_uncompressed(&mut bytes).unwrap();
+            let com_de = groth_sahai::Com2::<E>::deserialize_uncompressed(&bytes[..]).unwrap();
+            assert_eq!(*com, com_de);
+        }
+    }
+
+    fn commit_scalar_round_trip<E: Pairing>() {
+        let mut rng = test_rng();
+        let crs = CRS::<E>::from_seed([7u8; 32]);
+
+        let x = E::ScalarField::rand(&mut rng);
+        let r = E::ScalarField::rand(&mut rng);
+        let xcom = commit_scalar_to_B1_with_randomness(&x, &crs, r);
+        assert!(open_commit_scalar_to_B1(&xcom, &x, r, &crs));
+        assert!(!open_commit_scalar_to_B1(&xcom, &(x + x), r, &crs));
+        let ycom = commit_scalar_to_B2_with_randomness(&x, &crs, r);
+        assert!(open_commit_scalar_to_B2(&ycom, &x, r, &crs));
+        assert!(!open_commit_scalar_to_B2(&ycom, &(x + x), r, &crs));
+    }
+
+    fn commit_reproducible_with_seeded_rng<E: Pairing>() {
+        use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+        let crs = CRS::<E>::from_seed([7u8; 32]);
+        let xvars = vec![crs.g1_gen, E::G1Affine::zero()];
+        let yvars = vec![crs.g2_gen];
+        let scalars = vec![E::ScalarField::from(3u64)];
+
+        let run = |seed: [u8; 32]| {
+            let mut rng = ChaCha20Rng::from_seed(seed);
+            (
+                batch_commit_G1(&xvars, &crs, &mut rng),
+                batch_commit_G2(&yvars, &crs, &mut rng),
+                batch_commit_scalar_to_B1(&scalars, &crs, &mut rng),
+            )
+        };
+        let (xcoms, ycoms, scoms) = run([1u8; 32]);
+        let (xcoms2, ycoms2, scoms2) = run([1u8; 32]);
+        assert_eq!(xcoms, xcoms2);
+        assert_eq!(ycoms, ycoms2);
+        assert_eq!(scoms, scoms2);
+        assert_ne!(xcoms.coms, run([2u8; 32]).0.coms);
+
+        // The batch functions draw their randomness in order, so the same stream fed to the
+        // deterministic entry points gives the same commitments
+        let mut rng = ChaCha20Rng::from_seed([1u8; 32]);
+        for (xvar, com) in xvars.iter().zip(xcoms.coms.iter()) {
+            let (r1, r2) = (
+                E::ScalarField::rand(&mut rng),
+                E::ScalarField::rand(&mut rng),
+            );
+            assert_eq!(commit_G1_with_randomness(xvar, &crs, r1, r2), *com);
+        }
+    }
+
+    macro_rules! engine_tests {
+        ($name:ident, $engine:ty) => {
+            mod $name {
+                #[test]
+                fn commit_G1_round_trip() {
+                    super::commit_G1_round_trip::<$engine>();
+                }
+
+                #[test]
+                fn commit_G2_round_trip() {
+                    super::commit_G2_round_trip::<$engine>();
+                }
+
+                #[test]
+                fn commit_scalar_round_trip() {
+                    super::commit_scalar_round_trip::<$engine>();
+                }
+
+                #[test]
+                fn commit_reproducible_with_seeded_rng() {
+                    super::commit_reproducible_with_seeded_rng::<$engine>();
+                }
+            }
+        };
+    }
+
+    engine_tests!(bls12_381, ark_bls12_381::Bls12_381);
+    engine_tests!(bn254, ark_bn254::Bn254);
+    engine_tests!(bw6_761, ark_bw6_761::BW6_761);
+}
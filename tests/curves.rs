@@ -0,0 +1,65 @@
+#![allow(non_snake_case)]
+
+// Each module only compiles with its curve feature, e.g. `cargo test --test curves --features bn254`
+macro_rules! curve_tests {
+    ($curve:ident, $feature:literal) => {
+        #[cfg(feature = $feature)]
+        mod $curve {
+            use ark_ec::pairing::Pairing;
+            use ark_ec::CurveGroup;
+            use ark_std::{test_rng, UniformRand};
+
+            use groth_sahai::curves::$curve::*;
+            use groth_sahai::data_structures::{self, ComT, BT};
+            use groth_sahai::prover::{batch_commit_G1, batch_commit_G2, prove_ppe};
+            use groth_sahai::verifier::verify_ppe;
+            use groth_sahai::AbstractCrs;
+
+            #[test]
+            fn ppe_proof_verifies() {
+                let mut rng = test_rng();
+                let crs = CRS::generate_crs(&mut rng);
+
+                // e(X_1, P_2) * e(-P_1, Y_1) = 1 for X_1 = x P_1 and Y_1 = x P_2
+                let x = Fr::rand(&mut rng);
+                let xvars: Vec<G1Affine> = vec![(crs.g1_gen * x).into_affine()];
+                let yvars: Vec<G2Affine> = vec![(crs.g2_gen * x).into_affine()];
+                let equ = PPE::equality_G1_G2(&crs);
+                let xcoms: Commit1 = batch_commit_G1(&xvars, &crs, &mut rng);
+                let ycoms: Commit2 = batch_commit_G2(&yvars, &crs, &mut rng);
+
+                let proof: EquProof =
+                    prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+                assert_eq!(
+                    verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs),
+                    Ok(())
+                );
+                assert_eq!(equ.target, GT::default());
+                assert_eq!(
+                    E::pairing(xvars[0], crs.g2_gen),
+                    E::pairing(crs.g1_gen, yvars[0])
+                );
+            }
+
+            #[test]
+            fn pairing_sum_matches_pairings() {
+                let mut rng = test_rng();
+                // More pairs than ark-ec's BW6 multi-Miller loop handles correctly in one go
+                let mut g1 = || <E as Pairing>::G1::rand(&mut rng).into_affine();
+                let xs: Vec<Com1> = (0..6).map(|_| data_structures::Com1(g1(), g1())).collect();
+                let mut g2 = || <E as Pairing>::G2::rand(&mut rng).into_affine();
+                let ys: Vec<Com2> = (0..6).map(|_| data_structures::Com2(g2(), g2())).collect();
+                let sum: ComT<E> = xs
+                    .iter()
+                    .zip(&ys)
+                    .map(|(x, y)| ComT::<E>::pairing(*x, *y))
+                    .sum();
+                assert_eq!(ComT::<E>::pairing_sum(&xs, &ys), sum);
+            }
+        }
+    };
+}
+
+curve_tests!(bls12_381, "bls12_381");
+curve_tests!(bn254, "bn254");
+curve_tests!(bw6_761, "bw6_761");
@@ -0,0 +1,117 @@
+#![allow(non_snake_case)]
+
+//! Counts the heap allocations of the matrix operations with a counting global allocator.
+//!
+//! The counter is thread-local, so the tests running concurrently in this binary do not interfere
+//! with each other, and only the sequential products are measured.
+
+#[cfg(test)]
+mod matrix_alloc {
+
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::pairing::Pairing;
+    use ark_ff::UniformRand;
+    use ark_std::test_rng;
+
+    use groth_sahai::data_structures::{vec_to_col_vec, Mat, Matrix};
+    use groth_sahai::{AbstractCrs, Com1, CRS};
+
+    type Fr = <F as Pairing>::ScalarField;
+
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    // Returns the result of `f` along with the number of allocations it made on this thread.
+    fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+        let before = ALLOCATIONS.with(Cell::get);
+        let res = f();
+        (res, ALLOCATIONS.with(Cell::get) - before)
+    }
+
+    fn random_matrix(rows: usize, cols: usize) -> Matrix<Fr> {
+        let mut rng = test_rng();
+        (0..rows)
+            .map(|_| (0..cols).map(|_| Fr::rand(&mut rng)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn add_into_reuses_output() {
+        let (a, b, c) = (
+            random_matrix(8, 8),
+            random_matrix(8, 8),
+            random_matrix(8, 8),
+        );
+
+        // An outer vector plus one vector per row for every intermediate result
+        let (expected, allocs) = count_allocations(|| a.add(&b).add(&c));
+        assert_eq!(allocs, 2 * 9);
+
+        let mut out = Vec::new();
+        let (_, allocs) = count_allocations(|| a.add_into(&b, &mut out));
+        assert_eq!(allocs, 9);
+        let (_, allocs) = count_allocations(|| out.add_in_place(&c));
+        assert_eq!(allocs, 0);
+        assert_eq!(out, expected);
+
+        // A warm output buffer is refilled without allocating
+        let (_, allocs) = count_allocations(|| b.add_into(&a, &mut out));
+        assert_eq!(allocs, 0);
+        assert_eq!(out, a.add(&b));
+    }
+
+    #[test]
+    fn mul_into_reuses_output() {
+        let (a, b) = (random_matrix(8, 4), random_matrix(4, 6));
+
+        let (expected, allocs) = count_allocations(|| b.left_mul(&a, false));
+        assert_eq!(allocs, 9);
+        assert_eq!(a.right_mul(&b, false), expected);
+
+        let mut out = random_matrix(8, 6);
+        let (_, allocs) = count_allocations(|| b.left_mul_into(&a, &mut out));
+        assert_eq!(allocs, 0);
+        assert_eq!(out, expected);
+        let (_, allocs) = count_allocations(|| a.right_mul_into(&b, &mut out));
+        assert_eq!(allocs, 0);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn commitment_mul_into_reuses_output() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let R = random_matrix(16, 2);
+        let u = vec_to_col_vec(&crs.u);
+
+        // The scalar multiplications allocate internally, so only compare against `left_mul`,
+        // which additionally allocates the outer vector and one vector per row
+        let (expected, allocs) = count_allocations(|| u.left_mul(&R, false));
+
+        let mut out: Matrix<Com1<F>> = Vec::with_capacity(16);
+        u.left_mul_into(&R, &mut out);
+        let (_, allocs_into) = count_allocations(|| u.left_mul_into(&R, &mut out));
+        assert_eq!(allocs_into + 17, allocs);
+        assert_eq!(out, expected);
+    }
+}
@@ -3,17 +3,24 @@
 #[cfg(test)]
 mod SXDH_prover_tests {
 
-    use ark_bls12_381::Bls12_381 as F;
+    use ark_bls12_381::{Bls12_381 as F, Fq};
     use ark_ec::pairing::{Pairing, PairingOutput};
     use ark_ec::{AffineRepr, CurveGroup};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
     use ark_std::ops::Mul;
     use ark_std::str::FromStr;
-    use ark_std::{test_rng, UniformRand, Zero};
+    use ark_std::{test_rng, One, UniformRand, Zero};
 
     use groth_sahai::data_structures::*;
     use groth_sahai::prover::*;
     use groth_sahai::statement::*;
-    use groth_sahai::verifier::Verifiable;
+    use groth_sahai::verifier::{
+        batch_verify_ppe, blind_ppe, unblind, verify_commitment_equality,
+        verify_commitment_equality_G1_G2, verify_dlog_G1, verify_dlog_G2, verify_msme_g1,
+        verify_ppe, verify_ppe_bool, verify_ppe_ct, verify_ppe_system, verify_ppe_zk, verify_quad,
+        verify_set_membership, verify_stream, verify_system, verify_system_detailed,
+        ProofWithStatement, Verifiable, VerificationError,
+    };
     use groth_sahai::{AbstractCrs, CRS};
 
     type G1Affine = <F as Pairing>::G1Affine;
@@ -58,7 +65,7 @@ mod SXDH_prover_tests {
         };
 
         let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
-        assert!(equ.verify(&proof, &crs));
+        assert!(equ.verify(&proof, &crs).is_ok());
     }
 
     #[test]
@@ -95,8 +102,11 @@ mod SXDH_prover_tests {
             target,
         };
 
+        assert!(equ.is_satisfied(&xvars, &scalar_yvars));
+        assert!(!equ.is_satisfied(&xvars, &[Fr::one()]));
+
         let proof: CProof<F> = equ.commit_and_prove(&xvars, &scalar_yvars, &crs, &mut rng);
-        assert!(equ.verify(&proof, &crs));
+        assert!(equ.verify(&proof, &crs).is_ok());
     }
 
     #[test]
@@ -133,8 +143,11 @@ mod SXDH_prover_tests {
             target,
         };
 
+        assert!(equ.is_satisfied(&scalar_xvars, &yvars));
+        assert!(!equ.is_satisfied(&[Fr::one(), Fr::one()], &yvars));
+
         let proof: CProof<F> = equ.commit_and_prove(&scalar_xvars, &yvars, &crs, &mut rng);
-        assert!(equ.verify(&proof, &crs));
+        assert!(equ.verify(&proof, &crs).is_ok());
     }
 
     #[test]
@@ -167,7 +180,1786 @@ mod SXDH_prover_tests {
             target,
         };
 
+        assert!(equ.is_satisfied(&scalar_xvars, &scalar_yvars));
+        assert!(!equ.is_satisfied(&scalar_xvars, &[Fr::one()]));
+
         let proof: CProof<F> = equ.commit_and_prove(&scalar_xvars, &scalar_yvars, &crs, &mut rng);
-        assert!(equ.verify(&proof, &crs));
+        assert!(equ.verify(&proof, &crs).is_ok());
+    }
+
+    #[test]
+    fn equations_verify_under_hiding_CRS() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_hiding(&mut rng);
+
+        // e(X_1, c_1) * e(X_1, Y_1)^5 = t, with X_1 = 2 g1 and Y_1 = 4 g2
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::from_str("2").unwrap()).into_affine()];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::from_str("4").unwrap()).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let gamma: Matrix<Fr> = vec![vec![Fr::from_str("5").unwrap()]];
+        let target: GT = F::pairing(xvars[0], b_consts[0])
+            + F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine());
+        let equ: PPE<F> = PPE::<F> {
+            a_consts: vec![G1Affine::zero()],
+            b_consts,
+            gamma,
+            target,
+        };
+        let proof: CProof<F> = equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs).is_ok());
+
+        // c_1 * x_1 + (x_1 * y_1)*5 = t, with x_1 = 2 and y_1 = 4
+        let scalar_xvars: Vec<Fr> = vec![Fr::from_str("2").unwrap()];
+        let scalar_yvars: Vec<Fr> = vec![Fr::from_str("4").unwrap()];
+        let b_consts: Vec<Fr> = vec![Fr::rand(&mut rng)];
+        let gamma: Matrix<Fr> = vec![vec![Fr::from_str("5").unwrap()]];
+        let target: Fr =
+            b_consts[0] * scalar_xvars[0] + scalar_yvars[0] * scalar_xvars[0] * gamma[0][0];
+        let equ: QuadEqu<F> = QuadEqu::<F> {
+            a_consts: vec![Fr::zero()],
+            b_consts,
+            gamma,
+            target,
+        };
+        let proof: CProof<F> = equ.commit_and_prove(&scalar_xvars, &scalar_yvars, &crs, &mut rng);
+        assert!(equ.verify(&proof, &crs).is_ok());
+    }
+
+    #[test]
+    fn prove_ppe_end_to_end() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, c_1) * e(c_2, Y_1) * e(X_1, Y_1)^3 = t, with X_1 = 2 g1 and Y_1 = 5 g2
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::from_str("2").unwrap()).into_affine()];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::from_str("5").unwrap()).into_affine()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let gamma: Matrix<Fr> = vec![vec![Fr::from_str("3").unwrap()]];
+        let target: GT = F::pairing(xvars[0], b_consts[0])
+            + F::pairing(a_consts[0], yvars[0])
+            + F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine());
+        let equ: PPE<F> = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        assert_eq!(proof.pi.len(), 2);
+        assert_eq!(proof.theta.len(), 2);
+        assert!(verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs).is_ok());
+
+        // A witness that does not satisfy the equation yields a proof that does not verify
+        let bad_xvars: Vec<G1Affine> =
+            vec![crs.g1_gen.mul(Fr::from_str("7").unwrap()).into_affine()];
+        let bad_xcoms: Commit1<F> = batch_commit_G1(&bad_xvars, &crs, &mut rng);
+        let bad_proof = prove_ppe(&equ, &bad_xvars, &yvars, &bad_xcoms, &ycoms, &crs, &mut rng);
+        assert_eq!(
+            verify_ppe(&equ, &bad_xcoms.coms, &ycoms.coms, &bad_proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+        // Nor does a valid proof for different commitments
+        assert_eq!(
+            verify_ppe(&equ, &bad_xcoms.coms, &ycoms.coms, &proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+    }
+
+    #[test]
+    fn ppe_with_pairing_product_target_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, P_2) * e(P_1, Y_1) = e(Z_1, P_2) * e(P_1, W_1) with X_1 = x P_1, Y_1 = y P_2,
+        // Z_1 = (x + 1) P_1 and W_1 = (y - 1) P_2, where the target is only given as pairs
+        let x = Fr::rand(&mut rng);
+        let y = Fr::rand(&mut rng);
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(x).into_affine()];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(y).into_affine()];
+        let pairs = [
+            (crs.g1_gen.mul(x + Fr::one()).into_affine(), crs.g2_gen),
+            (crs.g1_gen, crs.g2_gen.mul(y - Fr::one()).into_affine()),
+        ];
+        let target = GtTarget::<F>::from_pairs(&pairs);
+        assert_eq!(
+            target.into_inner(),
+            F::pairing(pairs[0].0, pairs[0].1) + F::pairing(pairs[1].0, pairs[1].1)
+        );
+
+        let equ = PPE::<F> {
+            a_consts: vec![crs.g1_gen],
+            b_consts: vec![crs.g2_gen],
+            gamma: vec![vec![Fr::zero()]],
+            target: GT::zero(),
+        }
+        .with_target(target);
+
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        assert!(verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs).is_ok());
+
+        // The same equation with an unsatisfied pairing-product target is rejected
+        let bad_equ = equ.with_target(GtTarget::<F>::from_pairs(&[(xvars[0], yvars[0])]));
+        let bad_proof = prove_ppe(&bad_equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        assert!(verify_ppe(&bad_equ, &xcoms.coms, &ycoms.coms, &bad_proof, &crs).is_err());
+    }
+
+    #[test]
+    fn all_public_ppe_checks_target() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // No variables and no constants: the empty product must equal the target
+        let equ = PPE::<F> {
+            a_consts: vec![],
+            b_consts: vec![],
+            gamma: vec![],
+            target: GT::zero(),
+        };
+        let xcoms = batch_commit_G1(&[], &crs, &mut rng);
+        let ycoms = batch_commit_G2(&[], &crs, &mut rng);
+        let proof = prove_ppe(&equ, &[], &[], &xcoms, &ycoms, &crs, &mut rng);
+        assert_eq!((proof.pi.len(), proof.theta.len()), (2, 2));
+        assert!(verify_ppe(&equ, &[], &[], &proof, &crs).is_ok());
+
+        let unsatisfied = PPE::<F> {
+            target: crs.gt_gen,
+            ..equ.clone()
+        };
+        assert_eq!(
+            verify_ppe(&unsatisfied, &[], &[], &proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+        // The proof must still have the shape of a pairing-product proof
+        let mut short = proof.clone();
+        short.pi.pop();
+        assert_eq!(
+            verify_ppe(&equ, &[], &[], &short, &crs),
+            Err(VerificationError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn tracked_commitments_prove_and_open() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, c_1) * e(X_2, c_2) * e(c_3, Y_1) = t, committing one variable at a time
+        let xvars: Vec<G1Affine> = vec![
+            crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(),
+            crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![
+            crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
+            crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ];
+        let target: GT = F::pairing(xvars[0], b_consts[0])
+            + F::pairing(xvars[1], b_consts[1])
+            + F::pairing(a_consts[0], yvars[0]);
+        let equ = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma: vec![vec![Fr::zero()], vec![Fr::zero()]],
+            target,
+        };
+
+        let mut xcoms: Commit1<F> = commit_G1(&xvars[0], &crs, &mut rng);
+        xcoms.append(&mut commit_G1(&xvars[1], &crs, &mut rng));
+        let ycoms: Commit2<F> = commit_G2(&yvars[0], &crs, &mut rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        assert!(verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs).is_ok());
+
+        // The same commitments still open to the witness
+        assert_eq!(xcoms.openings().len(), 2);
+        for ((com, xvar), r) in xcoms.coms.iter().zip(&xvars).zip(xcoms.openings()) {
+            assert!(open_commit_G1(com, xvar, r[0], r[1], &crs));
+        }
+        let s = &ycoms.openings()[0];
+        assert!(open_commit_G2(&ycoms.coms[0], &yvars[0], s[0], s[1], &crs));
+    }
+
+    #[test]
+    fn prove_msme_g1_end_to_end() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // y_1 * c_1 + b_1 * X_1 + (y_1 * X_1)*3 = T_1, with X_1 = 2 g1 and y_1 = 5
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::from_str("2").unwrap()).into_affine()];
+        let scalar_yvars: Vec<Fr> = vec![Fr::from_str("5").unwrap()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let b_consts: Vec<Fr> = vec![Fr::rand(&mut rng)];
+        let gamma: Matrix<Fr> = vec![vec![Fr::from_str("3").unwrap()]];
+        let target: G1Affine = (a_consts[0].mul(scalar_yvars[0])
+            + xvars[0].mul(b_consts[0])
+            + xvars[0].mul(scalar_yvars[0] * gamma[0][0]))
+        .into_affine();
+        let equ: MSMEG1<F> = MSMEG1::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_scalar_to_B2(&scalar_yvars, &crs, &mut rng);
+        let proof = prove_msme_g1(&equ, &xvars, &scalar_yvars, &xcoms, &ycoms, &crs, &mut rng);
+        assert!(verify_msme_g1(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs).is_ok());
+
+        // A witness that does not satisfy the equation yields a proof that does not verify
+        let bad_yvars: Vec<Fr> = vec![Fr::from_str("6").unwrap()];
+        let bad_ycoms: Commit2<F> = batch_commit_scalar_to_B2(&bad_yvars, &crs, &mut rng);
+        let bad_proof = prove_msme_g1(&equ, &xvars, &bad_yvars, &xcoms, &bad_ycoms, &crs, &mut rng);
+        assert_eq!(
+            verify_msme_g1(&equ, &xcoms.coms, &bad_ycoms.coms, &bad_proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+    }
+
+    #[test]
+    fn prove_quad_end_to_end() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // a . y + x . b + x^T Gamma y = t for a random assignment of x_1, x_2 and y_1
+        let scalar_xvars: Vec<Fr> = vec![Fr::rand(&mut rng), Fr::rand(&mut rng)];
+        let scalar_yvars: Vec<Fr> = vec![Fr::rand(&mut rng)];
+        let a_consts: Vec<Fr> = vec![Fr::rand(&mut rng)];
+        let b_consts: Vec<Fr> = vec![Fr::rand(&mut rng), Fr::rand(&mut rng)];
+        let gamma: Matrix<Fr> = vec![vec![Fr::rand(&mut rng)], vec![Fr::rand(&mut rng)]];
+        let target: Fr = a_consts[0] * scalar_yvars[0]
+            + scalar_xvars[0] * b_consts[0]
+            + scalar_xvars[1] * b_consts[1]
+            + scalar_xvars[0] * gamma[0][0] * scalar_yvars[0]
+            + scalar_xvars[1] * gamma[1][0] * scalar_yvars[0];
+        let equ: QuadEqu<F> = QuadEqu::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+
+        let xcoms: Commit1<F> = batch_commit_scalar_to_B1(&scalar_xvars, &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_scalar_to_B2(&scalar_yvars, &crs, &mut rng);
+        let proof = prove_quad(
+            &equ,
+            &scalar_xvars,
+            &scalar_yvars,
+            &xcoms,
+            &ycoms,
+            &crs,
+            &mut rng,
+        );
+        assert!(verify_quad(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs).is_ok());
+
+        // Tampering with a single variable breaks the proof
+        let bad_xvars: Vec<Fr> = vec![scalar_xvars[0] + Fr::from(1u64), scalar_xvars[1]];
+        let bad_xcoms: Commit1<F> = batch_commit_scalar_to_B1(&bad_xvars, &crs, &mut rng);
+        let bad_proof = prove_quad(
+            &equ,
+            &bad_xvars,
+            &scalar_yvars,
+            &bad_xcoms,
+            &ycoms,
+            &crs,
+            &mut rng,
+        );
+        assert_eq!(
+            verify_quad(&equ, &bad_xcoms.coms, &ycoms.coms, &bad_proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+    }
+
+    // Proves that random X_1, X_2 and Y_1 satisfy e(X_1, c_1) * e(c_2, Y_1) * e(X_2, Y_1)^g = t
+    fn random_ppe_proof(crs: &CRS<F>, rng: &mut impl ark_std::rand::Rng) -> ProofWithStatement<F> {
+        let xvars: Vec<G1Affine> = vec![
+            crs.g1_gen.mul(Fr::rand(rng)).into_affine(),
+            crs.g1_gen.mul(Fr::rand(rng)).into_affine(),
+        ];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(rng)).into_affine()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(rng)).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![
+            crs.g2_gen.mul(Fr::rand(rng)).into_affine(),
+            G2Affine::zero(),
+        ];
+        let gamma: Matrix<Fr> = vec![vec![Fr::zero()], vec![Fr::rand(rng)]];
+        let target: GT = F::pairing(xvars[0], b_consts[0])
+            + F::pairing(a_consts[0], yvars[0])
+            + F::pairing(xvars[1], yvars[0].mul(gamma[1][0]).into_affine());
+        let equ: PPE<F> = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+
+        let xcoms: Commit1<F> = batch_commit_G1(&xvars, crs, rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&yvars, crs, rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, crs, rng);
+        ProofWithStatement {
+            equ,
+            xcoms: xcoms.coms,
+            ycoms: ycoms.coms,
+            proof,
+        }
+    }
+
+    #[test]
+    fn batch_verify_ppe_accepts_valid_proofs() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let proofs: Vec<ProofWithStatement<F>> =
+            (0..5).map(|_| random_ppe_proof(&crs, &mut rng)).collect();
+        for p in proofs.iter() {
+            assert!(verify_ppe(&p.equ, &p.xcoms, &p.ycoms, &p.proof, &crs).is_ok());
+        }
+        assert!(batch_verify_ppe(&proofs, &crs, &mut rng));
+        assert!(batch_verify_ppe(&proofs[..1], &crs, &mut rng));
+        assert!(batch_verify_ppe::<_, F>(&[], &crs, &mut rng));
+    }
+
+    #[test]
+    fn batch_verify_ppe_agrees_on_all_public_equations() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let mut proofs: Vec<ProofWithStatement<F>> =
+            (0..2).map(|_| random_ppe_proof(&crs, &mut rng)).collect();
+        // Without variables, any proof is accepted for the identity as target ...
+        let tautology = ProofWithStatement::<F> {
+            equ: PPE::<F>::tautology(0, 0),
+            xcoms: vec![],
+            ycoms: vec![],
+            proof: proofs[0].proof.clone(),
+        };
+        assert_eq!(
+            verify_ppe(&tautology.equ, &[], &[], &tautology.proof, &crs),
+            Ok(())
+        );
+        proofs.push(tautology);
+        assert!(batch_verify_ppe(&proofs, &crs, &mut rng));
+
+        // ... and none for any other target
+        let contradiction = ProofWithStatement::<F> {
+            equ: PPE::<F>::contradiction(0, 0),
+            xcoms: vec![],
+            ycoms: vec![],
+            proof: proofs[0].proof.clone(),
+        };
+        assert!(verify_ppe(&contradiction.equ, &[], &[], &contradiction.proof, &crs).is_err());
+        proofs.push(contradiction);
+        assert!(!batch_verify_ppe(&proofs, &crs, &mut rng));
+        assert!(!batch_verify_ppe(&proofs[3..], &crs, &mut rng));
+    }
+
+    #[test]
+    fn batch_verify_ppe_handles_one_sided_equations() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // X only: e(X_1, P_2) * e(X_2, -P_2) = 1, i.e. X_1 = X_2
+        let x = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let equ = PPE::<F>::equality_G1(&crs);
+        let xcoms: Commit1<F> = batch_commit_G1(&[x, x], &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&[], &crs, &mut rng);
+        let proof = prove_ppe(&equ, &[x, x], &[], &xcoms, &ycoms, &crs, &mut rng);
+        let x_only = ProofWithStatement {
+            equ,
+            xcoms: xcoms.coms,
+            ycoms: ycoms.coms,
+            proof,
+        };
+
+        // Y only: e(a_1, Y_1) = t
+        let y = crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let a = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let equ = PPE::<F> {
+            a_consts: vec![a],
+            b_consts: vec![],
+            gamma: vec![],
+            target: F::pairing(a, y),
+        };
+        let xcoms: Commit1<F> = batch_commit_G1(&[], &crs, &mut rng);
+        let ycoms: Commit2<F> = batch_commit_G2(&[y], &crs, &mut rng);
+        let proof = prove_ppe(&equ, &[], &[y], &xcoms, &ycoms, &crs, &mut rng);
+        let y_only = ProofWithStatement {
+            equ,
+            xcoms: xcoms.coms,
+            ycoms: ycoms.coms,
+            proof,
+        };
+
+        let proofs = vec![
+            x_only.clone(),
+            y_only.clone(),
+            random_ppe_proof(&crs, &mut rng),
+        ];
+        for p in proofs.iter() {
+            assert!(verify_ppe(&p.equ, &p.xcoms, &p.ycoms, &p.proof, &crs).is_ok());
+        }
+        assert!(batch_verify_ppe(&proofs, &crs, &mut rng));
+        assert!(batch_verify_ppe(&proofs[..1], &crs, &mut rng));
+        assert!(batch_verify_ppe(&proofs[1..2], &crs, &mut rng));
+
+        // An all-zero proof neither shows that commitments to different points are equal ...
+        let other = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let mut forged_x = x_only;
+        forged_x.xcoms = batch_commit_G1(&[x, other], &crs, &mut rng).coms;
+        forged_x.proof.pi = vec![Com2::<F>::zero(); 2];
+        forged_x.proof.theta = vec![Com1::<F>::zero(); 2];
+        assert!(verify_ppe(
+            &forged_x.equ,
+            &forged_x.xcoms,
+            &forged_x.ycoms,
+            &forged_x.proof,
+            &crs
+        )
+        .is_err());
+        assert!(!batch_verify_ppe(&[forged_x.clone()], &crs, &mut rng));
+        assert!(!batch_verify_ppe(
+            &[proofs[2].clone(), forged_x],
+            &crs,
+            &mut rng
+        ));
+
+        // ... nor a Y-only equation for a variable that does not satisfy it
+        let mut forged_y = y_only;
+        forged_y.ycoms = batch_commit_G2(&[crs.g2_gen], &crs, &mut rng).coms;
+        forged_y.proof.pi = vec![Com2::<F>::zero(); 2];
+        forged_y.proof.theta = vec![Com1::<F>::zero(); 2];
+        assert!(verify_ppe(
+            &forged_y.equ,
+            &forged_y.xcoms,
+            &forged_y.ycoms,
+            &forged_y.proof,
+            &crs
+        )
+        .is_err());
+        assert!(!batch_verify_ppe(&[forged_y.clone()], &crs, &mut rng));
+        assert!(!batch_verify_ppe(
+            &[proofs[2].clone(), forged_y],
+            &crs,
+            &mut rng
+        ));
+    }
+
+    #[test]
+    fn batch_verify_ppe_rejects_one_bad_proof() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let mut proofs: Vec<ProofWithStatement<F>> =
+            (0..5).map(|_| random_ppe_proof(&crs, &mut rng)).collect();
+        // Claim a different target for a single equation
+        proofs[3].equ.target += crs.gt_gen;
+        assert_eq!(
+            verify_ppe(
+                &proofs[3].equ,
+                &proofs[3].xcoms,
+                &proofs[3].ycoms,
+                &proofs[3].proof,
+                &crs
+            ),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+        assert!(!batch_verify_ppe(&proofs, &crs, &mut rng));
+
+        // Swapping the proofs of two valid equations is also caught
+        let mut proofs: Vec<ProofWithStatement<F>> =
+            (0..2).map(|_| random_ppe_proof(&crs, &mut rng)).collect();
+        let pf = proofs[0].proof.clone();
+        proofs[0].proof = proofs[1].proof.clone();
+        proofs[1].proof = pf;
+        assert!(!batch_verify_ppe(&proofs, &crs, &mut rng));
+    }
+
+    #[test]
+    fn verify_stream_stops_at_first_failure() {
+        use std::cell::Cell;
+
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let mut proofs: Vec<ProofWithStatement<F>> =
+            (0..3).map(|_| random_ppe_proof(&crs, &mut rng)).collect();
+        proofs[1].equ.target += crs.gt_gen;
+
+        // Every proof is verified on its own
+        let results: Vec<_> = verify_stream(proofs.clone(), &crs).collect();
+        assert_eq!(
+            results,
+            vec![
+                Ok(()),
+                Err(VerificationError::PairingMismatch { equation_index: 0 }),
+                Ok(())
+            ]
+        );
+
+        // Proofs are only received as far as they are verified
+        let received = Cell::new(0);
+        let stream = proofs
+            .into_iter()
+            .inspect(|_| received.set(received.get() + 1));
+        let first_failure = verify_stream(stream, &crs).position(|res| res.is_err());
+        assert_eq!(first_failure, Some(1));
+        assert_eq!(received.get(), 2);
+    }
+
+    // Property test: over random statements, honest proofs verify, while proofs checked against a
+    // different statement, random proofs and random commitments are rejected
+    #[test]
+    fn verify_ppe_rejects_forgeries() {
+        use ark_std::rand::Rng;
+        use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+        const ROUNDS: usize = 8;
+        let mut rng = ChaCha20Rng::seed_from_u64(64);
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let rand_g1 = |rng: &mut ChaCha20Rng| crs.g1_gen.mul(Fr::rand(rng)).into_affine();
+        let rand_g2 = |rng: &mut ChaCha20Rng| crs.g2_gen.mul(Fr::rand(rng)).into_affine();
+
+        for _ in 0..ROUNDS {
+            let p = random_ppe_proof(&crs, &mut rng);
+            let verifies =
+                |equ: &PPE<F>, xcoms: &[Com1<F>], ycoms: &[Com2<F>], proof: &EquProof<F>| {
+                    verify_ppe(equ, xcoms, ycoms, proof, &crs).is_ok()
+                };
+            assert!(verifies(&p.equ, &p.xcoms, &p.ycoms, &p.proof));
+
+            // S' != S: perturb one component of the statement at a time
+            let mut other = p.equ.clone();
+            other.target += F::pairing(rand_g1(&mut rng), crs.g2_gen);
+            assert!(!verifies(&other, &p.xcoms, &p.ycoms, &p.proof));
+            let mut other = p.equ.clone();
+            other.a_consts[0] = rand_g1(&mut rng);
+            assert!(!verifies(&other, &p.xcoms, &p.ycoms, &p.proof));
+            let mut other = p.equ.clone();
+            other.b_consts[0] = rand_g2(&mut rng);
+            assert!(!verifies(&other, &p.xcoms, &p.ycoms, &p.proof));
+            let mut other = p.equ.clone();
+            other.gamma[1][0] += Fr::rand(&mut rng);
+            assert!(!verifies(&other, &p.xcoms, &p.ycoms, &p.proof));
+
+            // An unrelated, honestly generated statement
+            let q = random_ppe_proof(&crs, &mut rng);
+            assert!(!verifies(&q.equ, &p.xcoms, &p.ycoms, &p.proof));
+
+            // Random proof elements
+            let mut forged = p.proof.clone();
+            forged.pi[rng.gen_range(0..2)] = Com2::<F>(rand_g2(&mut rng), rand_g2(&mut rng));
+            assert!(!verifies(&p.equ, &p.xcoms, &p.ycoms, &forged));
+            let mut forged = p.proof.clone();
+            forged.theta[rng.gen_range(0..2)] = Com1::<F>(rand_g1(&mut rng), rand_g1(&mut rng));
+            assert!(!verifies(&p.equ, &p.xcoms, &p.ycoms, &forged));
+
+            // Random commitments
+            let mut xcoms = p.xcoms.clone();
+            xcoms[rng.gen_range(0..2)] = Com1::<F>(rand_g1(&mut rng), rand_g1(&mut rng));
+            assert!(!verifies(&p.equ, &xcoms, &p.ycoms, &p.proof));
+            let mut ycoms = p.ycoms.clone();
+            ycoms[0] = Com2::<F>(rand_g2(&mut rng), rand_g2(&mut rng));
+            assert!(!verifies(&p.equ, &p.xcoms, &ycoms, &p.proof));
+        }
+    }
+
+    #[test]
+    fn verify_ppe_ct_agrees_with_verify_ppe() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let p = random_ppe_proof(&crs, &mut rng);
+        let mut wrong_target = p.equ.clone();
+        wrong_target.target += crs.gt_gen;
+        let mut wrong_theta = p.proof.clone();
+        wrong_theta.theta[1] = wrong_theta.theta[0];
+        let mut short_pi = p.proof.clone();
+        short_pi.pi.pop();
+
+        for (equ, proof) in [
+            (&p.equ, &p.proof),
+            (&wrong_target, &p.proof),
+            (&p.equ, &wrong_theta),
+            (&p.equ, &short_pi),
+        ] {
+            assert_eq!(
+                verify_ppe_ct(equ, &p.xcoms, &p.ycoms, proof, &crs),
+                verify_ppe(equ, &p.xcoms, &p.ycoms, proof, &crs)
+            );
+        }
+        assert!(verify_ppe_ct(&p.equ, &p.xcoms, &p.ycoms, &p.proof, &crs).is_ok());
+        assert!(verify_ppe_ct(&wrong_target, &p.xcoms, &p.ycoms, &p.proof, &crs).is_err());
+    }
+
+    #[test]
+    fn verifier_reports_dimension_mismatch() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let p = random_ppe_proof(&crs, &mut rng);
+        assert!(verify_ppe(&p.equ, &p.xcoms, &p.ycoms, &p.proof, &crs).is_ok());
+
+        // Missing commitment
+        assert_eq!(
+            verify_ppe(&p.equ, &p.xcoms[..1], &p.ycoms, &p.proof, &crs),
+            Err(VerificationError::DimensionMismatch)
+        );
+        // Truncated proof
+        let mut proof = p.proof.clone();
+        proof.pi.pop();
+        assert_eq!(
+            verify_ppe(&p.equ, &p.xcoms, &p.ycoms, &proof, &crs),
+            Err(VerificationError::DimensionMismatch)
+        );
+        // Proof for a different type of equation
+        let mut proof = p.proof.clone();
+        proof.equ_type = EquType::MultiScalarG1;
+        assert_eq!(
+            verify_ppe(&p.equ, &p.xcoms, &p.ycoms, &proof, &crs),
+            Err(VerificationError::DimensionMismatch)
+        );
+        // No equation proof or more than one
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen; 2];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen];
+        let mut com_proof: CProof<F> = p.equ.commit_and_prove(&xvars, &yvars, &crs, &mut rng);
+        com_proof.equ_proofs.push(p.proof.clone());
+        assert_eq!(
+            p.equ.verify(&com_proof, &crs),
+            Err(VerificationError::DimensionMismatch)
+        );
+        com_proof.equ_proofs.clear();
+        assert_eq!(
+            p.equ.verify(&com_proof, &crs),
+            Err(VerificationError::DimensionMismatch)
+        );
+        assert!(!verify_ppe_bool(
+            &p.equ,
+            &p.xcoms[..1],
+            &p.ycoms,
+            &p.proof,
+            &crs
+        ));
+    }
+
+    #[test]
+    fn prover_handles_one_sided_equations() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // m = 2 and n = 0: e(X_1, P_2) * e(X_2, -P_2) = 1
+        let x = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let xvars = vec![x, x];
+        let equ = PPE::<F>::equality_G1(&crs);
+        let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms = batch_commit_G2(&[], &crs, &mut rng);
+        let proof = try_prove_ppe(&equ, &xvars, &[], &xcoms, &ycoms, &crs, &mut rng).unwrap();
+        assert_eq!(verify_ppe(&equ, &xcoms.coms, &[], &proof, &crs), Ok(()));
+
+        // m = 0 and n = 1: e(P_1, Y_1) = t
+        let yvars = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let equ = PPE::<F> {
+            a_consts: vec![crs.g1_gen],
+            b_consts: vec![],
+            gamma: vec![],
+            target: F::pairing(crs.g1_gen, yvars[0]),
+        };
+        let xcoms = batch_commit_G1(&[], &crs, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof = try_prove_ppe(&equ, &[], &yvars, &xcoms, &ycoms, &crs, &mut rng).unwrap();
+        assert_eq!(verify_ppe(&equ, &[], &ycoms.coms, &proof, &crs), Ok(()));
+    }
+
+    #[test]
+    fn prover_rejects_scalar_commitments_for_group_variables() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let x = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let xvars = vec![x, x];
+        let equ = PPE::<F>::equality_G1(&crs);
+        // Commitments to scalars only carry one column of randomness
+        let scalar_xcoms = batch_commit_scalar_to_B1(&[Fr::one(), Fr::one()], &crs, &mut rng);
+        let ycoms = batch_commit_G2(&[], &crs, &mut rng);
+        assert_eq!(
+            try_prove_ppe(&equ, &xvars, &[], &scalar_xcoms, &ycoms, &crs, &mut rng),
+            Err(ProverError::DimensionMismatch {
+                variable: "xcoms randomness row",
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn prover_reports_dimension_mismatch() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, b_1) * e(X_2, b_2) * e(a_1, Y_1) * e(X_1, Y_1)^g = t with m = 2 and n = 1
+        let xvars: Vec<G1Affine> = vec![
+            crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(),
+            crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![
+            crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
+            crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine(),
+        ];
+        let gamma: Matrix<Fr> = vec![vec![Fr::rand(&mut rng)], vec![Fr::zero()]];
+        let target: GT = F::pairing(xvars[0], b_consts[0])
+            + F::pairing(xvars[1], b_consts[1])
+            + F::pairing(a_consts[0], yvars[0])
+            + F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine());
+        let equ = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+        let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+
+        let proof = try_prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng).unwrap();
+        assert!(verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs).is_ok());
+
+        // Too few X variables and commitments
+        let few_xcoms = batch_commit_G1(&xvars[..1], &crs, &mut rng);
+        assert_eq!(
+            try_prove_ppe(&equ, &xvars[..1], &yvars, &xcoms, &ycoms, &crs, &mut rng),
+            Err(ProverError::DimensionMismatch {
+                variable: "xvars",
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            try_prove_ppe(&equ, &xvars, &yvars, &few_xcoms, &ycoms, &crs, &mut rng),
+            Err(ProverError::DimensionMismatch {
+                variable: "xcoms",
+                expected: 2,
+                found: 1
+            })
+        );
+
+        // Too many Y variables
+        let many_yvars = vec![yvars[0], yvars[0]];
+        let many_ycoms = batch_commit_G2(&many_yvars, &crs, &mut rng);
+        assert_eq!(
+            try_prove_ppe(
+                &equ,
+                &xvars,
+                &many_yvars,
+                &xcoms,
+                &many_ycoms,
+                &crs,
+                &mut rng
+            ),
+            Err(ProverError::DimensionMismatch {
+                variable: "yvars",
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            try_prove_ppe(&equ, &xvars, &yvars, &xcoms, &many_ycoms, &crs, &mut rng),
+            Err(ProverError::DimensionMismatch {
+                variable: "ycoms",
+                expected: 1,
+                found: 2
+            })
+        );
+
+        // Wrong shape of Gamma
+        let mut bad_equ = equ.clone();
+        bad_equ.gamma.pop();
+        assert_eq!(
+            try_prove_ppe(&bad_equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng),
+            Err(ProverError::DimensionMismatch {
+                variable: "gamma",
+                expected: 2,
+                found: 1
+            })
+        );
+        let mut bad_equ = equ.clone();
+        bad_equ.gamma[1].push(Fr::one());
+        let err = try_prove_ppe(&bad_equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        assert_eq!(
+            err,
+            Err(ProverError::DimensionMismatch {
+                variable: "gamma row",
+                expected: 1,
+                found: 2
+            })
+        );
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "gamma row has 2 entries, but the equation requires 1"
+        );
+    }
+
+    #[test]
+    fn tautology_and_contradiction() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let tautology = PPE::<F>::tautology(2, 1);
+        let contradiction = PPE::<F>::contradiction(2, 1);
+
+        for _ in 0..3 {
+            let xvars: Vec<G1Affine> = (0..2)
+                .map(|_| crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine())
+                .collect();
+            let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+            let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+            let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+
+            assert!(tautology.is_satisfied(&xvars, &yvars));
+            let proof = prove_ppe(&tautology, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+            assert_eq!(
+                verify_ppe(&tautology, &xcoms.coms, &ycoms.coms, &proof, &crs),
+                Ok(())
+            );
+
+            assert!(!contradiction.is_satisfied(&xvars, &yvars));
+            let proof = prove_ppe(
+                &contradiction,
+                &xvars,
+                &yvars,
+                &xcoms,
+                &ycoms,
+                &crs,
+                &mut rng,
+            );
+            assert_eq!(
+                verify_ppe(&contradiction, &xcoms.coms, &ycoms.coms, &proof, &crs),
+                Err(VerificationError::PairingMismatch { equation_index: 0 })
+            );
+        }
+    }
+
+    #[test]
+    fn checked_prover_catches_unsatisfied_witness() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, b_1) * e(a_1, Y_1) * e(X_1, Y_1)^g = t
+        let xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let yvars: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let a_consts: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let b_consts: Vec<G2Affine> = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let gamma: Matrix<Fr> = vec![vec![Fr::rand(&mut rng)]];
+        let target: GT = F::pairing(xvars[0], b_consts[0])
+            + F::pairing(a_consts[0], yvars[0])
+            + F::pairing(xvars[0], yvars[0].mul(gamma[0][0]).into_affine());
+        let equ = PPE::<F> {
+            a_consts,
+            b_consts,
+            gamma,
+            target,
+        };
+        assert!(equ.is_satisfied(&xvars, &yvars));
+        assert!(!equ.is_satisfied(&xvars, &[]));
+
+        let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof =
+            prove_ppe_checked(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng).unwrap();
+        assert!(verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs).is_ok());
+
+        // A different X_1 no longer satisfies the equation and is caught before proving
+        let bad_xvars: Vec<G1Affine> = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let bad_xcoms = batch_commit_G1(&bad_xvars, &crs, &mut rng);
+        assert!(!equ.is_satisfied(&bad_xvars, &yvars));
+        let err = prove_ppe_checked(&equ, &bad_xvars, &yvars, &bad_xcoms, &ycoms, &crs, &mut rng);
+        assert_eq!(err, Err(ProverError::Unsatisfied));
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            "the witness does not satisfy the equation"
+        );
+    }
+
+    #[test]
+    fn verifier_reports_malformed_commitment() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let p = random_ppe_proof(&crs, &mut rng);
+
+        // A point on the curve outside of the prime-order subgroup
+        let outside = (1u64..)
+            .filter_map(|x| G1Affine::get_point_from_x_unchecked(Fq::from(x), false))
+            .find(|pt| !pt.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        let mut xcoms = p.xcoms.clone();
+        xcoms[0].1 = outside;
+        assert_eq!(
+            verify_ppe(&p.equ, &xcoms, &p.ycoms, &p.proof, &crs),
+            Err(VerificationError::CommitmentMalformed)
+        );
+        let mut proof = p.proof.clone();
+        proof.theta[1].0 = outside;
+        assert_eq!(
+            verify_ppe(&p.equ, &p.xcoms, &p.ycoms, &proof, &crs),
+            Err(VerificationError::CommitmentMalformed)
+        );
+    }
+
+    #[test]
+    fn verifier_reports_failing_equation_index() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let p = random_ppe_proof(&crs, &mut rng);
+
+        // Two equations over the same variables, the second claiming the wrong target
+        let mut bad_equ = p.equ.clone();
+        bad_equ.target += crs.gt_gen;
+        let equs = vec![p.equ.clone(), bad_equ];
+        let proofs = vec![p.proof.clone(), p.proof.clone()];
+        assert_eq!(
+            verify_ppe_system(&equs[..1], &p.xcoms, &p.ycoms, &proofs[..1], &crs),
+            Ok(())
+        );
+        assert_eq!(
+            verify_ppe_system(&equs, &p.xcoms, &p.ycoms, &proofs, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 1 })
+        );
+        assert_eq!(
+            verify_ppe_system(&equs, &p.xcoms, &p.ycoms, &proofs[..1], &crs),
+            Err(VerificationError::DimensionMismatch)
+        );
+    }
+
+    // The verification equation of a PPE, evaluated with a full pairing per term
+    fn naive_verify_ppe(p: &ProofWithStatement<F>, crs: &CRS<F>) -> bool {
+        let lin_a_com_y =
+            ComT::<F>::pairing_sum(&Com1::<F>::batch_linear_map(&p.equ.a_consts), &p.ycoms);
+        let com_x_lin_b =
+            ComT::<F>::pairing_sum(&p.xcoms, &Com2::<F>::batch_linear_map(&p.equ.b_consts));
+        let stmt_com_y = vec_to_col_vec(&p.ycoms).left_mul(&p.equ.gamma, false);
+        let com_x_stmt_com_y = ComT::<F>::pairing_sum(&p.xcoms, &col_vec_to_vec(&stmt_com_y));
+        let lhs = lin_a_com_y + com_x_lin_b + com_x_stmt_com_y;
+        let rhs = ComT::<F>::linear_map_PPE(&p.equ.target)
+            + ComT::<F>::pairing_sum(&crs.u, &p.proof.pi)
+            + ComT::<F>::pairing_sum(&p.proof.theta, &crs.v);
+        lhs == rhs
+    }
+
+    #[test]
+    fn verify_ppe_matches_naive_verification() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let mut proofs: Vec<ProofWithStatement<F>> =
+            (0..4).map(|_| random_ppe_proof(&crs, &mut rng)).collect();
+        proofs[1].equ.target += crs.gt_gen;
+        proofs[2].proof.pi[0] = proofs[2].proof.pi[0] + proofs[2].proof.pi[1];
+        proofs[3].xcoms.swap(0, 1);
+
+        for (i, p) in proofs.iter().enumerate() {
+            let verdict = verify_ppe_bool(&p.equ, &p.xcoms, &p.ycoms, &p.proof, &crs);
+            assert_eq!(verdict, naive_verify_ppe(p, &crs));
+            assert_eq!(verdict, i == 0);
+        }
+    }
+
+    #[test]
+    fn serialized_proof_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let p = random_ppe_proof(&crs, &mut rng);
+
+        let mut c_bytes = Vec::new();
+        p.proof.serialize_compressed(&mut c_bytes).unwrap();
+        assert_eq!(c_bytes.len(), p.proof.compressed_size());
+        let mut u_bytes = Vec::new();
+        p.proof.serialize_uncompressed(&mut u_bytes).unwrap();
+        assert_eq!(u_bytes.len(), p.proof.uncompressed_size());
+        assert!(c_bytes.len() < u_bytes.len());
+
+        let proof_de = EquProof::<F>::deserialize_compressed(&c_bytes[..]).unwrap();
+        assert_eq!(
+            verify_ppe(&p.equ, &p.xcoms, &p.ycoms, &proof_de, &crs),
+            Ok(())
+        );
+        let proof_de = EquProof::<F>::deserialize_uncompressed(&u_bytes[..]).unwrap();
+        assert_eq!(
+            verify_ppe(&p.equ, &p.xcoms, &p.ycoms, &proof_de, &crs),
+            Ok(())
+        );
+
+        // Truncated proofs are rejected
+        assert!(EquProof::<F>::deserialize_compressed(&c_bytes[..c_bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn serialized_proof_omits_proof_randomness() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let x = Fr::rand(&mut rng);
+        let xvars = vec![crs.g1_gen.mul(x).into_affine()];
+        let yvars = vec![crs.g2_gen.mul(x).into_affine()];
+        let equ = PPE::<F>::equality_G1_G2(&crs);
+        let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+
+        // (π, θ) of 2 + 2 compressed Com2, Com1 with their length prefixes, and the type, but no T
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), (8 + 2 * 2 * 96) + (8 + 2 * 2 * 48) + 1);
+
+        // The deserialized proof has the same public part, and can still be re-randomized
+        let proof_de = EquProof::<F>::deserialize_compressed(&bytes[..]).unwrap();
+        assert_eq!(proof_de, proof);
+        let (xcoms_re, ycoms_re, proof_re) =
+            rerandomize_proof(&equ, &xcoms.coms, &ycoms.coms, &proof_de, &crs, &mut rng).unwrap();
+        assert_eq!(
+            verify_ppe(&equ, &xcoms_re, &ycoms_re, &proof_re, &crs),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn predicted_proof_size_matches_serialized_size() {
+        use ark_serialize::Compress;
+
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let (m, n) = (3, 2);
+        let gamma: Matrix<Fr> = (0..m)
+            .map(|_| (0..n).map(|_| Fr::rand(&mut rng)).collect())
+            .collect();
+        let g1s = |k: usize, rng: &mut _| (0..k).map(|_| G1Affine::rand(rng)).collect::<Vec<_>>();
+        let g2s = |k: usize, rng: &mut _| (0..k).map(|_| G2Affine::rand(rng)).collect::<Vec<_>>();
+        let frs = |k: usize, rng: &mut _| (0..k).map(|_| Fr::rand(rng)).collect::<Vec<_>>();
+
+        fn check<E: Equation<F, A1, A2, AT>, A1, A2, AT>(
+            equ: &E,
+            proof: &CProof<F>,
+            m: usize,
+            n: usize,
+        ) {
+            assert_eq!(equ.commitment_count(), (m, n));
+            assert_eq!(proof.xcoms.coms.len(), m);
+            assert_eq!(proof.ycoms.coms.len(), n);
+            for compress in [Compress::Yes, Compress::No] {
+                assert_eq!(
+                    equ.proof_size_bytes(compress),
+                    proof.equ_proofs[0].serialized_size(compress)
+                );
+            }
+        }
+
+        let equ = PPE::<F> {
+            a_consts: g1s(n, &mut rng),
+            b_consts: g2s(m, &mut rng),
+            gamma: gamma.clone(),
+            target: GT::rand(&mut rng),
+        };
+        let proof = equ.commit_and_prove(&g1s(m, &mut rng), &g2s(n, &mut rng), &crs, &mut rng);
+        check(&equ, &proof, m, n);
+
+        let equ = MSMEG1::<F> {
+            a_consts: g1s(n, &mut rng),
+            b_consts: frs(m, &mut rng),
+            gamma: gamma.clone(),
+            target: G1Affine::rand(&mut rng),
+        };
+        let proof = equ.commit_and_prove(&g1s(m, &mut rng), &frs(n, &mut rng), &crs, &mut rng);
+        check(&equ, &proof, m, n);
+
+        let equ = MSMEG2::<F> {
+            a_consts: frs(n, &mut rng),
+            b_consts: g2s(m, &mut rng),
+            gamma: gamma.clone(),
+            target: G2Affine::rand(&mut rng),
+        };
+        let proof = equ.commit_and_prove(&frs(m, &mut rng), &g2s(n, &mut rng), &crs, &mut rng);
+        check(&equ, &proof, m, n);
+
+        let equ = QuadEqu::<F> {
+            a_consts: frs(n, &mut rng),
+            b_consts: frs(m, &mut rng),
+            gamma,
+            target: Fr::rand(&mut rng),
+        };
+        let proof = equ.commit_and_prove(&frs(m, &mut rng), &frs(n, &mut rng), &crs, &mut rng);
+        check(&equ, &proof, m, n);
+    }
+
+    #[test]
+    fn rerandomized_proof_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let p = random_ppe_proof(&crs, &mut rng);
+
+        let (xcoms, ycoms, proof) =
+            rerandomize_proof(&p.equ, &p.xcoms, &p.ycoms, &p.proof, &crs, &mut rng).unwrap();
+        assert_eq!(verify_ppe(&p.equ, &xcoms, &ycoms, &proof, &crs), Ok(()));
+        assert_ne!(xcoms, p.xcoms);
+        assert_ne!(ycoms, p.ycoms);
+
+        let mut bytes = Vec::new();
+        p.proof.serialize_compressed(&mut bytes).unwrap();
+        let mut new_bytes = Vec::new();
+        proof.serialize_compressed(&mut new_bytes).unwrap();
+        assert_ne!(bytes, new_bytes);
+
+        // Re-randomizing again still verifies
+        let (xcoms, ycoms, proof) =
+            rerandomize_proof(&p.equ, &xcoms, &ycoms, &proof, &crs, &mut rng).unwrap();
+        assert_eq!(verify_ppe(&p.equ, &xcoms, &ycoms, &proof, &crs), Ok(()));
+
+        // ... but does not turn an invalid proof into a valid one
+        let mut bad_equ = p.equ.clone();
+        bad_equ.target += crs.gt_gen;
+        let (xcoms, ycoms, proof) =
+            rerandomize_proof(&bad_equ, &p.xcoms, &p.ycoms, &p.proof, &crs, &mut rng).unwrap();
+        assert_eq!(
+            verify_ppe(&bad_equ, &xcoms, &ycoms, &proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+    }
+
+    #[test]
+    fn rerandomized_one_sided_proofs_verify() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // X variables only, from the randomness-only equality prover
+        let xvar = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let (com_a, r_a) = commit_G1_with_opening(&xvar, &crs, &mut rng);
+        let (com_b, r_b) = commit_G1_with_opening(&xvar, &crs, &mut rng);
+        let proof = prove_commitment_equality(&com_a, &r_a, &com_b, &r_b, &crs, &mut rng).unwrap();
+        let equ = PPE::<F>::equality_G1(&crs);
+        let (xcoms, ycoms, proof) =
+            rerandomize_proof(&equ, &[com_a, com_b], &[], &proof, &crs, &mut rng).unwrap();
+        assert!(ycoms.is_empty());
+        assert_eq!(verify_ppe(&equ, &xcoms, &ycoms, &proof, &crs), Ok(()));
+
+        // Y variables only: e(P_1, Y_1) = t
+        let yvars = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let equ = PPE::<F> {
+            a_consts: vec![crs.g1_gen],
+            b_consts: vec![],
+            gamma: vec![],
+            target: F::pairing(crs.g1_gen, yvars[0]),
+        };
+        let xcoms = batch_commit_G1(&[], &crs, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof = prove_ppe(&equ, &[], &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        let (xcoms, ycoms_re, proof) =
+            rerandomize_proof(&equ, &[], &ycoms.coms, &proof, &crs, &mut rng).unwrap();
+        assert!(xcoms.is_empty());
+        assert_ne!(ycoms_re, ycoms.coms);
+        assert_eq!(verify_ppe(&equ, &xcoms, &ycoms_re, &proof, &crs), Ok(()));
+
+        // Commitments that do not fit the equation are rejected
+        assert_eq!(
+            rerandomize_proof(&equ, &[], &[], &proof, &crs, &mut rng),
+            Err(VerificationError::DimensionMismatch)
+        );
+    }
+
+    // An equation, the pairs its target is the product of, and a witness for it
+    type ZkStatement = (
+        PPE<F>,
+        Vec<(G1Affine, G2Affine)>,
+        Vec<G1Affine>,
+        Vec<G2Affine>,
+    );
+
+    // e(X_1, P_2) * e(P_1, Y_1) * e(X_2, Y_1) = e(A_1, B_1) * e(A_2, B_2) for random pairs
+    // (A_k, B_k), together with a witness for it
+    fn zero_knowledge_statement(crs: &CRS<F>, rng: &mut impl ark_std::rand::Rng) -> ZkStatement {
+        let (a1, b1, a2, b2) = (Fr::rand(rng), Fr::rand(rng), Fr::rand(rng), Fr::rand(rng));
+        let target: Vec<(G1Affine, G2Affine)> = vec![
+            (
+                crs.g1_gen.mul(a1).into_affine(),
+                crs.g2_gen.mul(b1).into_affine(),
+            ),
+            (
+                crs.g1_gen.mul(a2).into_affine(),
+                crs.g2_gen.mul(b2).into_affine(),
+            ),
+        ];
+        // x_1 + y_1 + x_2 y_1 = a_1 b_1 + a_2 b_2
+        let (x2, y1) = (Fr::rand(rng), Fr::rand(rng));
+        let x1 = a1 * b1 + a2 * b2 - y1 - x2 * y1;
+        let xvars = vec![
+            crs.g1_gen.mul(x1).into_affine(),
+            crs.g1_gen.mul(x2).into_affine(),
+        ];
+        let yvars = vec![crs.g2_gen.mul(y1).into_affine()];
+        let equ = PPE::<F> {
+            a_consts: vec![crs.g1_gen],
+            b_consts: vec![crs.g2_gen, G2Affine::zero()],
+            gamma: vec![vec![Fr::zero()], vec![Fr::one()]],
+            target: GtTarget::from_pairs(&target).into(),
+        };
+        (equ, target, xvars, yvars)
+    }
+
+    #[test]
+    fn simulated_ppe_proof_verifies() {
+        let mut rng = test_rng();
+        let (crs, trapdoor) = CRS::<F>::generate_hiding_with_trapdoor(&mut rng);
+        let (equ, target, xvars, yvars) = zero_knowledge_statement(&crs, &mut rng);
+
+        let real = prove_ppe_zk(&equ, &target, &xvars, &yvars, &crs, &mut rng).unwrap();
+        let simulated = simulate_ppe(&equ, &target, &trapdoor, &crs, &mut rng).unwrap();
+        assert_eq!(verify_ppe_zk(&equ, &target, &real, &crs), Ok(()));
+        assert_eq!(verify_ppe_zk(&equ, &target, &simulated, &crs), Ok(()));
+
+        // Both have the same shape, so the verifier cannot tell them apart by it
+        assert_eq!(real.xcoms.len(), simulated.xcoms.len());
+        assert_eq!(real.ycoms.len(), simulated.ycoms.len());
+        assert_eq!(real.target_coms.len(), simulated.target_coms.len());
+        assert_eq!(real.equ_proofs.len(), simulated.equ_proofs.len());
+        for (real, simulated) in real.equ_proofs.iter().zip(simulated.equ_proofs.iter()) {
+            assert_eq!(real.equ_type, simulated.equ_type);
+            assert_eq!(real.pi.len(), simulated.pi.len());
+            assert_eq!(real.theta.len(), simulated.theta.len());
+        }
+        assert_eq!(real.compressed_size(), simulated.compressed_size());
+
+        // Simulations are randomized
+        let simulated2 = simulate_ppe(&equ, &target, &trapdoor, &crs, &mut rng).unwrap();
+        assert_ne!(simulated.xcoms, simulated2.xcoms);
+        assert_ne!(simulated.target_coms, simulated2.target_coms);
+
+        // None of them verifies for another statement
+        let mut other_target = target.clone();
+        other_target[0].0 = crs.g1_gen;
+        let other_equ = equ.clone().with_target(GtTarget::from_pairs(&other_target));
+        assert!(verify_ppe_zk(&other_equ, &other_target, &real, &crs).is_err());
+        assert!(verify_ppe_zk(&other_equ, &other_target, &simulated, &crs).is_err());
+    }
+
+    #[test]
+    fn zero_knowledge_ppe_proof_is_sound_under_binding_CRS() {
+        let mut rng = test_rng();
+        let (crs, _) = CRS::<F>::generate_binding(&mut rng);
+        let (equ, target, xvars, yvars) = zero_knowledge_statement(&crs, &mut rng);
+
+        let proof = prove_ppe_zk(&equ, &target, &xvars, &yvars, &crs, &mut rng).unwrap();
+        assert_eq!(verify_ppe_zk(&equ, &target, &proof, &crs), Ok(()));
+
+        // A witness that does not satisfy the equation is caught
+        let zeros = vec![G1Affine::zero(); 2];
+        let proof = prove_ppe_zk(&equ, &target, &zeros, &[G2Affine::zero()], &crs, &mut rng);
+        assert_eq!(
+            verify_ppe_zk(&equ, &target, &proof.unwrap(), &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+    }
+
+    #[test]
+    fn simulation_requires_trapdoor_and_matching_target() {
+        let mut rng = test_rng();
+        let (crs, trapdoor) = CRS::<F>::generate_hiding_with_trapdoor(&mut rng);
+        let (_, other_trapdoor) = CRS::<F>::generate_hiding_with_trapdoor(&mut rng);
+        let (equ, target, xvars, yvars) = zero_knowledge_statement(&crs, &mut rng);
+
+        assert!(simulate_ppe(&equ, &target, &other_trapdoor, &crs, &mut rng).is_none());
+
+        // The pairs must multiply up to the target of the equation
+        let proof = simulate_ppe(&equ, &target, &trapdoor, &crs, &mut rng).unwrap();
+        assert!(simulate_ppe(&equ, &target[1..], &trapdoor, &crs, &mut rng).is_none());
+        assert!(prove_ppe_zk(&equ, &target[1..], &xvars, &yvars, &crs, &mut rng).is_none());
+        assert_eq!(
+            verify_ppe_zk(&equ, &target[1..], &proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+
+        // Without pairs, the target is the identity
+        let equ = equ.with_target(GT::zero());
+        let proof = simulate_ppe(&equ, &[], &trapdoor, &crs, &mut rng).unwrap();
+        assert!(proof.target_coms.is_empty());
+        assert_eq!(verify_ppe_zk(&equ, &[], &proof, &crs), Ok(()));
+    }
+
+    #[test]
+    fn commitment_equality_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_hiding(&mut rng);
+
+        let x = Fr::rand(&mut rng);
+        let xvar = crs.g1_gen.mul(x).into_affine();
+        let yvar = crs.g2_gen.mul(x).into_affine();
+        let (com_a, r_a) = commit_G1_with_opening(&xvar, &crs, &mut rng);
+        let (com_b, r_b) = commit_G1_with_opening(&xvar, &crs, &mut rng);
+        let (com_y, s) = commit_G2_with_opening(&yvar, &crs, &mut rng);
+
+        let proof = prove_commitment_equality(&com_a, &r_a, &com_b, &r_b, &crs, &mut rng).unwrap();
+        assert_eq!(
+            verify_commitment_equality(&com_a, &com_b, &proof, &crs),
+            Ok(())
+        );
+
+        let proof =
+            prove_commitment_equality_G1_G2(&com_a, &r_a, &com_y, &s, &crs, &mut rng).unwrap();
+        assert_eq!(
+            verify_commitment_equality_G1_G2(&com_a, &com_y, &proof, &crs),
+            Ok(())
+        );
+
+        // The generic prover handles the equation, which has no Y variables, as well
+        let xvars = vec![xvar, xvar];
+        let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms = batch_commit_G2(&[], &crs, &mut rng);
+        let equ = PPE::<F>::equality_G1(&crs);
+        let proof = prove_ppe(&equ, &xvars, &[], &xcoms, &ycoms, &crs, &mut rng);
+        assert_eq!(
+            verify_commitment_equality(&xcoms.coms[0], &xcoms.coms[1], &proof, &crs),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn commitment_equality_rejects_distinct_values() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let x = Fr::rand(&mut rng);
+        let xvar = crs.g1_gen.mul(x).into_affine();
+        let other = crs.g1_gen.mul(x + Fr::one()).into_affine();
+        let (com_a, r_a) = commit_G1_with_opening(&xvar, &crs, &mut rng);
+        let (com_b, r_b) = commit_G1_with_opening(&xvar, &crs, &mut rng);
+        let (com_c, r_c) = commit_G1_with_opening(&other, &crs, &mut rng);
+        let (com_y, s) = commit_G2_with_opening(&crs.g2_gen.mul(x).into_affine(), &crs, &mut rng);
+
+        // The prover refuses to prove a false statement
+        assert!(prove_commitment_equality(&com_a, &r_a, &com_c, &r_c, &crs, &mut rng).is_none());
+        assert!(
+            prove_commitment_equality_G1_G2(&com_c, &r_c, &com_y, &s, &crs, &mut rng).is_none()
+        );
+
+        // A proof for equal values does not carry over to a commitment to another value
+        let proof = prove_commitment_equality(&com_a, &r_a, &com_b, &r_b, &crs, &mut rng).unwrap();
+        assert_eq!(
+            verify_commitment_equality(&com_a, &com_c, &proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+        let proof =
+            prove_commitment_equality_G1_G2(&com_a, &r_a, &com_y, &s, &crs, &mut rng).unwrap();
+        assert!(verify_commitment_equality_G1_G2(&com_c, &com_y, &proof, &crs).is_err());
+    }
+
+    // A PPE e(X_1, Y_1) = t, a quadratic equation x_1 * y_1 = t' and the linking MSME
+    // y_1 g1 - X_1 = 0 over the shared scalar y_1 = w with X_1 = w g1
+    fn linked_system(crs: &CRS<F>, witness: &SystemWitness<F>) -> EquationSystem<F> {
+        let mut system = EquationSystem::new();
+        system
+            .push(PPE::<F> {
+                a_consts: vec![G1Affine::zero()],
+                b_consts: vec![G2Affine::zero()],
+                gamma: vec![vec![Fr::one()]],
+                target: F::pairing(witness.xvars[0], witness.yvars[0]),
+            })
+            .push(MSMEG1::<F> {
+                a_consts: vec![crs.g1_gen],
+                b_consts: vec![-Fr::one()],
+                gamma: vec![vec![Fr::zero()]],
+                target: G1Affine::zero(),
+            })
+            .push(QuadEqu::<F> {
+                a_consts: vec![Fr::zero()],
+                b_consts: vec![Fr::zero()],
+                gamma: vec![vec![Fr::one()]],
+                target: witness.scalar_xvars[0] * witness.scalar_yvars[0],
+            });
+        system
+    }
+
+    #[test]
+    fn equation_system_with_shared_scalar_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let w = Fr::rand(&mut rng);
+        let witness = SystemWitness::<F> {
+            xvars: vec![crs.g1_gen.mul(w).into_affine()],
+            yvars: vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()],
+            scalar_xvars: vec![Fr::rand(&mut rng)],
+            scalar_yvars: vec![w],
+        };
+        let system = linked_system(&crs, &witness);
+
+        let proof = prove_system(&system, &witness, &crs, &mut rng);
+        // Every variable is committed to once, whichever equations use it
+        assert_eq!(proof.xcoms.len(), 1);
+        assert_eq!(proof.scalar_ycoms.len(), 1);
+        assert_eq!(proof.equ_proofs.len(), 3);
+        assert!(verify_system(&system, &proof, &crs));
+        assert_eq!(
+            verify_system_detailed(&system, &proof, &crs),
+            vec![Ok(()); 3]
+        );
+    }
+
+    #[test]
+    fn two_phase_system_proof_matches_one_shot() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let w = Fr::rand(&mut rng);
+        let witness = SystemWitness::<F> {
+            xvars: vec![crs.g1_gen.mul(w).into_affine()],
+            yvars: vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()],
+            scalar_xvars: vec![Fr::rand(&mut rng)],
+            scalar_yvars: vec![w],
+        };
+
+        // The commitments are fixed before the equations are known
+        let mut two_phase_rng = test_rng();
+        let commitments = commit_witness(&witness, &crs, &mut two_phase_rng);
+        let system = linked_system(&crs, &witness);
+        let proof =
+            prove_with_commitments(&system, &witness, &commitments, &crs, &mut two_phase_rng);
+        assert_eq!(proof.xcoms, commitments.xcoms.coms);
+        assert_eq!(proof.scalar_ycoms, commitments.scalar_ycoms.coms);
+        assert!(verify_system(&system, &proof, &crs));
+
+        // With the same randomness, the one-shot prover computes the same proof
+        let one_shot = prove_system(&system, &witness, &crs, &mut test_rng());
+        assert_eq!(one_shot, proof);
+    }
+
+    #[test]
+    fn witness_commit_all_matches_batch_commits() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let xvars = vec![crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine(); 2];
+        let yvars = vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()];
+        let scalar_xvars = vec![Fr::rand(&mut rng); 3];
+        let scalar_yvars = vec![Fr::rand(&mut rng)];
+        let witness = SystemWitness::<F>::from((
+            xvars.clone(),
+            yvars.clone(),
+            scalar_xvars.clone(),
+            scalar_yvars.clone(),
+        ));
+        assert_eq!(witness.xvars, xvars);
+        assert_eq!(witness.scalar_yvars, scalar_yvars);
+
+        let commitments = witness.commit_all(&crs, &mut test_rng());
+        let mut batch_rng = test_rng();
+        assert_eq!(
+            commitments,
+            SystemCommitments {
+                xcoms: batch_commit_G1(&xvars, &crs, &mut batch_rng),
+                ycoms: batch_commit_G2(&yvars, &crs, &mut batch_rng),
+                scalar_xcoms: batch_commit_scalar_to_B1(&scalar_xvars, &crs, &mut batch_rng),
+                scalar_ycoms: batch_commit_scalar_to_B2(&scalar_yvars, &crs, &mut batch_rng),
+            }
+        );
+        assert_eq!(commitments.xcoms.openings().len(), 2);
+        assert_eq!(commitments.scalar_xcoms.openings().len(), 3);
+    }
+
+    #[test]
+    fn equation_system_reports_violated_equation() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // X_1 is not w g1, so only the linking equation is violated
+        let w = Fr::rand(&mut rng);
+        let witness = SystemWitness::<F> {
+            xvars: vec![crs.g1_gen.mul(w + Fr::one()).into_affine()],
+            yvars: vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()],
+            scalar_xvars: vec![Fr::rand(&mut rng)],
+            scalar_yvars: vec![w],
+        };
+        let system = linked_system(&crs, &witness);
+        let proof = prove_system(&system, &witness, &crs, &mut rng);
+
+        assert!(!verify_system(&system, &proof, &crs));
+        assert_eq!(
+            verify_system_detailed(&system, &proof, &crs),
+            vec![
+                Ok(()),
+                Err(VerificationError::PairingMismatch { equation_index: 1 }),
+                Ok(()),
+            ]
+        );
+
+        // Without one proof per equation, no equation can be checked
+        let mut short_proof = proof.clone();
+        short_proof.equ_proofs.pop();
+        assert_eq!(
+            verify_system_detailed(&system, &short_proof, &crs),
+            vec![Err(VerificationError::DimensionMismatch); 3]
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_system_verification_matches_sequential() {
+        use groth_sahai::verifier::verify_system_parallel;
+
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let w = Fr::rand(&mut rng);
+        for xvar in [crs.g1_gen.mul(w), crs.g1_gen.mul(w + Fr::one())] {
+            let witness = SystemWitness::<F> {
+                xvars: vec![xvar.into_affine()],
+                yvars: vec![crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine()],
+                scalar_xvars: vec![Fr::rand(&mut rng)],
+                scalar_yvars: vec![w],
+            };
+            let system = linked_system(&crs, &witness);
+            let proof = prove_system(&system, &witness, &crs, &mut rng);
+            assert_eq!(
+                verify_system_parallel(&system, &proof, &crs),
+                verify_system(&system, &proof, &crs)
+            );
+
+            let mut short_proof = proof.clone();
+            short_proof.equ_proofs.pop();
+            assert!(!verify_system_parallel(&system, &short_proof, &crs));
+        }
+    }
+
+    #[test]
+    fn dlog_proof_verifies() {
+        let mut rng = test_rng();
+        for crs in [
+            CRS::<F>::generate_crs(&mut rng),
+            CRS::<F>::generate_hiding(&mut rng).into_inner(),
+        ] {
+            let x = Fr::rand(&mut rng);
+            let (r, s) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+
+            let base1 = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+            let point1 = base1.mul(x).into_affine();
+            let com2 = commit_scalar_to_B2_with_randomness(&x, &crs, s);
+            let proof = prove_dlog_G1(&base1, &s, &crs, &mut rng);
+            assert_eq!(verify_dlog_G1(&base1, &point1, &com2, &proof, &crs), Ok(()));
+
+            let base2 = crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine();
+            let point2 = base2.mul(x).into_affine();
+            let com1 = commit_scalar_to_B1_with_randomness(&x, &crs, r);
+            let proof = prove_dlog_G2(&base2, &r, &crs, &mut rng);
+            assert_eq!(verify_dlog_G2(&base2, &point2, &com1, &proof, &crs), Ok(()));
+        }
+    }
+
+    #[test]
+    fn dlog_proof_rejects_other_point() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        let x = Fr::rand(&mut rng);
+        let (r, s) = (Fr::rand(&mut rng), Fr::rand(&mut rng));
+        let base1 = crs.g1_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let base2 = crs.g2_gen.mul(Fr::rand(&mut rng)).into_affine();
+        let com2 = commit_scalar_to_B2_with_randomness(&x, &crs, s);
+        let com1 = commit_scalar_to_B1_with_randomness(&x, &crs, r);
+
+        // The committed scalar is not the discrete logarithm of these points
+        let point1 = base1.mul(x + Fr::one()).into_affine();
+        let point2 = base2.mul(x + Fr::one()).into_affine();
+        let proof = prove_dlog_G1(&base1, &s, &crs, &mut rng);
+        assert_eq!(
+            verify_dlog_G1(&base1, &point1, &com2, &proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+        let proof = prove_dlog_G2(&base2, &r, &crs, &mut rng);
+        assert_eq!(
+            verify_dlog_G2(&base2, &point2, &com1, &proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+
+        // Neither does a proof with the wrong randomness verify
+        let proof = prove_dlog_G1(&base1, &r, &crs, &mut rng);
+        let point1 = base1.mul(x).into_affine();
+        assert!(verify_dlog_G1(&base1, &point1, &com2, &proof, &crs).is_err());
+    }
+
+    #[test]
+    fn set_membership_proof_verifies() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let set: Vec<Fr> = [3u64, 5, 8, 13].iter().map(|&s| Fr::from(s)).collect();
+
+        for x in &set {
+            let r = Fr::rand(&mut rng);
+            let com = commit_scalar_to_B1_with_randomness(x, &crs, r);
+            let proof = prove_set_membership(x, &r, &set, &crs, &mut rng).unwrap();
+            assert_eq!(proof.partial_coms.len(), set.len());
+            assert_eq!(verify_set_membership(&com, &set, &proof, &crs), Ok(()));
+
+            // The proof is bound to the commitment and to the set
+            let other = commit_scalar_to_B1_with_randomness(x, &crs, Fr::rand(&mut rng));
+            assert!(verify_set_membership(&other, &set, &proof, &crs).is_err());
+            let mut shifted = set.clone();
+            shifted[0] += Fr::one();
+            assert!(verify_set_membership(&com, &shifted, &proof, &crs).is_err());
+        }
+    }
+
+    #[test]
+    fn set_membership_of_singleton_and_duplicates() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let x = Fr::from(7u64);
+        let r = Fr::rand(&mut rng);
+        let com = commit_scalar_to_B1_with_randomness(&x, &crs, r);
+
+        // A singleton set states that x is the element itself
+        let proof = prove_set_membership(&x, &r, &[x], &crs, &mut rng).unwrap();
+        assert_eq!(verify_set_membership(&com, &[x], &proof, &crs), Ok(()));
+        assert!(prove_set_membership(&Fr::from(8u64), &r, &[x], &crs, &mut rng).is_none());
+
+        // Duplicates are removed, so the proof does not depend on them or on the order
+        let set = [Fr::from(9u64), x, Fr::from(9u64), x];
+        let proof = prove_set_membership(&x, &r, &set, &crs, &mut rng).unwrap();
+        assert_eq!(proof.partial_coms.len(), 2);
+        assert_eq!(verify_set_membership(&com, &set, &proof, &crs), Ok(()));
+        let reordered = [x, Fr::from(9u64)];
+        assert_eq!(
+            verify_set_membership(&com, &reordered, &proof, &crs),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn set_membership_rejects_non_members() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+        let set: Vec<Fr> = [3u64, 5, 8].iter().map(|&s| Fr::from(s)).collect();
+        let (x, r) = (Fr::from(4u64), Fr::rand(&mut rng));
+        let com = commit_scalar_to_B1_with_randomness(&x, &crs, r);
+        assert!(prove_set_membership(&x, &r, &set, &crs, &mut rng).is_none());
+        assert!(prove_set_membership(&x, &r, &[], &crs, &mut rng).is_none());
+
+        // A proof for a member does not carry over to the commitment of a non-member
+        let member = Fr::from(5u64);
+        let proof = prove_set_membership(&member, &r, &set, &crs, &mut rng).unwrap();
+        assert_eq!(
+            verify_set_membership(&com, &set, &proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+        assert_eq!(
+            verify_set_membership(&com, &[], &proof, &crs),
+            Err(VerificationError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn blinded_ppe_verification_matches_direct() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(g1, Y_1) * e(X_1, g2) * e(X_1, Y_1) = t for X_1 = 2 g1 and Y_1 = 3 g2
+        let xvars = vec![crs.g1_gen.mul(Fr::from(2u64)).into_affine()];
+        let yvars = vec![crs.g2_gen.mul(Fr::from(3u64)).into_affine()];
+        let equ = PPE::<F> {
+            a_consts: vec![crs.g1_gen],
+            b_consts: vec![crs.g2_gen],
+            gamma: vec![vec![Fr::one()]],
+            target: crs.gt_gen * Fr::from(3 + 2 + 6u64),
+        };
+        let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+
+        let (blinded, key) =
+            blind_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs, &mut rng).unwrap();
+        // Neither the commitments nor the image of a constant are sent in the clear
+        assert!(!blinded.lhs.contains(&xcoms.coms[0]));
+        assert!(!blinded.rhs.contains(&ycoms.coms[0]));
+        assert!(!blinded.lhs.contains(&Com1::<F>::linear_map(&crs.g1_gen)));
+        let result = blinded.evaluate();
+        assert_eq!(unblind(key.clone(), &result), Ok(()));
+        assert_eq!(
+            verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs),
+            Ok(())
+        );
+
+        // Fresh blinding factors give an unrelated request, and a dishonest helper returning the
+        // unblinded target is caught
+        let (reblinded, _) =
+            blind_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs, &mut rng).unwrap();
+        assert_ne!(reblinded, blinded);
+        assert!(unblind(key, &ComT::<F>::linear_map_PPE(&equ.target)).is_err());
+
+        // A false statement is rejected either way
+        let mut wrong = equ.clone();
+        wrong.target = crs.gt_gen;
+        let direct = verify_ppe(&wrong, &xcoms.coms, &ycoms.coms, &proof, &crs);
+        let (blinded, key) =
+            blind_ppe(&wrong, &xcoms.coms, &ycoms.coms, &proof, &crs, &mut rng).unwrap();
+        assert_eq!(unblind(key, &blinded.evaluate()), direct);
+        assert_eq!(
+            direct,
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+
+        // Malformed inputs are rejected before blinding
+        assert_eq!(
+            blind_ppe(&equ, &[], &ycoms.coms, &proof, &crs, &mut rng).err(),
+            Some(VerificationError::DimensionMismatch)
+        );
+    }
+
+    #[test]
+    fn blinded_ppe_verification_rejects_identity_result() {
+        let mut rng = test_rng();
+        let crs = CRS::<F>::generate_crs(&mut rng);
+
+        // e(X_1, P_2) * e(-P_1, Y_1) = 1 with a Y_1 that does not match X_1
+        let equ = PPE::<F>::equality_G1_G2(&crs);
+        let xvars = vec![crs.g1_gen.mul(Fr::from(2u64)).into_affine()];
+        let yvars = vec![crs.g2_gen.mul(Fr::from(3u64)).into_affine()];
+        let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        assert_eq!(
+            verify_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs),
+            Err(VerificationError::PairingMismatch { equation_index: 0 })
+        );
+
+        // A helper returning the identity, which is ρ ι_T(1), or the value of a single pair is
+        // caught, as is the honest result
+        let (blinded, key) =
+            blind_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs, &mut rng).unwrap();
+        assert!(unblind(key.clone(), &ComT::<F>::zero()).is_err());
+        for (c, d) in blinded.lhs.iter().zip(&blinded.rhs) {
+            assert!(unblind(key.clone(), &ComT::<F>::pairing(*c, *d)).is_err());
+        }
+        assert!(unblind(key, &blinded.evaluate()).is_err());
+
+        // For a matching Y_1, the honest result is accepted
+        let yvars = vec![crs.g2_gen.mul(Fr::from(2u64)).into_affine()];
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+        let proof = prove_ppe(&equ, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        let (blinded, key) =
+            blind_ppe(&equ, &xcoms.coms, &ycoms.coms, &proof, &crs, &mut rng).unwrap();
+        assert!(unblind(key.clone(), &ComT::<F>::zero()).is_err());
+        assert_eq!(unblind(key, &blinded.evaluate()), Ok(()));
+
+        // Without committed variables, the verifier checks the target itself
+        let (blinded, key) =
+            blind_ppe(&PPE::<F>::tautology(0, 0), &[], &[], &proof, &crs, &mut rng).unwrap();
+        assert!(blinded.lhs.is_empty() && blinded.rhs.is_empty());
+        assert_eq!(unblind(key, &blinded.evaluate()), Ok(()));
+        assert_eq!(
+            blind_ppe(
+                &PPE::<F>::contradiction(0, 0),
+                &[],
+                &[],
+                &proof,
+                &crs,
+                &mut rng
+            )
+            .err(),
+            Some(VerificationError::PairingMismatch { equation_index: 0 })
+        );
     }
 }
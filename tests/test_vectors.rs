@@ -0,0 +1,171 @@
+#![allow(non_snake_case)]
+
+//! Fixed test vectors for BLS12-381, stored in `tests/vectors/bls12_381.txt`.
+//!
+//! The CRS, the witness and all randomness are derived from pinned seeds, so the commitments and
+//! proofs must be reproduced bit-for-bit. All values are compressed arkworks serializations in hex.
+//! After an intentional change of the encoding or of how randomness is consumed, regenerate the
+//! vectors with `GS_UPDATE_TEST_VECTORS=1 cargo test --test test_vectors`.
+
+#[cfg(test)]
+mod test_vectors {
+
+    use std::collections::BTreeMap;
+    use std::fmt::Write as _;
+
+    use ark_bls12_381::Bls12_381 as F;
+    use ark_ec::pairing::Pairing;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use ark_std::ops::Mul;
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+    use groth_sahai::prover::*;
+    use groth_sahai::statement::{QuadEqu, PPE};
+    use groth_sahai::verifier::{verify_ppe, verify_quad};
+    use groth_sahai::{Com1, Com2, CRS};
+
+    type G1Affine = <F as Pairing>::G1Affine;
+    type G2Affine = <F as Pairing>::G2Affine;
+    type Fr = <F as Pairing>::ScalarField;
+
+    const VECTORS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/vectors/bls12_381.txt");
+    const CRS_SEED: [u8; 32] = [0x47; 32];
+    const RNG_SEED: [u8; 32] = [0x53; 32];
+
+    fn to_hex<T: CanonicalSerialize>(value: &T) -> String {
+        let mut bytes = Vec::new();
+        value.serialize_compressed(&mut bytes).unwrap();
+        bytes.iter().fold(String::new(), |mut hex, b| {
+            write!(hex, "{:02x}", b).unwrap();
+            hex
+        })
+    }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn g1(k: u64) -> G1Affine {
+        G1Affine::generator().mul(Fr::from(k)).into_affine()
+    }
+
+    fn g2(k: u64) -> G2Affine {
+        G2Affine::generator().mul(Fr::from(k)).into_affine()
+    }
+
+    // The statements and witnesses:
+    // e(X_1, 13 g2) * e(X_2, 17 g2) * e(19 g1, Y_1) * e(X_1, Y_1)^23 = t with X = [2 g1, 3 g1] and
+    // Y = [5 g2], and 29 y_1 + 31 x_1 + 37 x_1 y_1 = t' with x = [7] and y = [11]
+    fn statements() -> (PPE<F>, QuadEqu<F>) {
+        let ppe = PPE::<F> {
+            a_consts: vec![g1(19)],
+            b_consts: vec![g2(13), g2(17)],
+            gamma: vec![vec![Fr::from(23u64)], vec![Fr::from(0u64)]],
+            target: F::pairing(G1Affine::generator(), G2Affine::generator())
+                * Fr::from(2 * 13 + 3 * 17 + 19 * 5 + 2 * 5 * 23u64),
+        };
+        let quad = QuadEqu::<F> {
+            a_consts: vec![Fr::from(29u64)],
+            b_consts: vec![Fr::from(31u64)],
+            gamma: vec![vec![Fr::from(37u64)]],
+            target: Fr::from(29 * 11 + 31 * 7 + 37 * 7 * 11u64),
+        };
+        (ppe, quad)
+    }
+
+    // Recomputes every vector from the pinned seeds, in a fixed order of consuming randomness.
+    fn compute_vectors() -> BTreeMap<&'static str, String> {
+        let crs = CRS::<F>::from_seed(CRS_SEED);
+        let mut rng = ChaCha20Rng::from_seed(RNG_SEED);
+        let (ppe, quad) = statements();
+
+        let xvars = vec![g1(2), g1(3)];
+        let yvars = vec![g2(5)];
+        let scalar_xvars = vec![Fr::from(7u64)];
+        let scalar_yvars = vec![Fr::from(11u64)];
+
+        let xcoms = batch_commit_G1(&xvars, &crs, &mut rng);
+        let ycoms = batch_commit_G2(&yvars, &crs, &mut rng);
+        let scalar_xcoms = batch_commit_scalar_to_B1(&scalar_xvars, &crs, &mut rng);
+        let scalar_ycoms = batch_commit_scalar_to_B2(&scalar_yvars, &crs, &mut rng);
+        let ppe_proof = prove_ppe(&ppe, &xvars, &yvars, &xcoms, &ycoms, &crs, &mut rng);
+        let quad_proof = prove_quad(
+            &quad,
+            &scalar_xvars,
+            &scalar_yvars,
+            &scalar_xcoms,
+            &scalar_ycoms,
+            &crs,
+            &mut rng,
+        );
+
+        BTreeMap::from([
+            ("crs", to_hex(&crs)),
+            ("xcoms", to_hex(&xcoms.coms)),
+            ("ycoms", to_hex(&ycoms.coms)),
+            ("scalar_xcoms", to_hex(&scalar_xcoms.coms)),
+            ("scalar_ycoms", to_hex(&scalar_ycoms.coms)),
+            ("ppe_proof", to_hex(&ppe_proof)),
+            ("quad_proof", to_hex(&quad_proof)),
+        ])
+    }
+
+    fn stored_vectors() -> BTreeMap<String, String> {
+        std::fs::read_to_string(VECTORS_PATH)
+            .unwrap()
+            .lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (name, hex) = line.split_once(" = ").unwrap();
+                (name.to_string(), hex.to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reproduces_stored_vectors() {
+        let computed = compute_vectors();
+        if std::env::var_os("GS_UPDATE_TEST_VECTORS").is_some() {
+            let mut file = String::from("# Generated by tests/test_vectors.rs, do not edit\n");
+            for (name, hex) in &computed {
+                writeln!(file, "{} = {}", name, hex).unwrap();
+            }
+            std::fs::write(VECTORS_PATH, file).unwrap();
+        }
+
+        let stored = stored_vectors();
+        assert_eq!(stored.len(), computed.len());
+        for (name, hex) in &computed {
+            assert_eq!(
+                stored.get(*name),
+                Some(hex),
+                "test vector `{}` has changed",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn stored_vectors_verify() {
+        let stored = stored_vectors();
+        let bytes = |name: &str| from_hex(&stored[name]);
+        let (ppe, quad) = statements();
+
+        let crs = CRS::<F>::from_bytes(&bytes("crs")).unwrap();
+        let xcoms = Vec::<Com1<F>>::deserialize_compressed(&bytes("xcoms")[..]).unwrap();
+        let ycoms = Vec::<Com2<F>>::deserialize_compressed(&bytes("ycoms")[..]).unwrap();
+        let scalar_xcoms =
+            Vec::<Com1<F>>::deserialize_compressed(&bytes("scalar_xcoms")[..]).unwrap();
+        let scalar_ycoms =
+            Vec::<Com2<F>>::deserialize_compressed(&bytes("scalar_ycoms")[..]).unwrap();
+        let ppe_proof = EquProof::<F>::deserialize_compressed(&bytes("ppe_proof")[..]).unwrap();
+        let quad_proof = EquProof::<F>::deserialize_compressed(&bytes("quad_proof")[..]).unwrap();
+
+        assert!(verify_ppe(&ppe, &xcoms, &ycoms, &ppe_proof, &crs).is_ok());
+        assert!(verify_quad(&quad, &scalar_xcoms, &scalar_ycoms, &quad_proof, &crs).is_ok());
+    }
+}